@@ -1,13 +1,15 @@
-use types::{DecodedEvent, StructuredInstruction};
+use types::{DecodeReport, StructuredInstruction};
 use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
 
 pub trait InstructionParser {
-    fn new() -> Self;
     fn get_program_id(&self) -> &str;
     fn decode_instructions(
         &self,
         instructions: Vec<StructuredInstruction>,
-        account_keys: &Vec<String>,
+        account_keys: &[String],
         transaction: &SubscribeUpdateTransaction
-    ) -> Vec<DecodedEvent>;
+    ) -> DecodeReport;
+    /// Runs once every parser has decoded the transaction, so a parser can point its events
+    /// at events other parsers decoded from the instructions it invoked.
+    fn link_events(&self, _report: &mut DecodeReport) {}
 }
\ No newline at end of file
@@ -0,0 +1,352 @@
+//! `#[derive(Layout)]` generates a bounds-checked Borsh decoder for a struct whose fields are
+//! laid out back to back, so decoders declare their layout instead of tracking offsets.
+//!
+//! ```ignore
+//! #[derive(Layout)]
+//! #[layout(discriminator = [189, 219, 127, 211, 78, 230, 97, 238])]
+//! pub struct TradeEvent {
+//!     #[layout(pubkey)]
+//!     pub mint: String,
+//!     pub sol_amount: u64,
+//!     pub is_buy: bool,
+//!     /// Only logged by recent program versions.
+//!     #[layout(trailing)]
+//!     pub ix_name: Option<String>,
+//! }
+//! ```
+//!
+//! Supported field types are `u8`, `bool`, `u16`, `u32`, `u64`, `i32`, `i64`, `u128`,
+//! `String` (Borsh string, or a base58 public key with `#[layout(pubkey)]`), `[T; N]` and
+//! `Option<T>` (Borsh option, or with `#[layout(trailing)]` a field only decoded when bytes are
+//! left, for fields appended by later program versions). The generated code reads through
+//! `utils` and fails with `types::DecodeErrorKind`, so the deriving crate depends on both.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    Data, DeriveInput, Error, Expr, ExprArray, Fields, GenericArgument, PathArguments, Type,
+    parse_macro_input, spanned::Spanned,
+};
+
+#[proc_macro_derive(Layout, attributes(layout))]
+pub fn derive_layout(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+enum FieldKind {
+    Primitive { reader: &'static str, size: usize },
+    Pubkey,
+    String,
+    Bytes(Expr),
+    Array(Box<FieldKind>, Expr),
+    Option(Box<FieldKind>),
+    Trailing(Box<FieldKind>),
+}
+
+impl FieldKind {
+    /// Bytes the field takes at least.
+    fn min_len(&self) -> TokenStream2 {
+        match self {
+            FieldKind::Primitive { size, .. } => quote!(#size),
+            FieldKind::Pubkey => quote!(32usize),
+            FieldKind::String => quote!(4usize),
+            FieldKind::Bytes(len) => quote!((#len) as usize),
+            FieldKind::Array(inner, len) => {
+                let inner = inner.min_len();
+                quote!((#len) as usize * #inner)
+            }
+            FieldKind::Option(_) => quote!(1usize),
+            FieldKind::Trailing(_) => quote!(0usize),
+        }
+    }
+
+    /// Expression reading the field at `__offset` and moving `__offset` past it.
+    fn read(&self) -> TokenStream2 {
+        match self {
+            FieldKind::Primitive { reader, size } => {
+                let reader = syn::Ident::new(reader, proc_macro2::Span::call_site());
+                quote!({
+                    let __value = ::utils::#reader(__data, __offset)?;
+                    __offset += #size;
+                    __value
+                })
+            }
+            FieldKind::Pubkey => quote!({
+                let __value = ::utils::read_pubkey(__data, __offset)?;
+                __offset += 32;
+                __value
+            }),
+            FieldKind::String => quote!({
+                let (__value, __next_offset) = ::utils::read_string(__data, __offset)?;
+                __offset = __next_offset;
+                __value
+            }),
+            FieldKind::Bytes(len) => quote!({
+                let __len = (#len) as usize;
+                let __value = ::utils::read_bytes(__data, __offset, __len)?.try_into().unwrap();
+                __offset += __len;
+                __value
+            }),
+            FieldKind::Array(inner, len) => {
+                let read_inner = inner.read();
+                quote!({
+                    let mut __values = Vec::with_capacity((#len) as usize);
+                    for _ in 0..(#len) as usize {
+                        __values.push(#read_inner);
+                    }
+                    __values.try_into().unwrap_or_else(|_| unreachable!())
+                })
+            }
+            FieldKind::Option(inner) => {
+                let read_inner = inner.read();
+                quote!({
+                    let __tag = ::utils::read_u8(__data, __offset)?;
+                    __offset += 1;
+                    match __tag {
+                        0 => None,
+                        1 => Some(#read_inner),
+                        __variant => {
+                            return Err(::types::DecodeErrorKind::InvalidEnumVariant {
+                                offset: __offset - 1,
+                                variant: __variant as u32,
+                            });
+                        }
+                    }
+                })
+            }
+            FieldKind::Trailing(inner) => {
+                let read_inner = inner.read();
+                quote!(if __offset < __data.len() { Some(#read_inner) } else { None })
+            }
+        }
+    }
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let name = &input.ident;
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new(input.span(), "Layout needs a struct with named fields")),
+        },
+        _ => return Err(Error::new(input.span(), "Layout can only be derived for structs")),
+    };
+
+    let mut discriminator: Option<ExprArray> = None;
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("layout")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("discriminator") {
+                discriminator = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `discriminator = [..]`"))
+            }
+        })?;
+    }
+
+    let mut idents = Vec::new();
+    let mut reads = Vec::new();
+    let mut min_lens = Vec::new();
+    let mut seen_trailing = false;
+    for field in fields {
+        let (mut pubkey, mut trailing) = (false, false);
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("layout")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("pubkey") {
+                    pubkey = true;
+                    Ok(())
+                } else if meta.path.is_ident("trailing") {
+                    trailing = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `pubkey` or `trailing`"))
+                }
+            })?;
+        }
+        let kind = field_kind(&field.ty, pubkey, trailing)?;
+        if trailing {
+            seen_trailing = true;
+        } else if seen_trailing {
+            return Err(Error::new(
+                field.span(),
+                "only `#[layout(trailing)]` fields can follow a trailing field",
+            ));
+        }
+        idents.push(field.ident.clone().unwrap());
+        reads.push(kind.read());
+        min_lens.push(kind.min_len());
+    }
+
+    let discriminator_items = discriminator.map(|discriminator| {
+        let len = discriminator.elems.len();
+        quote! {
+            pub const DISCRIMINATOR: [u8; #len] = #discriminator;
+
+            /// Decodes `data` if it starts with `DISCRIMINATOR`.
+            pub fn decode_with_discriminator(
+                data: &[u8],
+            ) -> Result<Option<Self>, ::types::DecodeErrorKind> {
+                match data.strip_prefix(&Self::DISCRIMINATOR[..]) {
+                    Some(data) => Self::decode(data).map(Some),
+                    None => Ok(None),
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #name {
+            /// Bytes taken by the fields that are always present.
+            pub const MIN_LEN: usize = 0 #(+ #min_lens)*;
+
+            #discriminator_items
+
+            /// Decodes the fields from data starting right after the discriminator.
+            #[allow(unused_assignments, clippy::identity_op)]
+            pub fn decode(__data: &[u8]) -> Result<Self, ::types::DecodeErrorKind> {
+                if __data.len() < Self::MIN_LEN {
+                    return Err(::types::DecodeErrorKind::DataTooShort {
+                        offset: 0,
+                        needed: Self::MIN_LEN,
+                        len: __data.len(),
+                    });
+                }
+                let mut __offset: usize = 0;
+                #(let #idents = #reads;)*
+                Ok(Self { #(#idents),* })
+            }
+        }
+    })
+}
+
+fn field_kind(ty: &Type, pubkey: bool, trailing: bool) -> Result<FieldKind, Error> {
+    if trailing {
+        let inner = option_inner(ty)
+            .ok_or_else(|| Error::new(ty.span(), "`#[layout(trailing)]` fields must be an Option"))?;
+        return Ok(FieldKind::Trailing(Box::new(field_kind(inner, pubkey, false)?)));
+    }
+    if let Some(inner) = option_inner(ty) {
+        return Ok(FieldKind::Option(Box::new(field_kind(inner, pubkey, false)?)));
+    }
+    if let Type::Array(array) = ty {
+        if matches!(&*array.elem, Type::Path(elem) if elem.path.is_ident("u8")) {
+            if pubkey {
+                return Err(Error::new(ty.span(), "`#[layout(pubkey)]` fields must be a String"));
+            }
+            return Ok(FieldKind::Bytes(array.len.clone()));
+        }
+        let inner = field_kind(&array.elem, pubkey, false)?;
+        return Ok(FieldKind::Array(Box::new(inner), array.len.clone()));
+    }
+    let ident = match ty {
+        Type::Path(path) => path.path.get_ident().map(|ident| ident.to_string()),
+        _ => None,
+    };
+    let (reader, size) = match ident.as_deref() {
+        Some("String") if pubkey => return Ok(FieldKind::Pubkey),
+        Some("String") => return Ok(FieldKind::String),
+        Some("u8") => ("read_u8", 1),
+        Some("bool") => ("read_bool", 1),
+        Some("u16") => ("read_u16", 2),
+        Some("u32") => ("read_u32", 4),
+        Some("u64") => ("read_u64", 8),
+        Some("i32") => ("read_i32", 4),
+        Some("i64") => ("read_i64", 8),
+        Some("u128") => ("read_u128", 16),
+        _ => return Err(Error::new(ty.span(), "unsupported layout field type")),
+    };
+    if pubkey {
+        return Err(Error::new(ty.span(), "`#[layout(pubkey)]` fields must be a String"));
+    }
+    Ok(FieldKind::Primitive { reader, size })
+}
+
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else { return None };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first()? {
+            GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! The derive cannot be applied inside its own crate, so these check what `expand` accepts;
+    //! decoding itself is tested on the layouts of the parsers.
+    use super::*;
+    use syn::parse_quote;
+
+    fn expand_error(input: DeriveInput) -> String {
+        match expand(&input) {
+            Ok(_) => panic!("expected the layout to be rejected"),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    #[test]
+    fn generates_discriminator_items_only_when_asked() {
+        let with: DeriveInput = parse_quote! {
+            #[layout(discriminator = [1, 2])]
+            struct Event { amount: u64 }
+        };
+        let without: DeriveInput = parse_quote! {
+            struct Args { amount: u64 }
+        };
+        assert!(expand(&with).unwrap().to_string().contains("decode_with_discriminator"));
+        assert!(!expand(&without).unwrap().to_string().contains("decode_with_discriminator"));
+    }
+
+    #[test]
+    fn leaves_trailing_fields_out_of_the_minimum_length() {
+        let kind = field_kind(&parse_quote!(Option<u64>), false, true).unwrap();
+        assert_eq!(kind.min_len().to_string(), quote!(0usize).to_string());
+        let kind = field_kind(&parse_quote!(Option<u64>), false, false).unwrap();
+        assert_eq!(kind.min_len().to_string(), quote!(1usize).to_string());
+    }
+
+    #[test]
+    fn rejects_a_field_after_a_trailing_one() {
+        let error = expand_error(parse_quote! {
+            struct Event {
+                #[layout(trailing)]
+                fee: Option<u64>,
+                amount: u64,
+            }
+        });
+        assert!(error.contains("can follow a trailing field"));
+    }
+
+    #[test]
+    fn rejects_a_trailing_field_that_is_not_an_option() {
+        let error = expand_error(parse_quote! {
+            struct Event {
+                #[layout(trailing)]
+                fee: u64,
+            }
+        });
+        assert!(error.contains("must be an Option"));
+    }
+
+    #[test]
+    fn rejects_a_pubkey_that_is_not_a_string() {
+        let error = expand_error(parse_quote! {
+            struct Event {
+                #[layout(pubkey)]
+                mint: [u8; 32],
+            }
+        });
+        assert!(error.contains("must be a String"));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::idl::{Idl, IdlFields, IdlType, IdlTypeDef};
+use types::{DecodeErrorKind, IdlValue};
+use utils::{read_bytes, read_pubkey, read_string, read_u8, read_u16, read_u32, read_u64, read_u128};
+
+/// Borsh-decodes `fields` from `data` at `offset`, advancing `offset` past them.
+pub(crate) fn decode_fields(
+    idl: &Idl,
+    fields: &IdlFields,
+    data: &[u8],
+    offset: &mut usize,
+) -> Result<IdlValue, DecodeErrorKind> {
+    match fields {
+        IdlFields::Named(fields) => {
+            let mut values = Vec::with_capacity(fields.len());
+            for (name, ty) in fields {
+                values.push((name.clone(), decode_type(idl, ty, data, offset)?));
+            }
+            Ok(IdlValue::Struct(values))
+        }
+        IdlFields::Tuple(types) if types.is_empty() => Ok(IdlValue::Unit),
+        IdlFields::Tuple(types) => {
+            let mut values = Vec::with_capacity(types.len());
+            for ty in types {
+                values.push(decode_type(idl, ty, data, offset)?);
+            }
+            Ok(IdlValue::Tuple(values))
+        }
+    }
+}
+
+pub(crate) fn decode_type(
+    idl: &Idl,
+    ty: &IdlType,
+    data: &[u8],
+    offset: &mut usize,
+) -> Result<IdlValue, DecodeErrorKind> {
+    let start = *offset;
+    let (value, len) = match ty {
+        IdlType::Bool => (IdlValue::Bool(read_u8(data, start)? != 0), 1),
+        IdlType::U8 => (IdlValue::Unsigned(read_u8(data, start)? as u128), 1),
+        IdlType::I8 => (IdlValue::Signed(read_u8(data, start)? as i8 as i128), 1),
+        IdlType::U16 => (IdlValue::Unsigned(read_u16(data, start)? as u128), 2),
+        IdlType::I16 => (IdlValue::Signed(read_u16(data, start)? as i16 as i128), 2),
+        IdlType::U32 => (IdlValue::Unsigned(read_u32(data, start)? as u128), 4),
+        IdlType::I32 => (IdlValue::Signed(read_u32(data, start)? as i32 as i128), 4),
+        IdlType::U64 => (IdlValue::Unsigned(read_u64(data, start)? as u128), 8),
+        IdlType::I64 => (IdlValue::Signed(read_u64(data, start)? as i64 as i128), 8),
+        IdlType::U128 => (IdlValue::Unsigned(read_u128(data, start)?), 16),
+        IdlType::I128 => (IdlValue::Signed(read_u128(data, start)? as i128), 16),
+        IdlType::F32 => {
+            let bytes = read_bytes(data, start, 4)?.try_into().unwrap();
+            (IdlValue::Float(f32::from_le_bytes(bytes) as f64), 4)
+        }
+        IdlType::F64 => {
+            let bytes = read_bytes(data, start, 8)?.try_into().unwrap();
+            (IdlValue::Float(f64::from_le_bytes(bytes)), 8)
+        }
+        IdlType::String => {
+            let (string, next_offset) = read_string(data, start)?;
+            (IdlValue::String(string), next_offset - start)
+        }
+        IdlType::Bytes => {
+            let len = read_u32(data, start)? as usize;
+            (IdlValue::Bytes(read_bytes(data, start + 4, len)?.to_vec()), 4 + len)
+        }
+        IdlType::Pubkey => (IdlValue::Pubkey(read_pubkey(data, start)?), 32),
+        IdlType::Vec(inner) => {
+            let len = read_u32(data, start)? as usize;
+            *offset += 4;
+            return decode_array(idl, inner, len, data, offset);
+        }
+        IdlType::Array(inner, len) => return decode_array(idl, inner, *len, data, offset),
+        IdlType::Option(inner) => {
+            *offset += 1;
+            return match read_u8(data, start)? {
+                0 => Ok(IdlValue::Option(None)),
+                1 => Ok(IdlValue::Option(Some(Box::new(decode_type(idl, inner, data, offset)?)))),
+                variant => Err(DecodeErrorKind::InvalidEnumVariant {
+                    offset: start,
+                    variant: variant as u32,
+                }),
+            };
+        }
+        // `COption` is always as wide as its value, the tag is a u32
+        IdlType::COption(inner) => {
+            *offset += 4;
+            return match read_u32(data, start)? {
+                0 => {
+                    decode_type(idl, inner, data, offset)?;
+                    Ok(IdlValue::Option(None))
+                }
+                1 => Ok(IdlValue::Option(Some(Box::new(decode_type(idl, inner, data, offset)?)))),
+                variant => Err(DecodeErrorKind::InvalidEnumVariant { offset: start, variant }),
+            };
+        }
+        IdlType::Defined(index) => return decode_defined(idl, *index, data, offset),
+        IdlType::Unsupported(ty) => return Err(DecodeErrorKind::UnsupportedIdlType(ty.clone())),
+    };
+    *offset += len;
+    Ok(value)
+}
+
+fn decode_array(
+    idl: &Idl,
+    ty: &IdlType,
+    len: usize,
+    data: &[u8],
+    offset: &mut usize,
+) -> Result<IdlValue, DecodeErrorKind> {
+    // `u8` arrays are kept as bytes rather than one value per byte
+    if let IdlType::U8 = ty {
+        let bytes = read_bytes(data, *offset, len)?.to_vec();
+        *offset += len;
+        return Ok(IdlValue::Bytes(bytes));
+    }
+    // the length comes from the data, so grow as elements are actually decoded, and refuse
+    // elements that read nothing: a forged length would otherwise spin through them unbounded
+    let mut values = Vec::new();
+    for _ in 0..len {
+        let start = *offset;
+        values.push(decode_type(idl, ty, data, offset)?);
+        if *offset == start {
+            return Err(DecodeErrorKind::DataTooShort {
+                offset: start,
+                needed: len - values.len() + 1,
+                len: data.len(),
+            });
+        }
+    }
+    Ok(IdlValue::Array(values))
+}
+
+fn decode_defined(
+    idl: &Idl,
+    index: usize,
+    data: &[u8],
+    offset: &mut usize,
+) -> Result<IdlValue, DecodeErrorKind> {
+    match &idl.types[index] {
+        IdlTypeDef::Struct(fields) => decode_fields(idl, fields, data, offset),
+        IdlTypeDef::Enum(variants) => {
+            let start = *offset;
+            let variant = read_u8(data, start)?;
+            let (name, fields) = variants.get(variant as usize).ok_or(
+                DecodeErrorKind::InvalidEnumVariant {
+                    offset: start,
+                    variant: variant as u32,
+                },
+            )?;
+            *offset += 1;
+            Ok(IdlValue::Enum {
+                variant: name.clone(),
+                value: Box::new(decode_fields(idl, fields, data, offset)?),
+            })
+        }
+        IdlTypeDef::Alias(ty) => decode_type(idl, ty, data, offset),
+        IdlTypeDef::Unsupported(ty) => Err(DecodeErrorKind::UnsupportedIdlType(ty.clone())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_a_forged_length_of_empty_elements() {
+        let idl = Idl::from_json(
+            r#"{"instructions": [], "types": [
+                {"name": "Empty", "type": {"kind": "struct", "fields": []}}
+            ]}"#,
+        )
+        .unwrap();
+        let ty = IdlType::Vec(Box::new(IdlType::Defined(0)));
+        let data = u32::MAX.to_le_bytes();
+        let mut offset = 0;
+        assert!(matches!(
+            decode_type(&idl, &ty, &data, &mut offset),
+            Err(DecodeErrorKind::DataTooShort { offset: 4, .. })
+        ));
+    }
+}
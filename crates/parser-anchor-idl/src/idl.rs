@@ -0,0 +1,344 @@
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum IdlError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    MissingField { path: String, field: &'static str },
+    MissingProgramId,
+}
+
+impl fmt::Display for IdlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdlError::Io(err) => write!(f, "failed to read IDL: {}", err),
+            IdlError::Json(err) => write!(f, "invalid IDL JSON: {}", err),
+            IdlError::MissingField { path, field } => {
+                write!(f, "IDL entry {} has no {} field", path, field)
+            }
+            IdlError::MissingProgramId => write!(f, "IDL has no program address"),
+        }
+    }
+}
+
+impl std::error::Error for IdlError {}
+
+impl From<std::io::Error> for IdlError {
+    fn from(err: std::io::Error) -> Self {
+        IdlError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for IdlError {
+    fn from(err: serde_json::Error) -> Self {
+        IdlError::Json(err)
+    }
+}
+
+/// Types the decoder cannot handle (generics, 256-bit integers, undefined names) are kept as
+/// `Unsupported` so the rest of the IDL stays usable; decoding one of them fails.
+#[derive(Debug, Clone)]
+pub(crate) enum IdlType {
+    Bool,
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    U128,
+    I128,
+    F32,
+    F64,
+    String,
+    Bytes,
+    Pubkey,
+    Vec(Box<IdlType>),
+    Option(Box<IdlType>),
+    COption(Box<IdlType>),
+    Array(Box<IdlType>, usize),
+    /// Index into `Idl::types`.
+    Defined(usize),
+    Unsupported(String),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum IdlFields {
+    Named(Vec<(String, IdlType)>),
+    Tuple(Vec<IdlType>),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum IdlTypeDef {
+    Struct(IdlFields),
+    Enum(Vec<(String, IdlFields)>),
+    Alias(IdlType),
+    Unsupported(String),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct IdlInstruction {
+    pub name: String,
+    pub discriminator: Vec<u8>,
+    pub accounts: Vec<String>,
+    pub args: IdlFields,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct IdlEvent {
+    pub name: String,
+    pub discriminator: Vec<u8>,
+    pub fields: IdlFields,
+}
+
+/// An Anchor IDL, in either the current (0.30+) or the legacy format. Discriminators missing
+/// from a legacy IDL are derived the way Anchor does.
+#[derive(Debug, Clone)]
+pub struct Idl {
+    pub name: String,
+    pub address: Option<String>,
+    pub(crate) instructions: Vec<IdlInstruction>,
+    pub(crate) events: Vec<IdlEvent>,
+    pub(crate) types: Vec<IdlTypeDef>,
+}
+
+impl Idl {
+    pub fn from_json(json: &str) -> Result<Self, IdlError> {
+        let idl: Value = serde_json::from_str(json)?;
+        Self::from_value(&idl)
+    }
+
+    pub fn from_value(idl: &Value) -> Result<Self, IdlError> {
+        let name = idl
+            .pointer("/metadata/name")
+            .or_else(|| idl.get("name"))
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let address = idl
+            .get("address")
+            .or_else(|| idl.pointer("/metadata/address"))
+            .and_then(Value::as_str)
+            .map(str::to_string);
+
+        let type_defs = get_array(idl, "types");
+        let mut type_indexes = HashMap::new();
+        for (index, type_def) in type_defs.iter().enumerate() {
+            type_indexes.insert(get_str(type_def, "name", "types")?, index);
+        }
+        let mut types = Vec::with_capacity(type_defs.len());
+        for type_def in type_defs {
+            let path = format!("types.{}", get_str(type_def, "name", "types")?);
+            let ty = type_def
+                .get("type")
+                .ok_or(IdlError::MissingField { path: path.clone(), field: "type" })?;
+            types.push(parse_type_def(ty, &type_indexes));
+        }
+
+        let mut instructions = Vec::new();
+        for instruction in get_array(idl, "instructions") {
+            let name = get_str(instruction, "name", "instructions")?;
+            let discriminator = match instruction.get("discriminator") {
+                Some(discriminator) => parse_discriminator(discriminator),
+                None => sighash("global", &to_snake_case(name)),
+            };
+            let mut accounts = Vec::new();
+            flatten_accounts(get_array(instruction, "accounts"), "", &mut accounts);
+            instructions.push(IdlInstruction {
+                name: name.to_string(),
+                discriminator,
+                accounts,
+                args: parse_fields(instruction.get("args"), &type_indexes),
+            });
+        }
+
+        // legacy events carry their fields, current ones point at the type of the same name
+        let mut events = Vec::new();
+        for event in get_array(idl, "events") {
+            let name = get_str(event, "name", "events")?;
+            let discriminator = match event.get("discriminator") {
+                Some(discriminator) => parse_discriminator(discriminator),
+                None => sighash("event", name),
+            };
+            let fields = match event.get("fields") {
+                Some(fields) => parse_fields(Some(fields), &type_indexes),
+                None => match type_indexes.get(name).map(|index| &types[*index]) {
+                    Some(IdlTypeDef::Struct(fields)) => fields.clone(),
+                    _ => IdlFields::Tuple(vec![IdlType::Unsupported(name.to_string())]),
+                },
+            };
+            events.push(IdlEvent {
+                name: name.to_string(),
+                discriminator,
+                fields,
+            });
+        }
+
+        Ok(Self {
+            name,
+            address,
+            instructions,
+            events,
+            types,
+        })
+    }
+}
+
+fn get_array<'a>(value: &'a Value, field: &str) -> &'a [Value] {
+    value
+        .get(field)
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+fn get_str<'a>(value: &'a Value, field: &'static str, path: &str) -> Result<&'a str, IdlError> {
+    value
+        .get(field)
+        .and_then(Value::as_str)
+        .ok_or(IdlError::MissingField { path: path.to_string(), field })
+}
+
+fn parse_discriminator(discriminator: &Value) -> Vec<u8> {
+    discriminator
+        .as_array()
+        .map(|bytes| bytes.iter().filter_map(Value::as_u64).map(|byte| byte as u8).collect())
+        .unwrap_or_default()
+}
+
+/// First 8 bytes of `sha256("<namespace>:<name>")`.
+pub fn sighash(namespace: &str, name: &str) -> Vec<u8> {
+    Sha256::digest(format!("{}:{}", namespace, name).as_bytes())[..8].to_vec()
+}
+
+/// Legacy IDLs name instructions in camelCase while their discriminators hash the snake_case
+/// name.
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (index, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && index > 0 {
+            let previous = chars[index - 1];
+            let next_is_lower = chars.get(index + 1).is_some_and(|next| next.is_lowercase());
+            if previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase() && next_is_lower)
+            {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+fn flatten_accounts(accounts: &[Value], prefix: &str, flattened: &mut Vec<String>) {
+    for account in accounts {
+        let name = account.get("name").and_then(Value::as_str).unwrap_or_default();
+        let name = if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", prefix, name)
+        };
+        match account.get("accounts").and_then(Value::as_array) {
+            Some(nested) => flatten_accounts(nested, &name, flattened),
+            None => flattened.push(name),
+        }
+    }
+}
+
+fn parse_fields(fields: Option<&Value>, type_indexes: &HashMap<&str, usize>) -> IdlFields {
+    let fields = fields.and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default();
+    if fields.first().is_some_and(|field| field.get("name").is_some()) {
+        IdlFields::Named(
+            fields
+                .iter()
+                .map(|field| {
+                    let name = field.get("name").and_then(Value::as_str).unwrap_or_default();
+                    let ty = match field.get("type") {
+                        Some(ty) => parse_type(ty, type_indexes),
+                        None => IdlType::Unsupported(name.to_string()),
+                    };
+                    (name.to_string(), ty)
+                })
+                .collect(),
+        )
+    } else {
+        IdlFields::Tuple(fields.iter().map(|ty| parse_type(ty, type_indexes)).collect())
+    }
+}
+
+fn parse_type_def(ty: &Value, type_indexes: &HashMap<&str, usize>) -> IdlTypeDef {
+    match ty.get("kind").and_then(Value::as_str) {
+        Some("struct") => IdlTypeDef::Struct(parse_fields(ty.get("fields"), type_indexes)),
+        Some("enum") => IdlTypeDef::Enum(
+            get_array(ty, "variants")
+                .iter()
+                .map(|variant| {
+                    let name = variant.get("name").and_then(Value::as_str).unwrap_or_default();
+                    (name.to_string(), parse_fields(variant.get("fields"), type_indexes))
+                })
+                .collect(),
+        ),
+        Some("type") => match ty.get("alias") {
+            Some(alias) => IdlTypeDef::Alias(parse_type(alias, type_indexes)),
+            None => IdlTypeDef::Unsupported(ty.to_string()),
+        },
+        _ => IdlTypeDef::Unsupported(ty.to_string()),
+    }
+}
+
+fn parse_type(ty: &Value, type_indexes: &HashMap<&str, usize>) -> IdlType {
+    if let Some(name) = ty.as_str() {
+        return match name {
+            "bool" => IdlType::Bool,
+            "u8" => IdlType::U8,
+            "i8" => IdlType::I8,
+            "u16" => IdlType::U16,
+            "i16" => IdlType::I16,
+            "u32" => IdlType::U32,
+            "i32" => IdlType::I32,
+            "u64" => IdlType::U64,
+            "i64" => IdlType::I64,
+            "u128" => IdlType::U128,
+            "i128" => IdlType::I128,
+            "f32" => IdlType::F32,
+            "f64" => IdlType::F64,
+            "string" => IdlType::String,
+            "bytes" => IdlType::Bytes,
+            "pubkey" | "publicKey" => IdlType::Pubkey,
+            _ => IdlType::Unsupported(name.to_string()),
+        };
+    }
+    if let Some(inner) = ty.get("vec") {
+        IdlType::Vec(Box::new(parse_type(inner, type_indexes)))
+    } else if let Some(inner) = ty.get("option") {
+        IdlType::Option(Box::new(parse_type(inner, type_indexes)))
+    } else if let Some(inner) = ty.get("coption") {
+        IdlType::COption(Box::new(parse_type(inner, type_indexes)))
+    } else if let Some([inner, len]) = ty.get("array").and_then(Value::as_array).map(Vec::as_slice) {
+        match len.as_u64() {
+            Some(len) => IdlType::Array(Box::new(parse_type(inner, type_indexes)), len as usize),
+            None => IdlType::Unsupported(ty.to_string()),
+        }
+    } else if let Some(defined) = ty.get("defined") {
+        let has_generics = defined
+            .get("generics")
+            .and_then(Value::as_array)
+            .is_some_and(|generics| !generics.is_empty());
+        let name = defined
+            .as_str()
+            .or_else(|| defined.get("name").and_then(Value::as_str));
+        match name.and_then(|name| type_indexes.get(name)) {
+            Some(index) if !has_generics => IdlType::Defined(*index),
+            _ => IdlType::Unsupported(ty.to_string()),
+        }
+    } else {
+        IdlType::Unsupported(ty.to_string())
+    }
+}
@@ -0,0 +1,175 @@
+mod decode;
+mod idl;
+
+pub use idl::{Idl, IdlError, sighash};
+
+use decode::decode_fields;
+use instruction_parser::InstructionParser;
+use std::path::Path;
+use std::sync::Arc;
+use types::{
+    DecodeError, DecodeErrorKind, DecodeReport, DecodedAnchorIdlAccount,
+    DecodedAnchorIdlEmittedEvent, DecodedAnchorIdlEvent, DecodedEvent, DecodedInstruction,
+    StructuredInstruction,
+};
+use utils::{EVENT_IX_TAG, get_account};
+use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
+
+/// Decodes any Anchor program from its IDL, loaded at runtime, into `IdlValue` trees.
+#[derive(Clone, Debug)]
+pub struct AnchorIdlInstructionParser {
+    program_id: String,
+    idl: Arc<Idl>,
+}
+
+impl InstructionParser for AnchorIdlInstructionParser {
+    fn get_program_id(&self) -> &str {
+        &self.program_id
+    }
+
+    fn decode_instructions(
+        &self,
+        instructions: Vec<StructuredInstruction>,
+        account_keys: &[String],
+        _transaction: &SubscribeUpdateTransaction,
+    ) -> DecodeReport {
+        let mut report = DecodeReport::default();
+        for instruction in instructions.iter() {
+            match self.decode_instruction(instruction, account_keys) {
+                Ok(Some(decoded_instruction)) => report.events.push(DecodedInstruction::new(
+                    &self.program_id,
+                    instruction,
+                    DecodedEvent::AnchorIdl(decoded_instruction),
+                )),
+                Ok(None) => {}
+                Err(kind) => report
+                    .errors
+                    .push(DecodeError::new(&self.program_id, instruction, 8, kind)),
+            }
+        }
+        report
+    }
+}
+
+impl AnchorIdlInstructionParser {
+    /// Takes the program id from the IDL's `address`, so an IDL without one is rejected.
+    pub fn from_idl(idl: Idl) -> Result<Self, IdlError> {
+        let program_id = idl.address.clone().ok_or(IdlError::MissingProgramId)?;
+        Ok(Self {
+            program_id,
+            idl: Arc::new(idl),
+        })
+    }
+
+    pub fn from_idl_json(json: &str) -> Result<Self, IdlError> {
+        Self::from_idl(Idl::from_json(json)?)
+    }
+
+    pub fn from_idl_file(path: impl AsRef<Path>) -> Result<Self, IdlError> {
+        Self::from_idl_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// For programs deployed at another address than the one in their IDL.
+    pub fn with_program_id(mut self, program_id: &str) -> Self {
+        self.program_id = program_id.to_string();
+        self
+    }
+
+    pub fn idl(&self) -> &Idl {
+        &self.idl
+    }
+
+    /// Instructions the IDL does not describe, including the self-CPIs carrying events, are
+    /// skipped.
+    pub fn decode_instruction(
+        &self,
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Result<Option<DecodedAnchorIdlEvent>, DecodeErrorKind> {
+        let data: &Vec<u8> = &instruction.data;
+        let idl_instruction = match self.idl.instructions.iter().find(|idl_instruction| {
+            !idl_instruction.discriminator.is_empty()
+                && data.starts_with(&idl_instruction.discriminator)
+        }) {
+            Some(idl_instruction) => idl_instruction,
+            None => return Ok(None),
+        };
+
+        let mut offset = idl_instruction.discriminator.len();
+        let args = decode_fields(&self.idl, &idl_instruction.args, data, &mut offset)?;
+
+        let mut accounts = Vec::with_capacity(idl_instruction.accounts.len());
+        for (position, name) in idl_instruction.accounts.iter().enumerate() {
+            if position >= instruction.account_key_indexes.len() {
+                break;
+            }
+            accounts.push(DecodedAnchorIdlAccount {
+                name: name.clone(),
+                pubkey: get_account(instruction, account_keys, position)?,
+            });
+        }
+        let mut remaining_accounts = Vec::new();
+        for position in idl_instruction.accounts.len()..instruction.account_key_indexes.len() {
+            remaining_accounts.push(get_account(instruction, account_keys, position)?);
+        }
+
+        Ok(Some(DecodedAnchorIdlEvent {
+            program_name: self.idl.name.clone(),
+            instruction: idl_instruction.name.clone(),
+            args,
+            accounts,
+            remaining_accounts,
+            events: self.decode_events(instruction)?,
+        }))
+    }
+
+    /// Events emitted through a self-CPI directly below `instruction`, in emission order.
+    fn decode_events(
+        &self,
+        instruction: &StructuredInstruction,
+    ) -> Result<Vec<DecodedAnchorIdlEmittedEvent>, DecodeErrorKind> {
+        let mut events = Vec::new();
+        for inner in instruction.inner_instructions.iter() {
+            let payload = match inner.data.strip_prefix(&EVENT_IX_TAG[..]) {
+                Some(payload) => payload,
+                None => continue,
+            };
+            let event = match self.idl.events.iter().find(|event| {
+                !event.discriminator.is_empty() && payload.starts_with(&event.discriminator)
+            }) {
+                Some(event) => event,
+                None => continue,
+            };
+            let mut offset = event.discriminator.len();
+            events.push(DecodedAnchorIdlEmittedEvent {
+                name: event.name.clone(),
+                data: decode_fields(&self.idl, &event.fields, payload, &mut offset)?,
+            });
+        }
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn takes_the_program_id_from_the_idl_address() {
+        let json = r#"{
+            "address": "boop8hVGQGqehUK2iVEMEnMrL5RbjywRzHKBmBE7ry4",
+            "instructions": []
+        }"#;
+        let parser = AnchorIdlInstructionParser::from_idl_json(json).unwrap();
+        assert_eq!(parser.get_program_id(), "boop8hVGQGqehUK2iVEMEnMrL5RbjywRzHKBmBE7ry4");
+    }
+
+    #[test]
+    fn rejects_an_idl_without_an_address() {
+        let idl = Idl::from_json(r#"{"instructions": []}"#).unwrap();
+        assert!(matches!(
+            AnchorIdlInstructionParser::from_idl(idl),
+            Err(IdlError::MissingProgramId)
+        ));
+    }
+}
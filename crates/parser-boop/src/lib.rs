@@ -0,0 +1,408 @@
+use instruction_parser::InstructionParser;
+use types::{
+    DecodeError, DecodeErrorKind, DecodeReport, DecodedBoopCreatePoolEvent,
+    DecodedBoopDeployBondingCurveEvent, DecodedBoopEvent, DecodedBoopMigrateEvent,
+    DecodedBoopSwapEvent, DecodedEvent, DecodedInstruction, StructuredInstruction,
+    SwapEventAccounts, TransactionType,
+};
+use utils::{
+    find_event, get_account, get_account_lamports, get_instruction_token_balance,
+    read_pubkey, read_string, read_u64,
+};
+use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
+
+#[derive(Clone, Debug, Default)]
+pub struct BoopInstructionParser {}
+
+impl InstructionParser for BoopInstructionParser {
+    fn get_program_id(&self) -> &str {
+        Self::PROGRAM_ID
+    }
+
+    fn decode_instructions(
+        &self,
+        instructions: Vec<StructuredInstruction>,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> DecodeReport {
+        let mut report = DecodeReport::default();
+        for instruction in instructions.iter() {
+            match self.decode_instruction(instruction, account_keys, transaction) {
+                Ok(Some(decoded_instruction)) => report.events.push(DecodedInstruction::new(
+                    Self::PROGRAM_ID,
+                    instruction,
+                    DecodedEvent::Boop(decoded_instruction),
+                )),
+                Ok(None) => {}
+                Err(kind) => report
+                    .errors
+                    .push(DecodeError::new(Self::PROGRAM_ID, instruction, 8, kind)),
+            }
+        }
+        report
+    }
+}
+
+impl BoopInstructionParser {
+    const PROGRAM_ID: &'static str = "boop8hVGQGqehUK2iVEMEnMrL5RbjywRzHKBmBE7ry4";
+    const WSOL_ADDRESS: &'static str = "So11111111111111111111111111111111111111112";
+    const CREATE_TOKEN_DISCRIMINATOR: [u8; 8] = [84, 52, 204, 228, 24, 140, 234, 75];
+    const DEPLOY_BONDING_CURVE_DISCRIMINATOR: [u8; 8] = [180, 89, 199, 76, 168, 236, 217, 138];
+    const BUY_TOKEN_DISCRIMINATOR: [u8; 8] = [138, 127, 14, 91, 38, 87, 115, 105];
+    const SELL_TOKEN_DISCRIMINATOR: [u8; 8] = [109, 61, 40, 187, 230, 176, 135, 174];
+    const GRADUATE_DISCRIMINATOR: [u8; 8] = [45, 235, 225, 181, 17, 218, 64, 130];
+    const TOKEN_BOUGHT_EVENT_DISCRIMINATOR: [u8; 8] = [197, 182, 3, 228, 82, 236, 7, 143];
+    const TOKEN_SOLD_EVENT_DISCRIMINATOR: [u8; 8] = [88, 61, 1, 247, 185, 6, 252, 86];
+
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn decode_instruction(
+        &self,
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> Result<Option<DecodedBoopEvent>, DecodeErrorKind> {
+        let discriminator = match instruction.data.get(..8) {
+            Some(discriminator) => discriminator,
+            None => return Ok(None),
+        };
+        if discriminator == Self::BUY_TOKEN_DISCRIMINATOR {
+            return Ok(Some(DecodedBoopEvent::Swap(Self::decode_swap_event(
+                instruction,
+                account_keys,
+                transaction,
+                true,
+            )?)));
+        } else if discriminator == Self::SELL_TOKEN_DISCRIMINATOR {
+            return Ok(Some(DecodedBoopEvent::Swap(Self::decode_swap_event(
+                instruction,
+                account_keys,
+                transaction,
+                false,
+            )?)));
+        } else if discriminator == Self::CREATE_TOKEN_DISCRIMINATOR {
+            return Ok(Some(DecodedBoopEvent::CreatePool(Self::decode_create_token_event(
+                instruction,
+                account_keys,
+            )?)));
+        } else if discriminator == Self::DEPLOY_BONDING_CURVE_DISCRIMINATOR {
+            return Ok(Some(DecodedBoopEvent::DeployBondingCurve(
+                Self::decode_deploy_bonding_curve_event(instruction, account_keys)?,
+            )));
+        } else if discriminator == Self::GRADUATE_DISCRIMINATOR {
+            return Ok(Some(DecodedBoopEvent::Migrate(Self::decode_graduate_event(
+                instruction,
+                account_keys,
+            )?)));
+        }
+        Ok(None)
+    }
+
+    /// `buy_token` accounts: [mint, bonding_curve, trading_fees_vault, bonding_curve_vault,
+    /// bonding_curve_sol_vault, recipient_token_account, buyer, ...]
+    ///
+    /// `sell_token` accounts: [mint, bonding_curve, trading_fees_vault, bonding_curve_vault,
+    /// bonding_curve_sol_vault, seller_token_account, seller, recipient, ...]
+    ///
+    /// `TokenBought` / `TokenSold`: mint, amount_in, amount_out, swap_fee, buyer or seller,
+    /// recipient
+    pub fn decode_swap_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+        buy: bool,
+    ) -> Result<DecodedBoopSwapEvent, DecodeErrorKind> {
+        let bonding_curve = get_account(instruction, account_keys, 1)?;
+        let sol_vault_index = *instruction
+            .account_key_indexes
+            .get(4)
+            .ok_or(DecodeErrorKind::MissingAccount { position: 4 })?;
+        let sol_reserve = get_account_lamports(transaction, sol_vault_index as u32)?;
+        let (_, token_reserve, _) = get_instruction_token_balance(instruction, transaction, 3)?;
+
+        let event_discriminator = if buy {
+            Self::TOKEN_BOUGHT_EVENT_DISCRIMINATOR
+        } else {
+            Self::TOKEN_SOLD_EVENT_DISCRIMINATOR
+        };
+        let data = find_event(instruction, &event_discriminator)
+            .ok_or(DecodeErrorKind::MissingEventLog)?;
+        let mut offset: usize = 0;
+        let mint = read_pubkey(data, offset)?;
+        offset += 32;
+        let amount_in = read_u64(data, offset)?;
+        offset += 8;
+        let amount_out = read_u64(data, offset)?;
+        offset += 8;
+        let swap_fee = read_u64(data, offset)?;
+        offset += 8;
+        let user = read_pubkey(data, offset)?;
+
+        let wsol = Self::WSOL_ADDRESS.to_string();
+        let (mint_in, mint_out, mint_in_reserve, mint_out_reserve, event_type) = if buy {
+            (wsol.clone(), mint.clone(), sol_reserve, token_reserve, TransactionType::Buy)
+        } else {
+            (mint.clone(), wsol.clone(), token_reserve, sol_reserve, TransactionType::Sell)
+        };
+
+        Ok(DecodedBoopSwapEvent {
+            accounts: SwapEventAccounts {
+                pool: bonding_curve,
+                user,
+                base_mint: mint,
+                quote_mint: wsol,
+            },
+            mint_in,
+            mint_out,
+            amount_in,
+            amount_out,
+            mint_in_reserve,
+            mint_out_reserve,
+            swap_fee,
+            event_type,
+        })
+    }
+
+    /// Accounts: [config, metadata, mint, payer, ...]
+    ///
+    /// Args: salt, name, symbol, uri
+    pub fn decode_create_token_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Result<DecodedBoopCreatePoolEvent, DecodeErrorKind> {
+        let data: &Vec<u8> = &instruction.data;
+        let offset = 16;
+        let (name, offset) = read_string(data, offset)?;
+        let (symbol, offset) = read_string(data, offset)?;
+        let (uri, _) = read_string(data, offset)?;
+
+        let mint = get_account(instruction, account_keys, 2)?;
+        let creator = get_account(instruction, account_keys, 3)?;
+
+        Ok(DecodedBoopCreatePoolEvent {
+            name,
+            symbol,
+            uri,
+            creator,
+            base_mint: mint,
+            quote_mint: Self::WSOL_ADDRESS.to_string(),
+            event_type: TransactionType::CreatePool,
+        })
+    }
+
+    /// Accounts: [mint, vault_authority, bonding_curve, bonding_curve_sol_vault,
+    /// bonding_curve_vault, config, payer, ...]
+    ///
+    /// Args: creator, salt
+    pub fn decode_deploy_bonding_curve_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Result<DecodedBoopDeployBondingCurveEvent, DecodeErrorKind> {
+        let creator = read_pubkey(&instruction.data, 8)?;
+        let mint = get_account(instruction, account_keys, 0)?;
+        let bonding_curve = get_account(instruction, account_keys, 2)?;
+        let bonding_curve_sol_vault = get_account(instruction, account_keys, 3)?;
+        let bonding_curve_vault = get_account(instruction, account_keys, 4)?;
+        Ok(DecodedBoopDeployBondingCurveEvent {
+            creator,
+            base_mint: mint,
+            bonding_curve,
+            bonding_curve_vault,
+            bonding_curve_sol_vault,
+        })
+    }
+
+    /// Accounts: [mint, wsol, protocol_fee_recipient, token_distributor,
+    /// token_distributor_token_account, vault_authority, bonding_curve_sol_vault,
+    /// bonding_curve, bonding_curve_vault, ...]
+    pub fn decode_graduate_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Result<DecodedBoopMigrateEvent, DecodeErrorKind> {
+        let mint = get_account(instruction, account_keys, 0)?;
+        let bonding_curve = get_account(instruction, account_keys, 7)?;
+        Ok(DecodedBoopMigrateEvent {
+            base_mint: mint,
+            bonding_curve,
+            event_type: TransactionType::Migrate,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::testing::{USER, event_cpi, pubkey};
+    use yellowstone_grpc_proto::prelude::{
+        SubscribeUpdateTransactionInfo, TokenBalance, TransactionStatusMeta, UiTokenAmount,
+    };
+
+    const CURVE: &str = "3Kzk1Fp3JH7ndXnBV2fDTf8e1qAr1mCdZTqXsYEr8Y2Q";
+    const MINT: &str = "4k3Dyjzvzp8eMZWUXbBCjEvwSkkk59S5iCNLY3QrkX6R";
+
+    /// The accounts of `buy_token` and `sell_token`, followed by the program.
+    fn account_keys() -> Vec<String> {
+        let mut account_keys: Vec<String> =
+            (0..8).map(|index| format!("account{index}")).collect();
+        account_keys[0] = MINT.to_string();
+        account_keys[1] = CURVE.to_string();
+        account_keys[6] = USER.to_string();
+        account_keys.push(BoopInstructionParser::PROGRAM_ID.to_string());
+        account_keys
+    }
+
+    /// A transaction holding 30 SOL in the curve's SOL vault and 600M tokens in its vault.
+    fn transaction() -> SubscribeUpdateTransaction {
+        let mut post_balances = vec![0; 9];
+        post_balances[4] = 30_000_000_000;
+        SubscribeUpdateTransaction {
+            transaction: Some(SubscribeUpdateTransactionInfo {
+                meta: Some(TransactionStatusMeta {
+                    post_balances,
+                    post_token_balances: vec![TokenBalance {
+                        account_index: 3,
+                        mint: MINT.to_string(),
+                        ui_token_amount: Some(UiTokenAmount {
+                            amount: "600000000000000000".to_string(),
+                            decimals: 9,
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// An instruction of the program, emitting `event` through a self-CPI when given one.
+    fn instruction(data: Vec<u8>, event: Option<Vec<u8>>) -> StructuredInstruction {
+        let inner_instructions = event
+            .map(|event| event_cpi(8, vec![0, 0], &event))
+            .into_iter()
+            .collect();
+        StructuredInstruction {
+            account_key_indexes: (0..8).collect(),
+            program_id_index: 8,
+            data,
+            inner_instructions,
+            stack_height: 1,
+            path: vec![0],
+            program_data_logs: Vec::new(),
+        }
+    }
+
+    fn swap_event(discriminator: [u8; 8], amount_in: u64, amount_out: u64) -> Vec<u8> {
+        let mut event = discriminator.to_vec();
+        event.extend(pubkey(MINT));
+        event.extend(amount_in.to_le_bytes());
+        event.extend(amount_out.to_le_bytes());
+        event.extend(5_000_000u64.to_le_bytes()); // swap_fee
+        event.extend(pubkey(USER)); // buyer or seller
+        event.extend(pubkey(USER)); // recipient
+        event
+    }
+
+    #[test]
+    fn decodes_a_buy_from_its_token_bought_event() {
+        let mut data = BoopInstructionParser::BUY_TOKEN_DISCRIMINATOR.to_vec();
+        data.extend(500_000_000u64.to_le_bytes()); // buy_amount
+        data.extend(12_000_000_000_000u64.to_le_bytes()); // amount_out_min
+        let event = swap_event(
+            BoopInstructionParser::TOKEN_BOUGHT_EVENT_DISCRIMINATOR,
+            495_000_000,
+            12_345_678_901_234,
+        );
+        let buy = instruction(data, Some(event));
+
+        let Some(DecodedBoopEvent::Swap(event)) = BoopInstructionParser::new()
+            .decode_instruction(&buy, &account_keys(), &transaction())
+            .unwrap()
+        else {
+            panic!("expected a swap");
+        };
+        assert_eq!(event.accounts.pool, CURVE);
+        assert_eq!(event.accounts.user, USER);
+        assert_eq!(event.accounts.base_mint, MINT);
+        assert_eq!(event.mint_in, BoopInstructionParser::WSOL_ADDRESS);
+        assert_eq!(event.mint_out, MINT);
+        assert_eq!(event.amount_in, 495_000_000);
+        assert_eq!(event.amount_out, 12_345_678_901_234);
+        assert_eq!(event.mint_in_reserve, 30_000_000_000);
+        assert_eq!(event.mint_out_reserve, 600_000_000_000_000_000);
+        assert_eq!(event.swap_fee, 5_000_000);
+        assert!(matches!(event.event_type, TransactionType::Buy));
+    }
+
+    #[test]
+    fn decodes_a_sell_from_its_token_sold_event() {
+        let mut data = BoopInstructionParser::SELL_TOKEN_DISCRIMINATOR.to_vec();
+        data.extend(12_345_678_901_234u64.to_le_bytes()); // sell_amount
+        data.extend(480_000_000u64.to_le_bytes()); // amount_out_min
+        let event = swap_event(
+            BoopInstructionParser::TOKEN_SOLD_EVENT_DISCRIMINATOR,
+            12_345_678_901_234,
+            489_000_000,
+        );
+        let sell = instruction(data, Some(event));
+
+        let Some(DecodedBoopEvent::Swap(event)) = BoopInstructionParser::new()
+            .decode_instruction(&sell, &account_keys(), &transaction())
+            .unwrap()
+        else {
+            panic!("expected a swap");
+        };
+        assert_eq!(event.mint_in, MINT);
+        assert_eq!(event.mint_out, BoopInstructionParser::WSOL_ADDRESS);
+        assert_eq!(event.amount_in, 12_345_678_901_234);
+        assert_eq!(event.amount_out, 489_000_000);
+        assert_eq!(event.mint_in_reserve, 600_000_000_000_000_000);
+        assert_eq!(event.mint_out_reserve, 30_000_000_000);
+        assert!(matches!(event.event_type, TransactionType::Sell));
+    }
+
+    #[test]
+    fn rejects_a_buy_without_its_event() {
+        let data = BoopInstructionParser::BUY_TOKEN_DISCRIMINATOR.to_vec();
+        let result = BoopInstructionParser::new().decode_instruction(
+            &instruction(data, None),
+            &account_keys(),
+            &transaction(),
+        );
+        assert!(matches!(result, Err(DecodeErrorKind::MissingEventLog)));
+    }
+
+    #[test]
+    fn decodes_a_token_creation_from_its_args() {
+        let mut account_keys = account_keys();
+        account_keys.swap(0, 2); // the mint comes third in create_token
+        account_keys[3] = USER.to_string(); // payer
+        let mut data = BoopInstructionParser::CREATE_TOKEN_DISCRIMINATOR.to_vec();
+        data.extend(42u64.to_le_bytes()); // salt
+        for field in ["Boop Cat", "BCAT", "https://ipfs.io/ipfs/bafkreibcat"] {
+            data.extend((field.len() as u32).to_le_bytes());
+            data.extend(field.as_bytes());
+        }
+
+        let Some(DecodedBoopEvent::CreatePool(event)) = BoopInstructionParser::new()
+            .decode_instruction(
+                &instruction(data, None),
+                &account_keys,
+                &SubscribeUpdateTransaction::default(),
+            )
+            .unwrap()
+        else {
+            panic!("expected a token creation");
+        };
+        assert_eq!(event.name, "Boop Cat");
+        assert_eq!(event.symbol, "BCAT");
+        assert_eq!(event.uri, "https://ipfs.io/ipfs/bafkreibcat");
+        assert_eq!(event.creator, USER);
+        assert_eq!(event.base_mint, MINT);
+        assert_eq!(event.quote_mint, BoopInstructionParser::WSOL_ADDRESS);
+    }
+}
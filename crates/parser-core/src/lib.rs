@@ -1,41 +1,292 @@
 use instruction_parser::InstructionParser;
+#[cfg(feature = "pump-amm")]
 use parser_pump_amm::PumpAmmInstructionParser;
+#[cfg(feature = "pumpfun")]
 use parser_pumpfun::PumpFunInstructionParser;
+#[cfg(feature = "raydium")]
+use parser_raydium::RaydiumInstructionParser;
+#[cfg(feature = "raydium-cpmm")]
+use parser_raydium_cpmm::RaydiumCpmmInstructionParser;
+#[cfg(feature = "raydium-clmm")]
+use parser_raydium_clmm::RaydiumClmmInstructionParser;
+#[cfg(feature = "raydium-launchlab")]
+use parser_raydium_launchlab::LaunchLabInstructionParser;
+#[cfg(feature = "orca-whirlpool")]
+use parser_orca_whirlpool::OrcaWhirlpoolInstructionParser;
+#[cfg(feature = "meteora-dlmm")]
+use parser_meteora_dlmm::MeteoraDlmmInstructionParser;
+#[cfg(feature = "meteora-damm")]
+use parser_meteora_damm::MeteoraDammInstructionParser;
+#[cfg(feature = "meteora-damm-v2")]
+use parser_meteora_damm_v2::MeteoraDammV2InstructionParser;
+#[cfg(feature = "meteora-dbc")]
+use parser_meteora_dbc::MeteoraDbcInstructionParser;
+#[cfg(feature = "jupiter")]
+use parser_jupiter::JupiterInstructionParser;
+#[cfg(feature = "jupiter-limit-order")]
+use parser_jupiter_limit_order::JupiterLimitOrderInstructionParser;
+#[cfg(feature = "jupiter-dca")]
+use parser_jupiter_dca::JupiterDcaInstructionParser;
+#[cfg(feature = "phoenix")]
+use parser_phoenix::PhoenixInstructionParser;
+#[cfg(feature = "openbook-v2")]
+use parser_openbook_v2::OpenBookV2InstructionParser;
+#[cfg(feature = "moonshot")]
+use parser_moonshot::MoonshotInstructionParser;
+#[cfg(feature = "boop")]
+use parser_boop::BoopInstructionParser;
+#[cfg(feature = "anchor-idl")]
+pub use parser_anchor_idl::{AnchorIdlInstructionParser, IdlError};
 use std::collections::{HashMap, HashSet};
-use types::{DecodedEvent, StructuredInstruction};
+use types::{DecodeError, DecodeErrorKind, DecodeReport, StructuredInstruction};
 use utils::{
-    filter_instructions, get_account_keys,
+    attach_program_data_logs, filter_instructions, get_account_keys, get_program_data_logs,
     structure_all_instructions,
 };
 use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
 
+#[cfg(not(any(
+    feature = "pump-amm",
+    feature = "pumpfun",
+    feature = "raydium",
+    feature = "raydium-cpmm",
+    feature = "raydium-clmm",
+    feature = "raydium-launchlab",
+    feature = "orca-whirlpool",
+    feature = "meteora-dlmm",
+    feature = "meteora-damm",
+    feature = "meteora-damm-v2",
+    feature = "meteora-dbc",
+    feature = "jupiter",
+    feature = "jupiter-limit-order",
+    feature = "jupiter-dca",
+    feature = "phoenix",
+    feature = "openbook-v2",
+    feature = "moonshot",
+    feature = "boop",
+    feature = "anchor-idl",
+)))]
+compile_error!("parser-core needs at least one parser feature enabled");
+
 #[derive(Clone, Debug)]
 pub enum ParserEnum {
+    #[cfg(feature = "pump-amm")]
     PumpAmm(PumpAmmInstructionParser),
+    #[cfg(feature = "pumpfun")]
     PumpFun(PumpFunInstructionParser),
+    #[cfg(feature = "raydium")]
+    Raydium(RaydiumInstructionParser),
+    #[cfg(feature = "raydium-cpmm")]
+    RaydiumCpmm(RaydiumCpmmInstructionParser),
+    #[cfg(feature = "raydium-clmm")]
+    RaydiumClmm(RaydiumClmmInstructionParser),
+    #[cfg(feature = "raydium-launchlab")]
+    LaunchLab(LaunchLabInstructionParser),
+    #[cfg(feature = "orca-whirlpool")]
+    OrcaWhirlpool(OrcaWhirlpoolInstructionParser),
+    #[cfg(feature = "meteora-dlmm")]
+    MeteoraDlmm(MeteoraDlmmInstructionParser),
+    #[cfg(feature = "meteora-damm")]
+    MeteoraDamm(MeteoraDammInstructionParser),
+    #[cfg(feature = "meteora-damm-v2")]
+    MeteoraDammV2(MeteoraDammV2InstructionParser),
+    #[cfg(feature = "meteora-dbc")]
+    MeteoraDbc(MeteoraDbcInstructionParser),
+    #[cfg(feature = "jupiter")]
+    Jupiter(JupiterInstructionParser),
+    #[cfg(feature = "jupiter-limit-order")]
+    JupiterLimitOrder(JupiterLimitOrderInstructionParser),
+    #[cfg(feature = "jupiter-dca")]
+    JupiterDca(JupiterDcaInstructionParser),
+    #[cfg(feature = "phoenix")]
+    Phoenix(PhoenixInstructionParser),
+    #[cfg(feature = "openbook-v2")]
+    OpenBookV2(OpenBookV2InstructionParser),
+    #[cfg(feature = "moonshot")]
+    Moonshot(MoonshotInstructionParser),
+    #[cfg(feature = "boop")]
+    Boop(BoopInstructionParser),
+    #[cfg(feature = "anchor-idl")]
+    AnchorIdl(AnchorIdlInstructionParser),
 }
 
-impl InstructionParser for ParserEnum {
-    fn new() -> Self {
-        ParserEnum::PumpAmm(PumpAmmInstructionParser::new())
+impl ParserEnum {
+    /// Every parser compiled in through the protocol features. IDL-driven parsers need an IDL,
+    /// so they are added with `TransactionParser::register` instead.
+    pub fn all() -> Vec<ParserEnum> {
+        vec![
+            #[cfg(feature = "pump-amm")]
+            ParserEnum::PumpAmm(PumpAmmInstructionParser::new()),
+            #[cfg(feature = "pumpfun")]
+            ParserEnum::PumpFun(PumpFunInstructionParser::new()),
+            #[cfg(feature = "raydium")]
+            ParserEnum::Raydium(RaydiumInstructionParser::new()),
+            #[cfg(feature = "raydium-cpmm")]
+            ParserEnum::RaydiumCpmm(RaydiumCpmmInstructionParser::new()),
+            #[cfg(feature = "raydium-clmm")]
+            ParserEnum::RaydiumClmm(RaydiumClmmInstructionParser::new()),
+            #[cfg(feature = "raydium-launchlab")]
+            ParserEnum::LaunchLab(LaunchLabInstructionParser::new()),
+            #[cfg(feature = "orca-whirlpool")]
+            ParserEnum::OrcaWhirlpool(OrcaWhirlpoolInstructionParser::new()),
+            #[cfg(feature = "meteora-dlmm")]
+            ParserEnum::MeteoraDlmm(MeteoraDlmmInstructionParser::new()),
+            #[cfg(feature = "meteora-damm")]
+            ParserEnum::MeteoraDamm(MeteoraDammInstructionParser::new()),
+            #[cfg(feature = "meteora-damm-v2")]
+            ParserEnum::MeteoraDammV2(MeteoraDammV2InstructionParser::new()),
+            #[cfg(feature = "meteora-dbc")]
+            ParserEnum::MeteoraDbc(MeteoraDbcInstructionParser::new()),
+            #[cfg(feature = "jupiter")]
+            ParserEnum::Jupiter(JupiterInstructionParser::new()),
+            #[cfg(feature = "jupiter-limit-order")]
+            ParserEnum::JupiterLimitOrder(JupiterLimitOrderInstructionParser::new()),
+            #[cfg(feature = "jupiter-dca")]
+            ParserEnum::JupiterDca(JupiterDcaInstructionParser::new()),
+            #[cfg(feature = "phoenix")]
+            ParserEnum::Phoenix(PhoenixInstructionParser::new()),
+            #[cfg(feature = "openbook-v2")]
+            ParserEnum::OpenBookV2(OpenBookV2InstructionParser::new()),
+            #[cfg(feature = "moonshot")]
+            ParserEnum::Moonshot(MoonshotInstructionParser::new()),
+            #[cfg(feature = "boop")]
+            ParserEnum::Boop(BoopInstructionParser::new()),
+        ]
     }
+}
 
+impl InstructionParser for ParserEnum {
     fn get_program_id(&self) -> &str {
         match self {
+            #[cfg(feature = "pump-amm")]
             ParserEnum::PumpAmm(p) => p.get_program_id(),
+            #[cfg(feature = "pumpfun")]
             ParserEnum::PumpFun(p) => p.get_program_id(),
+            #[cfg(feature = "raydium")]
+            ParserEnum::Raydium(p) => p.get_program_id(),
+            #[cfg(feature = "raydium-cpmm")]
+            ParserEnum::RaydiumCpmm(p) => p.get_program_id(),
+            #[cfg(feature = "raydium-clmm")]
+            ParserEnum::RaydiumClmm(p) => p.get_program_id(),
+            #[cfg(feature = "raydium-launchlab")]
+            ParserEnum::LaunchLab(p) => p.get_program_id(),
+            #[cfg(feature = "orca-whirlpool")]
+            ParserEnum::OrcaWhirlpool(p) => p.get_program_id(),
+            #[cfg(feature = "meteora-dlmm")]
+            ParserEnum::MeteoraDlmm(p) => p.get_program_id(),
+            #[cfg(feature = "meteora-damm")]
+            ParserEnum::MeteoraDamm(p) => p.get_program_id(),
+            #[cfg(feature = "meteora-damm-v2")]
+            ParserEnum::MeteoraDammV2(p) => p.get_program_id(),
+            #[cfg(feature = "meteora-dbc")]
+            ParserEnum::MeteoraDbc(p) => p.get_program_id(),
+            #[cfg(feature = "jupiter")]
+            ParserEnum::Jupiter(p) => p.get_program_id(),
+            #[cfg(feature = "jupiter-limit-order")]
+            ParserEnum::JupiterLimitOrder(p) => p.get_program_id(),
+            #[cfg(feature = "jupiter-dca")]
+            ParserEnum::JupiterDca(p) => p.get_program_id(),
+            #[cfg(feature = "phoenix")]
+            ParserEnum::Phoenix(p) => p.get_program_id(),
+            #[cfg(feature = "openbook-v2")]
+            ParserEnum::OpenBookV2(p) => p.get_program_id(),
+            #[cfg(feature = "moonshot")]
+            ParserEnum::Moonshot(p) => p.get_program_id(),
+            #[cfg(feature = "boop")]
+            ParserEnum::Boop(p) => p.get_program_id(),
+            #[cfg(feature = "anchor-idl")]
+            ParserEnum::AnchorIdl(p) => p.get_program_id(),
         }
     }
 
     fn decode_instructions(
         &self,
         instructions: Vec<StructuredInstruction>,
-        account_keys: &Vec<String>,
+        account_keys: &[String],
         transaction: &SubscribeUpdateTransaction,
-    ) -> Vec<DecodedEvent> {
+    ) -> DecodeReport {
         match self {
+            #[cfg(feature = "pump-amm")]
             ParserEnum::PumpAmm(p) => p.decode_instructions(instructions, account_keys, transaction),
+            #[cfg(feature = "pumpfun")]
             ParserEnum::PumpFun(p) => p.decode_instructions(instructions, account_keys, transaction),
+            #[cfg(feature = "raydium")]
+            ParserEnum::Raydium(p) => p.decode_instructions(instructions, account_keys, transaction),
+            #[cfg(feature = "raydium-cpmm")]
+            ParserEnum::RaydiumCpmm(p) => p.decode_instructions(instructions, account_keys, transaction),
+            #[cfg(feature = "raydium-clmm")]
+            ParserEnum::RaydiumClmm(p) => p.decode_instructions(instructions, account_keys, transaction),
+            #[cfg(feature = "raydium-launchlab")]
+            ParserEnum::LaunchLab(p) => p.decode_instructions(instructions, account_keys, transaction),
+            #[cfg(feature = "orca-whirlpool")]
+            ParserEnum::OrcaWhirlpool(p) => p.decode_instructions(instructions, account_keys, transaction),
+            #[cfg(feature = "meteora-dlmm")]
+            ParserEnum::MeteoraDlmm(p) => p.decode_instructions(instructions, account_keys, transaction),
+            #[cfg(feature = "meteora-damm")]
+            ParserEnum::MeteoraDamm(p) => p.decode_instructions(instructions, account_keys, transaction),
+            #[cfg(feature = "meteora-damm-v2")]
+            ParserEnum::MeteoraDammV2(p) => p.decode_instructions(instructions, account_keys, transaction),
+            #[cfg(feature = "meteora-dbc")]
+            ParserEnum::MeteoraDbc(p) => p.decode_instructions(instructions, account_keys, transaction),
+            #[cfg(feature = "jupiter")]
+            ParserEnum::Jupiter(p) => p.decode_instructions(instructions, account_keys, transaction),
+            #[cfg(feature = "jupiter-limit-order")]
+            ParserEnum::JupiterLimitOrder(p) => p.decode_instructions(instructions, account_keys, transaction),
+            #[cfg(feature = "jupiter-dca")]
+            ParserEnum::JupiterDca(p) => p.decode_instructions(instructions, account_keys, transaction),
+            #[cfg(feature = "phoenix")]
+            ParserEnum::Phoenix(p) => p.decode_instructions(instructions, account_keys, transaction),
+            #[cfg(feature = "openbook-v2")]
+            ParserEnum::OpenBookV2(p) => p.decode_instructions(instructions, account_keys, transaction),
+            #[cfg(feature = "moonshot")]
+            ParserEnum::Moonshot(p) => p.decode_instructions(instructions, account_keys, transaction),
+            #[cfg(feature = "boop")]
+            ParserEnum::Boop(p) => p.decode_instructions(instructions, account_keys, transaction),
+            #[cfg(feature = "anchor-idl")]
+            ParserEnum::AnchorIdl(p) => p.decode_instructions(instructions, account_keys, transaction),
+        }
+    }
+
+    fn link_events(&self, report: &mut DecodeReport) {
+        match self {
+            #[cfg(feature = "pump-amm")]
+            ParserEnum::PumpAmm(p) => p.link_events(report),
+            #[cfg(feature = "pumpfun")]
+            ParserEnum::PumpFun(p) => p.link_events(report),
+            #[cfg(feature = "raydium")]
+            ParserEnum::Raydium(p) => p.link_events(report),
+            #[cfg(feature = "raydium-cpmm")]
+            ParserEnum::RaydiumCpmm(p) => p.link_events(report),
+            #[cfg(feature = "raydium-clmm")]
+            ParserEnum::RaydiumClmm(p) => p.link_events(report),
+            #[cfg(feature = "raydium-launchlab")]
+            ParserEnum::LaunchLab(p) => p.link_events(report),
+            #[cfg(feature = "orca-whirlpool")]
+            ParserEnum::OrcaWhirlpool(p) => p.link_events(report),
+            #[cfg(feature = "meteora-dlmm")]
+            ParserEnum::MeteoraDlmm(p) => p.link_events(report),
+            #[cfg(feature = "meteora-damm")]
+            ParserEnum::MeteoraDamm(p) => p.link_events(report),
+            #[cfg(feature = "meteora-damm-v2")]
+            ParserEnum::MeteoraDammV2(p) => p.link_events(report),
+            #[cfg(feature = "meteora-dbc")]
+            ParserEnum::MeteoraDbc(p) => p.link_events(report),
+            #[cfg(feature = "jupiter")]
+            ParserEnum::Jupiter(p) => p.link_events(report),
+            #[cfg(feature = "jupiter-limit-order")]
+            ParserEnum::JupiterLimitOrder(p) => p.link_events(report),
+            #[cfg(feature = "jupiter-dca")]
+            ParserEnum::JupiterDca(p) => p.link_events(report),
+            #[cfg(feature = "phoenix")]
+            ParserEnum::Phoenix(p) => p.link_events(report),
+            #[cfg(feature = "openbook-v2")]
+            ParserEnum::OpenBookV2(p) => p.link_events(report),
+            #[cfg(feature = "moonshot")]
+            ParserEnum::Moonshot(p) => p.link_events(report),
+            #[cfg(feature = "boop")]
+            ParserEnum::Boop(p) => p.link_events(report),
+            #[cfg(feature = "anchor-idl")]
+            ParserEnum::AnchorIdl(p) => p.link_events(report),
         }
     }
 }
@@ -45,25 +296,21 @@ pub struct TransactionParser {
     program_ids: HashSet<String>,
 }
 
+impl Default for TransactionParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl TransactionParser {
     pub fn new() -> Self {
         let mut parsers: HashMap<String, ParserEnum> = HashMap::new();
         let mut program_ids: HashSet<String> = HashSet::new();
 
-        let pump_amm_parser = ParserEnum::PumpAmm(PumpAmmInstructionParser::new());
-        let pump_fun_parser = ParserEnum::PumpFun(PumpFunInstructionParser::new());
-
-        parsers.insert(
-            pump_amm_parser.get_program_id().to_string(),
-            pump_amm_parser.clone(),
-        );
-        parsers.insert(
-            pump_fun_parser.get_program_id().to_string(),
-            pump_fun_parser.clone(),
-        );
-
-        program_ids.insert(pump_amm_parser.get_program_id().to_string());
-        program_ids.insert(pump_fun_parser.get_program_id().to_string());
+        for parser in ParserEnum::all() {
+            program_ids.insert(parser.get_program_id().to_string());
+            parsers.insert(parser.get_program_id().to_string(), parser);
+        }
 
         Self {
             parsers,
@@ -71,31 +318,58 @@ impl TransactionParser {
         }
     }
 
+    /// Registers `parser` for its program, replacing the parser already registered for it.
+    pub fn register(&mut self, parser: ParserEnum) {
+        self.program_ids.insert(parser.get_program_id().to_string());
+        self.parsers.insert(parser.get_program_id().to_string(), parser);
+    }
+
+    /// Program ids of the registered parsers, sorted so subscriptions are stable.
+    pub fn get_program_ids(&self) -> Vec<String> {
+        let mut program_ids: Vec<String> = self.program_ids.iter().cloned().collect();
+        program_ids.sort();
+        program_ids
+    }
+
+    /// The transaction's logs are parsed here, once, and handed to the instructions that
+    /// logged them.
     pub fn get_parsers_and_instructions(
         &self,
         tx: &SubscribeUpdateTransaction,
-        account_keys: &Vec<String>,
-    ) -> HashMap<String, Vec<StructuredInstruction>> {
-        let structured_instructions = structure_all_instructions(tx);
-        filter_instructions(
-            &structured_instructions,
-            &account_keys,
-            &self.program_ids.clone(),
-        )
+        account_keys: &[String],
+    ) -> Result<HashMap<String, Vec<StructuredInstruction>>, DecodeErrorKind> {
+        let mut structured_instructions = structure_all_instructions(tx)?;
+        attach_program_data_logs(
+            &mut structured_instructions,
+            get_program_data_logs(tx, account_keys)?,
+        );
+        Ok(filter_instructions(&structured_instructions, account_keys, &self.program_ids))
     }
 
-    pub fn decode_transaction(&self, tx: &SubscribeUpdateTransaction) -> Vec<DecodedEvent> {
-        let mut ret: Vec<DecodedEvent> = vec![];
-        let account_keys = get_account_keys(tx);
-        let ids_and_ixs = self.get_parsers_and_instructions(tx, &account_keys);
-        let keys = ids_and_ixs.keys().cloned().collect::<Vec<String>>();
-        keys.iter().for_each(|key| {
-            if let Some(parser) = self.parsers.get(key) {
-                let mut results = parser
-                    .decode_instructions(ids_and_ixs.get(key).unwrap().clone(), &account_keys, tx);
+    /// An update without its message or meta decodes to a report carrying just that error.
+    pub fn decode_transaction(&self, tx: &SubscribeUpdateTransaction) -> DecodeReport {
+        let mut ret = DecodeReport::default();
+        let (account_keys, ids_and_ixs) = match get_account_keys(tx).and_then(|account_keys| {
+            let ids_and_ixs = self.get_parsers_and_instructions(tx, &account_keys)?;
+            Ok((account_keys, ids_and_ixs))
+        }) {
+            Ok(decoded) => decoded,
+            Err(kind) => {
+                ret.errors.push(DecodeError::for_transaction(kind));
+                return ret;
+            }
+        };
+        for (program_id, instructions) in ids_and_ixs {
+            if let Some(parser) = self.parsers.get(&program_id) {
+                let mut results = parser.decode_instructions(instructions, &account_keys, tx);
                 ret.append(&mut results);
             }
-        });
+        }
+        // parsers run in no particular order; report events in execution order
+        ret.events.sort_by(|a, b| a.instruction_path.cmp(&b.instruction_path));
+        for parser in self.parsers.values() {
+            parser.link_events(&mut ret);
+        }
         ret
     }
 }
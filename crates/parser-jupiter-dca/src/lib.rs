@@ -0,0 +1,359 @@
+use instruction_parser::InstructionParser;
+use types::{
+    DecodeError, DecodeErrorKind, DecodeReport, DecodedEvent, DecodedInstruction,
+    DecodedJupiterDcaCloseEvent, DecodedJupiterDcaEvent, DecodedJupiterDcaFillEvent,
+    DecodedJupiterDcaInitiateFillEvent, DecodedJupiterDcaOpenEvent, StructuredInstruction,
+};
+use utils::{find_event, get_account, read_bool, read_i64, read_pubkey, read_u64};
+use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
+
+#[derive(Clone, Debug, Default)]
+pub struct JupiterDcaInstructionParser {}
+
+impl InstructionParser for JupiterDcaInstructionParser {
+    fn get_program_id(&self) -> &str {
+        Self::PROGRAM_ID
+    }
+
+    fn decode_instructions(
+        &self,
+        instructions: Vec<StructuredInstruction>,
+        account_keys: &[String],
+        _transaction: &SubscribeUpdateTransaction,
+    ) -> DecodeReport {
+        let mut report = DecodeReport::default();
+        for instruction in instructions.iter() {
+            match self.decode_instruction(instruction, account_keys) {
+                Ok(Some(decoded_instruction)) => report.events.push(DecodedInstruction::new(
+                    Self::PROGRAM_ID,
+                    instruction,
+                    DecodedEvent::JupiterDca(decoded_instruction),
+                )),
+                Ok(None) => {}
+                Err(kind) => report
+                    .errors
+                    .push(DecodeError::new(Self::PROGRAM_ID, instruction, 8, kind)),
+            }
+        }
+        report
+    }
+
+    fn link_events(&self, report: &mut DecodeReport) {
+        for index in 0..report.events.len() {
+            if report.events[index].program_id != Self::PROGRAM_ID {
+                continue;
+            }
+            let swaps = report.fill_event_indexes(index);
+            if let DecodedEvent::JupiterDca(DecodedJupiterDcaEvent::Fill(fill)) =
+                &mut report.events[index].event
+            {
+                fill.swaps = swaps;
+            }
+        }
+    }
+}
+
+impl JupiterDcaInstructionParser {
+    const PROGRAM_ID: &'static str = "DCA265Vj8a9CEuX1eb1LWRnDT7uK6q1xMipnNyatn23M";
+    const OPEN_DCA_DISCRIMINATOR: [u8; 8] = [36, 65, 185, 54, 1, 210, 100, 163];
+    const OPEN_DCA_V2_DISCRIMINATOR: [u8; 8] = [142, 119, 43, 109, 162, 52, 11, 177];
+    const CLOSE_DCA_DISCRIMINATOR: [u8; 8] = [22, 7, 33, 98, 168, 183, 34, 243];
+    const END_AND_CLOSE_DISCRIMINATOR: [u8; 8] = [83, 125, 166, 69, 247, 252, 103, 133];
+    const FULFILL_FLASH_FILL_DISCRIMINATOR: [u8; 8] = [115, 64, 226, 78, 33, 211, 105, 162];
+    const FULFILL_DLMM_FILL_DISCRIMINATOR: [u8; 8] = [1, 230, 118, 251, 45, 177, 101, 187];
+    const INITIATE_FLASH_FILL_DISCRIMINATOR: [u8; 8] = [143, 205, 3, 191, 162, 215, 245, 49];
+    const INITIATE_DLMM_FILL_DISCRIMINATOR: [u8; 8] = [155, 193, 80, 121, 91, 147, 254, 187];
+    const OPENED_EVENT_DISCRIMINATOR: [u8; 8] = [166, 172, 97, 9, 77, 76, 189, 109];
+    const FILLED_EVENT_DISCRIMINATOR: [u8; 8] = [134, 4, 17, 63, 221, 45, 177, 173];
+    const CLOSED_EVENT_DISCRIMINATOR: [u8; 8] = [50, 31, 87, 155, 135, 220, 195, 239];
+
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn decode_instruction(
+        &self,
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Result<Option<DecodedJupiterDcaEvent>, DecodeErrorKind> {
+        let discriminator = match instruction.data.get(..8) {
+            Some(discriminator) => discriminator,
+            None => return Ok(None),
+        };
+        if discriminator == Self::OPEN_DCA_DISCRIMINATOR
+            || discriminator == Self::OPEN_DCA_V2_DISCRIMINATOR
+        {
+            return Ok(Some(DecodedJupiterDcaEvent::Open(Self::decode_open_event(instruction)?)));
+        } else if discriminator == Self::CLOSE_DCA_DISCRIMINATOR
+            || discriminator == Self::END_AND_CLOSE_DISCRIMINATOR
+        {
+            return Ok(Some(DecodedJupiterDcaEvent::Close(Self::decode_close_event(
+                instruction,
+            )?)));
+        } else if discriminator == Self::FULFILL_FLASH_FILL_DISCRIMINATOR
+            || discriminator == Self::FULFILL_DLMM_FILL_DISCRIMINATOR
+        {
+            return Ok(Some(DecodedJupiterDcaEvent::Fill(Self::decode_fill_event(instruction)?)));
+        } else if discriminator == Self::INITIATE_FLASH_FILL_DISCRIMINATOR
+            || discriminator == Self::INITIATE_DLMM_FILL_DISCRIMINATOR
+        {
+            return Ok(Some(DecodedJupiterDcaEvent::InitiateFill(
+                Self::decode_initiate_fill_event(instruction, account_keys)?,
+            )));
+        }
+        Ok(None)
+    }
+
+    /// `Opened`: user_key, dca_key, in_deposited, input_mint, output_mint, cycle_frequency,
+    /// in_amount_per_cycle, created_at
+    pub fn decode_open_event(
+        instruction: &StructuredInstruction,
+    ) -> Result<DecodedJupiterDcaOpenEvent, DecodeErrorKind> {
+        let data = find_event(instruction, &Self::OPENED_EVENT_DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)?;
+        let mut offset: usize = 0;
+        let user = read_pubkey(data, offset)?;
+        offset += 32;
+        let dca = read_pubkey(data, offset)?;
+        offset += 32;
+        let in_deposited = read_u64(data, offset)?;
+        offset += 8;
+        let input_mint = read_pubkey(data, offset)?;
+        offset += 32;
+        let output_mint = read_pubkey(data, offset)?;
+        offset += 32;
+        let cycle_frequency = read_i64(data, offset)?;
+        offset += 8;
+        let in_amount_per_cycle = read_u64(data, offset)?;
+        offset += 8;
+        let created_at = read_i64(data, offset)?;
+        Ok(DecodedJupiterDcaOpenEvent {
+            dca,
+            user,
+            input_mint,
+            output_mint,
+            in_deposited,
+            in_amount_per_cycle,
+            cycle_frequency,
+            created_at,
+        })
+    }
+
+    /// `Closed`: user_key, dca_key, in_deposited, input_mint, output_mint, cycle_frequency,
+    /// in_amount_per_cycle, created_at, total_in_withdrawn, total_out_withdrawn,
+    /// unfilled_amount, user_closed
+    pub fn decode_close_event(
+        instruction: &StructuredInstruction,
+    ) -> Result<DecodedJupiterDcaCloseEvent, DecodeErrorKind> {
+        let data = find_event(instruction, &Self::CLOSED_EVENT_DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)?;
+        let mut offset: usize = 0;
+        let user = read_pubkey(data, offset)?;
+        offset += 32;
+        let dca = read_pubkey(data, offset)?;
+        offset += 40;
+        let input_mint = read_pubkey(data, offset)?;
+        offset += 32;
+        let output_mint = read_pubkey(data, offset)?;
+        // cycle_frequency, in_amount_per_cycle, created_at
+        offset += 56;
+        let total_in_withdrawn = read_u64(data, offset)?;
+        offset += 8;
+        let total_out_withdrawn = read_u64(data, offset)?;
+        offset += 8;
+        let unfilled_amount = read_u64(data, offset)?;
+        offset += 8;
+        let user_closed = read_bool(data, offset)?;
+        Ok(DecodedJupiterDcaCloseEvent {
+            dca,
+            user,
+            input_mint,
+            output_mint,
+            total_in_withdrawn,
+            total_out_withdrawn,
+            unfilled_amount,
+            user_closed,
+        })
+    }
+
+    /// Accounts: [keeper, dca, input_mint, keeper_in_ata, in_ata, out_ata, ...]
+    pub fn decode_initiate_fill_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Result<DecodedJupiterDcaInitiateFillEvent, DecodeErrorKind> {
+        let keeper = get_account(instruction, account_keys, 0)?;
+        let dca = get_account(instruction, account_keys, 1)?;
+        Ok(DecodedJupiterDcaInitiateFillEvent { dca, keeper })
+    }
+
+    /// `Filled`: user_key, dca_key, input_mint, output_mint, in_amount, out_amount, fee_mint, fee
+    pub fn decode_fill_event(
+        instruction: &StructuredInstruction,
+    ) -> Result<DecodedJupiterDcaFillEvent, DecodeErrorKind> {
+        let data = find_event(instruction, &Self::FILLED_EVENT_DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)?;
+        let mut offset: usize = 0;
+        let user = read_pubkey(data, offset)?;
+        offset += 32;
+        let dca = read_pubkey(data, offset)?;
+        offset += 32;
+        let input_mint = read_pubkey(data, offset)?;
+        offset += 32;
+        let output_mint = read_pubkey(data, offset)?;
+        offset += 32;
+        let in_amount = read_u64(data, offset)?;
+        offset += 8;
+        let out_amount = read_u64(data, offset)?;
+        offset += 8;
+        let fee_mint = read_pubkey(data, offset)?;
+        offset += 32;
+        let fee = read_u64(data, offset)?;
+        Ok(DecodedJupiterDcaFillEvent {
+            dca,
+            user,
+            input_mint,
+            output_mint,
+            in_amount,
+            out_amount,
+            fee_mint,
+            fee,
+            swaps: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::testing::{USDC_MINT, USER, WSOL_MINT, event_cpi, pubkey};
+
+    const DCA: &str = "3Kzk1Fp3JH7ndXnBV2fDTf8e1qAr1mCdZTqXsYEr8Y2Q";
+    const KEEPER: &str = "JD38n7ynKYcgPpF7k1BhXEeREu1KqptU93fVGy3S624k";
+
+    fn account_keys() -> Vec<String> {
+        vec![
+            KEEPER.to_string(),
+            DCA.to_string(),
+            JupiterDcaInstructionParser::PROGRAM_ID.to_string(),
+        ]
+    }
+
+    /// An instruction of the program emitting `event` through a self-CPI.
+    fn instruction(discriminator: [u8; 8], event: Vec<u8>) -> StructuredInstruction {
+        StructuredInstruction {
+            account_key_indexes: vec![0, 1],
+            program_id_index: 2,
+            data: discriminator.to_vec(),
+            inner_instructions: vec![event_cpi(2, vec![0, 0], &event)],
+            stack_height: 1,
+            path: vec![0],
+            program_data_logs: Vec::new(),
+        }
+    }
+
+    fn decode(instruction: &StructuredInstruction) -> DecodedJupiterDcaEvent {
+        JupiterDcaInstructionParser::new()
+            .decode_instruction(instruction, &account_keys())
+            .unwrap()
+            .unwrap()
+    }
+
+    /// The fields `Opened` and `Closed` share: 100 USDC into SOL, 10 USDC an hour.
+    fn dca_fields() -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend(pubkey(USER));
+        data.extend(pubkey(DCA));
+        data.extend(100_000_000u64.to_le_bytes()); // in_deposited
+        data.extend(pubkey(USDC_MINT));
+        data.extend(pubkey(WSOL_MINT));
+        data.extend(3_600i64.to_le_bytes()); // cycle_frequency
+        data.extend(10_000_000u64.to_le_bytes()); // in_amount_per_cycle
+        data.extend(1_750_000_000i64.to_le_bytes()); // created_at
+        data
+    }
+
+    #[test]
+    fn decodes_an_opened_event() {
+        let mut event = JupiterDcaInstructionParser::OPENED_EVENT_DISCRIMINATOR.to_vec();
+        event.extend(dca_fields());
+        let DecodedJupiterDcaEvent::Open(open) = decode(&instruction(
+            JupiterDcaInstructionParser::OPEN_DCA_V2_DISCRIMINATOR,
+            event,
+        )) else {
+            panic!("expected an opened DCA");
+        };
+        assert_eq!(open.user, USER);
+        assert_eq!(open.dca, DCA);
+        assert_eq!(open.input_mint, USDC_MINT);
+        assert_eq!(open.output_mint, WSOL_MINT);
+        assert_eq!(open.in_deposited, 100_000_000);
+        assert_eq!(open.in_amount_per_cycle, 10_000_000);
+        assert_eq!(open.cycle_frequency, 3_600);
+        assert_eq!(open.created_at, 1_750_000_000);
+    }
+
+    #[test]
+    fn decodes_a_closed_event() {
+        let mut event = JupiterDcaInstructionParser::CLOSED_EVENT_DISCRIMINATOR.to_vec();
+        event.extend(dca_fields());
+        event.extend(100_000_000u64.to_le_bytes()); // total_in_withdrawn
+        event.extend(662_345_678u64.to_le_bytes()); // total_out_withdrawn
+        event.extend(0u64.to_le_bytes()); // unfilled_amount
+        event.push(0); // user_closed
+        let DecodedJupiterDcaEvent::Close(close) = decode(&instruction(
+            JupiterDcaInstructionParser::END_AND_CLOSE_DISCRIMINATOR,
+            event,
+        )) else {
+            panic!("expected a closed DCA");
+        };
+        assert_eq!(close.user, USER);
+        assert_eq!(close.dca, DCA);
+        assert_eq!(close.input_mint, USDC_MINT);
+        assert_eq!(close.output_mint, WSOL_MINT);
+        assert_eq!(close.total_in_withdrawn, 100_000_000);
+        assert_eq!(close.total_out_withdrawn, 662_345_678);
+        assert_eq!(close.unfilled_amount, 0);
+        assert!(!close.user_closed);
+    }
+
+    #[test]
+    fn decodes_a_filled_event() {
+        let mut event = JupiterDcaInstructionParser::FILLED_EVENT_DISCRIMINATOR.to_vec();
+        event.extend(pubkey(USER));
+        event.extend(pubkey(DCA));
+        event.extend(pubkey(USDC_MINT));
+        event.extend(pubkey(WSOL_MINT));
+        event.extend(10_000_000u64.to_le_bytes()); // in_amount
+        event.extend(66_234_567u64.to_le_bytes()); // out_amount
+        event.extend(pubkey(WSOL_MINT)); // fee_mint
+        event.extend(66_234u64.to_le_bytes()); // fee
+        let DecodedJupiterDcaEvent::Fill(fill) = decode(&instruction(
+            JupiterDcaInstructionParser::FULFILL_FLASH_FILL_DISCRIMINATOR,
+            event,
+        )) else {
+            panic!("expected a fill");
+        };
+        assert_eq!(fill.user, USER);
+        assert_eq!(fill.dca, DCA);
+        assert_eq!(fill.in_amount, 10_000_000);
+        assert_eq!(fill.out_amount, 66_234_567);
+        assert_eq!(fill.fee_mint, WSOL_MINT);
+        assert_eq!(fill.fee, 66_234);
+        assert!(fill.swaps.is_empty());
+    }
+
+    #[test]
+    fn decodes_the_keeper_and_dca_of_an_initiated_fill() {
+        // nothing is emitted until the fill is fulfilled
+        let mut initiate = instruction(
+            JupiterDcaInstructionParser::INITIATE_DLMM_FILL_DISCRIMINATOR,
+            Vec::new(),
+        );
+        initiate.inner_instructions.clear();
+        let DecodedJupiterDcaEvent::InitiateFill(initiate) = decode(&initiate) else {
+            panic!("expected an initiated fill");
+        };
+        assert_eq!(initiate.keeper, KEEPER);
+        assert_eq!(initiate.dca, DCA);
+    }
+}
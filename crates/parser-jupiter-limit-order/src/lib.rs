@@ -0,0 +1,363 @@
+use instruction_parser::InstructionParser;
+use types::{
+    DecodeError, DecodeErrorKind, DecodeReport, DecodedEvent, DecodedInstruction,
+    DecodedJupiterLimitOrderCancelEvent, DecodedJupiterLimitOrderCreateEvent,
+    DecodedJupiterLimitOrderEvent, DecodedJupiterLimitOrderFillEvent,
+    DecodedJupiterLimitOrderPreFlashFillEvent, StructuredInstruction,
+};
+use utils::{find_event, get_account, read_bool, read_i64, read_pubkey, read_u64};
+use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
+
+#[derive(Clone, Debug, Default)]
+pub struct JupiterLimitOrderInstructionParser {}
+
+impl InstructionParser for JupiterLimitOrderInstructionParser {
+    fn get_program_id(&self) -> &str {
+        Self::PROGRAM_ID
+    }
+
+    fn decode_instructions(
+        &self,
+        instructions: Vec<StructuredInstruction>,
+        account_keys: &[String],
+        _transaction: &SubscribeUpdateTransaction,
+    ) -> DecodeReport {
+        let mut report = DecodeReport::default();
+        for instruction in instructions.iter() {
+            match self.decode_instruction(instruction, account_keys) {
+                Ok(Some(decoded_instruction)) => report.events.push(DecodedInstruction::new(
+                    Self::PROGRAM_ID,
+                    instruction,
+                    DecodedEvent::JupiterLimitOrder(decoded_instruction),
+                )),
+                Ok(None) => {}
+                Err(kind) => report
+                    .errors
+                    .push(DecodeError::new(Self::PROGRAM_ID, instruction, 8, kind)),
+            }
+        }
+        report
+    }
+
+    fn link_events(&self, report: &mut DecodeReport) {
+        for index in 0..report.events.len() {
+            if report.events[index].program_id != Self::PROGRAM_ID {
+                continue;
+            }
+            let swaps = report.fill_event_indexes(index);
+            if let DecodedEvent::JupiterLimitOrder(DecodedJupiterLimitOrderEvent::Fill(fill)) =
+                &mut report.events[index].event
+            {
+                fill.swaps = swaps;
+            }
+        }
+    }
+}
+
+impl JupiterLimitOrderInstructionParser {
+    const PROGRAM_ID: &'static str = "j1o2qRpjcyUwEvwtcfhEQefh773ZgjxcVRry7LDqg5X";
+    const INITIALIZE_ORDER_DISCRIMINATOR: [u8; 8] = [133, 110, 74, 175, 112, 159, 245, 159];
+    const CANCEL_ORDER_DISCRIMINATOR: [u8; 8] = [95, 129, 237, 240, 8, 49, 223, 132];
+    const FILL_ORDER_DISCRIMINATOR: [u8; 8] = [232, 122, 115, 25, 199, 143, 136, 162];
+    const FLASH_FILL_ORDER_DISCRIMINATOR: [u8; 8] = [252, 104, 18, 134, 164, 78, 18, 140];
+    const PRE_FLASH_FILL_ORDER_DISCRIMINATOR: [u8; 8] = [240, 47, 153, 68, 13, 190, 225, 42];
+    const CREATE_ORDER_EVENT_DISCRIMINATOR: [u8; 8] = [49, 142, 72, 166, 230, 29, 84, 84];
+    const CANCEL_ORDER_EVENT_DISCRIMINATOR: [u8; 8] = [174, 66, 141, 17, 4, 224, 162, 77];
+    const TRADE_EVENT_DISCRIMINATOR: [u8; 8] = [189, 219, 127, 211, 78, 230, 97, 238];
+
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn decode_instruction(
+        &self,
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Result<Option<DecodedJupiterLimitOrderEvent>, DecodeErrorKind> {
+        let discriminator = match instruction.data.get(..8) {
+            Some(discriminator) => discriminator,
+            None => return Ok(None),
+        };
+        if discriminator == Self::INITIALIZE_ORDER_DISCRIMINATOR {
+            return Ok(Some(DecodedJupiterLimitOrderEvent::CreateOrder(
+                Self::decode_create_order_event(instruction)?,
+            )));
+        } else if discriminator == Self::CANCEL_ORDER_DISCRIMINATOR {
+            return Ok(Some(DecodedJupiterLimitOrderEvent::CancelOrder(
+                Self::decode_cancel_order_event(instruction, account_keys)?,
+            )));
+        } else if discriminator == Self::FILL_ORDER_DISCRIMINATOR
+            || discriminator == Self::FLASH_FILL_ORDER_DISCRIMINATOR
+        {
+            return Ok(Some(DecodedJupiterLimitOrderEvent::Fill(Self::decode_fill_event(
+                instruction,
+            )?)));
+        } else if discriminator == Self::PRE_FLASH_FILL_ORDER_DISCRIMINATOR {
+            return Ok(Some(DecodedJupiterLimitOrderEvent::PreFlashFill(
+                Self::decode_pre_flash_fill_event(instruction, account_keys)?,
+            )));
+        }
+        Ok(None)
+    }
+
+    /// `CreateOrderEvent`: order_key, maker, input_mint, output_mint, input_token_program,
+    /// output_token_program, making_amount, taking_amount, expired_at, ...
+    pub fn decode_create_order_event(
+        instruction: &StructuredInstruction,
+    ) -> Result<DecodedJupiterLimitOrderCreateEvent, DecodeErrorKind> {
+        let data = find_event(instruction, &Self::CREATE_ORDER_EVENT_DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)?;
+        let mut offset: usize = 0;
+        let order = read_pubkey(data, offset)?;
+        offset += 32;
+        let maker = read_pubkey(data, offset)?;
+        offset += 32;
+        let input_mint = read_pubkey(data, offset)?;
+        offset += 32;
+        let output_mint = read_pubkey(data, offset)?;
+        offset += 96;
+        let making_amount = read_u64(data, offset)?;
+        offset += 8;
+        let taking_amount = read_u64(data, offset)?;
+        offset += 8;
+        let expired_at = if read_bool(data, offset)? {
+            Some(read_i64(data, offset + 1)?)
+        } else {
+            None
+        };
+        Ok(DecodedJupiterLimitOrderCreateEvent {
+            order,
+            maker,
+            input_mint,
+            output_mint,
+            making_amount,
+            taking_amount,
+            expired_at,
+        })
+    }
+
+    /// Accounts: [signer, maker, order, ...]
+    pub fn decode_cancel_order_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Result<DecodedJupiterLimitOrderCancelEvent, DecodeErrorKind> {
+        let maker = get_account(instruction, account_keys, 1)?;
+        let data = find_event(instruction, &Self::CANCEL_ORDER_EVENT_DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)?;
+        let order = read_pubkey(data, 0)?;
+        Ok(DecodedJupiterLimitOrderCancelEvent { order, maker })
+    }
+
+    /// Accounts: [taker, order, ...]
+    ///
+    /// Args: making_amount
+    pub fn decode_pre_flash_fill_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Result<DecodedJupiterLimitOrderPreFlashFillEvent, DecodeErrorKind> {
+        let taker = get_account(instruction, account_keys, 0)?;
+        let order = get_account(instruction, account_keys, 1)?;
+        let making_amount = read_u64(&instruction.data, 8)?;
+        Ok(DecodedJupiterLimitOrderPreFlashFillEvent { order, taker, making_amount })
+    }
+
+    /// `TradeEvent`: order_key, taker, remaining_making_amount, remaining_taking_amount,
+    /// making_amount, taking_amount
+    pub fn decode_fill_event(
+        instruction: &StructuredInstruction,
+    ) -> Result<DecodedJupiterLimitOrderFillEvent, DecodeErrorKind> {
+        let data = find_event(instruction, &Self::TRADE_EVENT_DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)?;
+        let mut offset: usize = 0;
+        let order = read_pubkey(data, offset)?;
+        offset += 32;
+        let taker = read_pubkey(data, offset)?;
+        offset += 32;
+        let remaining_making_amount = read_u64(data, offset)?;
+        offset += 8;
+        let remaining_taking_amount = read_u64(data, offset)?;
+        offset += 8;
+        let making_amount = read_u64(data, offset)?;
+        offset += 8;
+        let taking_amount = read_u64(data, offset)?;
+        Ok(DecodedJupiterLimitOrderFillEvent {
+            order,
+            taker,
+            making_amount,
+            taking_amount,
+            remaining_making_amount,
+            remaining_taking_amount,
+            swaps: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{DecodedJupiterEvent, DecodedJupiterRouteEvent, JupiterRouteType};
+    use utils::testing::{USDC_MINT, WSOL_MINT, event_cpi, pubkey};
+
+    const MAKER: &str = "7u7cD7NxcZEuzRCBaYo8uVpotRdqZwez47vvuwzCov43";
+    const ORDER: &str = "3Kzk1Fp3JH7ndXnBV2fDTf8e1qAr1mCdZTqXsYEr8Y2Q";
+    const TAKER: &str = "JD38n7ynKYcgPpF7k1BhXEeREu1KqptU93fVGy3S624k";
+    const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+    fn account_keys() -> Vec<String> {
+        vec![
+            TAKER.to_string(),
+            MAKER.to_string(),
+            ORDER.to_string(),
+            JupiterLimitOrderInstructionParser::PROGRAM_ID.to_string(),
+        ]
+    }
+
+    /// An instruction of the program at `path` with the accounts [taker, maker, order],
+    /// emitting `events` through self-CPIs.
+    fn instruction(path: usize, data: Vec<u8>, events: Vec<Vec<u8>>) -> StructuredInstruction {
+        let inner_instructions = events
+            .into_iter()
+            .enumerate()
+            .map(|(index, event)| event_cpi(3, vec![path, index], &event))
+            .collect();
+        StructuredInstruction {
+            account_key_indexes: vec![0, 1, 2],
+            program_id_index: 3,
+            data,
+            inner_instructions,
+            stack_height: 1,
+            path: vec![path],
+            program_data_logs: Vec::new(),
+        }
+    }
+
+    fn decode(instruction: &StructuredInstruction) -> DecodedJupiterLimitOrderEvent {
+        JupiterLimitOrderInstructionParser::new()
+            .decode_instruction(instruction, &account_keys())
+            .unwrap()
+            .unwrap()
+    }
+
+    /// The `TradeEvent` of a fill selling the whole 1 SOL order for 151.5 USDC.
+    fn trade_event() -> Vec<u8> {
+        let mut event = JupiterLimitOrderInstructionParser::TRADE_EVENT_DISCRIMINATOR.to_vec();
+        event.extend(pubkey(ORDER));
+        event.extend(pubkey(TAKER));
+        event.extend(0u64.to_le_bytes()); // remaining_making_amount
+        event.extend(0u64.to_le_bytes()); // remaining_taking_amount
+        event.extend(1_000_000_000u64.to_le_bytes()); // making_amount
+        event.extend(151_500_000u64.to_le_bytes()); // taking_amount
+        event
+    }
+
+    #[test]
+    fn decodes_a_create_order_event() {
+        let mut event =
+            JupiterLimitOrderInstructionParser::CREATE_ORDER_EVENT_DISCRIMINATOR.to_vec();
+        event.extend(pubkey(ORDER));
+        event.extend(pubkey(MAKER));
+        event.extend(pubkey(WSOL_MINT));
+        event.extend(pubkey(USDC_MINT));
+        event.extend(pubkey(TOKEN_PROGRAM)); // input_token_program
+        event.extend(pubkey(TOKEN_PROGRAM)); // output_token_program
+        event.extend(1_000_000_000u64.to_le_bytes()); // making_amount
+        event.extend(151_500_000u64.to_le_bytes()); // taking_amount
+        event.push(1);
+        event.extend(1_750_086_400i64.to_le_bytes()); // expired_at
+        event.extend(10u16.to_le_bytes()); // fee_bps
+        event.extend(pubkey(MAKER)); // fee_account
+        let data = JupiterLimitOrderInstructionParser::INITIALIZE_ORDER_DISCRIMINATOR.to_vec();
+
+        let DecodedJupiterLimitOrderEvent::CreateOrder(create) =
+            decode(&instruction(0, data, vec![event]))
+        else {
+            panic!("expected a created order");
+        };
+        assert_eq!(create.order, ORDER);
+        assert_eq!(create.maker, MAKER);
+        assert_eq!(create.input_mint, WSOL_MINT);
+        assert_eq!(create.output_mint, USDC_MINT);
+        assert_eq!(create.making_amount, 1_000_000_000);
+        assert_eq!(create.taking_amount, 151_500_000);
+        assert_eq!(create.expired_at, Some(1_750_086_400));
+    }
+
+    #[test]
+    fn decodes_a_cancel_order_event() {
+        let mut event =
+            JupiterLimitOrderInstructionParser::CANCEL_ORDER_EVENT_DISCRIMINATOR.to_vec();
+        event.extend(pubkey(ORDER));
+        let data = JupiterLimitOrderInstructionParser::CANCEL_ORDER_DISCRIMINATOR.to_vec();
+
+        let DecodedJupiterLimitOrderEvent::CancelOrder(cancel) =
+            decode(&instruction(0, data, vec![event]))
+        else {
+            panic!("expected a cancelled order");
+        };
+        assert_eq!(cancel.order, ORDER);
+        assert_eq!(cancel.maker, MAKER);
+    }
+
+    #[test]
+    fn links_a_flash_fill_to_the_swap_since_its_pre_flash_fill() {
+        let mut pre_flash_fill =
+            JupiterLimitOrderInstructionParser::PRE_FLASH_FILL_ORDER_DISCRIMINATOR.to_vec();
+        pre_flash_fill.extend(1_000_000_000u64.to_le_bytes()); // making_amount
+        let flash_fill =
+            JupiterLimitOrderInstructionParser::FLASH_FILL_ORDER_DISCRIMINATOR.to_vec();
+        let mut pre_flash_fill = instruction(0, pre_flash_fill, Vec::new());
+        // [taker, order, ...]
+        pre_flash_fill.account_key_indexes = vec![0, 2];
+        let parser = JupiterLimitOrderInstructionParser::new();
+        let mut report = parser.decode_instructions(
+            vec![
+                pre_flash_fill,
+                instruction(2, flash_fill, vec![trade_event()]),
+            ],
+            &account_keys(),
+            &SubscribeUpdateTransaction::default(),
+        );
+        assert!(report.errors.is_empty());
+        // the keeper's swap between the two, as the Jupiter parser reports it
+        report.events.insert(
+            1,
+            DecodedInstruction {
+                program_id: "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4".to_string(),
+                instruction_path: vec![1],
+                event: DecodedEvent::Jupiter(DecodedJupiterEvent::Route(
+                    DecodedJupiterRouteEvent {
+                        user: TAKER.to_string(),
+                        input_mint: WSOL_MINT.to_string(),
+                        output_mint: USDC_MINT.to_string(),
+                        input_amount: 1_000_000_000,
+                        output_amount: 151_600_000,
+                        route_type: JupiterRouteType::Route,
+                        swaps: Vec::new(),
+                        hops: Vec::new(),
+                    },
+                )),
+            },
+        );
+        parser.link_events(&mut report);
+
+        let DecodedEvent::JupiterLimitOrder(DecodedJupiterLimitOrderEvent::PreFlashFill(pre)) =
+            &report.events[0].event
+        else {
+            panic!("expected a pre flash fill");
+        };
+        assert_eq!(pre.taker, TAKER);
+        assert_eq!(pre.order, ORDER);
+        assert_eq!(pre.making_amount, 1_000_000_000);
+        let DecodedEvent::JupiterLimitOrder(DecodedJupiterLimitOrderEvent::Fill(fill)) =
+            &report.events[2].event
+        else {
+            panic!("expected a fill");
+        };
+        assert_eq!(fill.order, ORDER);
+        assert_eq!(fill.taker, TAKER);
+        assert_eq!(fill.making_amount, 1_000_000_000);
+        assert_eq!(fill.taking_amount, 151_500_000);
+        assert_eq!(fill.remaining_making_amount, 0);
+        assert_eq!(fill.swaps, [1]);
+    }
+}
@@ -0,0 +1,306 @@
+use instruction_parser::InstructionParser;
+use types::{
+    DecodeError, DecodeErrorKind, DecodeReport, DecodedEvent, DecodedInstruction,
+    DecodedJupiterEvent, DecodedJupiterRouteEvent, DecodedJupiterSwapLog, JupiterRouteType,
+    StructuredInstruction,
+};
+use utils::{find_events, get_account, read_pubkey, read_u32, read_u64};
+use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
+
+#[derive(Clone, Debug, Default)]
+pub struct JupiterInstructionParser {}
+
+impl InstructionParser for JupiterInstructionParser {
+    fn get_program_id(&self) -> &str {
+        Self::PROGRAM_ID
+    }
+
+    fn decode_instructions(
+        &self,
+        instructions: Vec<StructuredInstruction>,
+        account_keys: &[String],
+        _transaction: &SubscribeUpdateTransaction,
+    ) -> DecodeReport {
+        let mut report = DecodeReport::default();
+        for instruction in instructions.iter() {
+            match self.decode_instruction(instruction, account_keys) {
+                Ok(Some(decoded_instruction)) => report.events.push(DecodedInstruction::new(
+                    Self::PROGRAM_ID,
+                    instruction,
+                    DecodedEvent::Jupiter(decoded_instruction),
+                )),
+                Ok(None) => {}
+                Err(kind) => report
+                    .errors
+                    .push(DecodeError::new(Self::PROGRAM_ID, instruction, 8, kind)),
+            }
+        }
+        report
+    }
+
+    fn link_events(&self, report: &mut DecodeReport) {
+        for index in 0..report.events.len() {
+            if report.events[index].program_id != Self::PROGRAM_ID {
+                continue;
+            }
+            let hops = report.nested_event_indexes(&report.events[index].instruction_path);
+            if let DecodedEvent::Jupiter(DecodedJupiterEvent::Route(route)) =
+                &mut report.events[index].event
+            {
+                route.hops = hops;
+            }
+        }
+    }
+}
+
+impl JupiterInstructionParser {
+    const PROGRAM_ID: &'static str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+    const ROUTE_DISCRIMINATOR: [u8; 8] = [229, 23, 203, 151, 122, 227, 173, 42];
+    const SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR: [u8; 8] = [193, 32, 155, 51, 65, 214, 156, 129];
+    const EXACT_OUT_ROUTE_DISCRIMINATOR: [u8; 8] = [208, 51, 239, 151, 123, 43, 237, 92];
+    const SHARED_ACCOUNTS_EXACT_OUT_ROUTE_DISCRIMINATOR: [u8; 8] =
+        [176, 209, 105, 168, 154, 125, 69, 62];
+    const ROUTE_V2_DISCRIMINATOR: [u8; 8] = [187, 100, 250, 204, 49, 196, 175, 20];
+    const SHARED_ACCOUNTS_ROUTE_V2_DISCRIMINATOR: [u8; 8] = [209, 152, 83, 147, 124, 254, 216, 233];
+    const EXACT_OUT_ROUTE_V2_DISCRIMINATOR: [u8; 8] = [157, 138, 184, 82, 21, 244, 243, 36];
+    const SHARED_ACCOUNTS_EXACT_OUT_ROUTE_V2_DISCRIMINATOR: [u8; 8] =
+        [53, 96, 229, 202, 216, 187, 250, 24];
+    const SWAP_EVENT_DISCRIMINATOR: [u8; 8] = [64, 198, 205, 232, 38, 8, 113, 226];
+    const SWAPS_EVENT_DISCRIMINATOR: [u8; 8] = [152, 47, 78, 235, 192, 96, 110, 106];
+
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn decode_instruction(
+        &self,
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Result<Option<DecodedJupiterEvent>, DecodeErrorKind> {
+        let discriminator = match instruction.data.get(..8) {
+            Some(discriminator) => discriminator,
+            None => return Ok(None),
+        };
+        // position of user_transfer_authority in each variant's accounts
+        let (route_type, user_position) = if discriminator == Self::ROUTE_DISCRIMINATOR {
+            (JupiterRouteType::Route, 1)
+        } else if discriminator == Self::SHARED_ACCOUNTS_ROUTE_DISCRIMINATOR {
+            (JupiterRouteType::SharedAccountsRoute, 2)
+        } else if discriminator == Self::EXACT_OUT_ROUTE_DISCRIMINATOR {
+            (JupiterRouteType::ExactOutRoute, 1)
+        } else if discriminator == Self::SHARED_ACCOUNTS_EXACT_OUT_ROUTE_DISCRIMINATOR {
+            (JupiterRouteType::SharedAccountsExactOutRoute, 2)
+        } else if discriminator == Self::ROUTE_V2_DISCRIMINATOR {
+            (JupiterRouteType::RouteV2, 0)
+        } else if discriminator == Self::SHARED_ACCOUNTS_ROUTE_V2_DISCRIMINATOR {
+            (JupiterRouteType::SharedAccountsRouteV2, 1)
+        } else if discriminator == Self::EXACT_OUT_ROUTE_V2_DISCRIMINATOR {
+            (JupiterRouteType::ExactOutRouteV2, 0)
+        } else if discriminator == Self::SHARED_ACCOUNTS_EXACT_OUT_ROUTE_V2_DISCRIMINATOR {
+            (JupiterRouteType::SharedAccountsExactOutRouteV2, 1)
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(DecodedJupiterEvent::Route(Self::decode_route_event(
+            instruction,
+            account_keys,
+            route_type,
+            user_position,
+        )?)))
+    }
+
+    /// The route plan in the instruction args is not decoded: the swap events already carry
+    /// the mints and amounts of every hop.
+    pub fn decode_route_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        route_type: JupiterRouteType,
+        user_position: usize,
+    ) -> Result<DecodedJupiterRouteEvent, DecodeErrorKind> {
+        let user = get_account(instruction, account_keys, user_position)?;
+        let mut swaps = Vec::new();
+        for data in find_events(instruction, &Self::SWAP_EVENT_DISCRIMINATOR) {
+            swaps.push(Self::decode_swap_log(data)?);
+        }
+        for data in find_events(instruction, &Self::SWAPS_EVENT_DISCRIMINATOR) {
+            swaps.append(&mut Self::decode_swaps_log(data)?);
+        }
+        let (first, last) = match (swaps.first(), swaps.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Err(DecodeErrorKind::MissingEventLog),
+        };
+        let input_mint = first.input_mint.clone();
+        let output_mint = last.output_mint.clone();
+
+        // split routes spend the input and collect the output over several hops
+        let (input_amount, output_amount) = if input_mint == output_mint {
+            (first.input_amount, last.output_amount)
+        } else {
+            (
+                swaps
+                    .iter()
+                    .filter(|swap| swap.input_mint == input_mint)
+                    .map(|swap| swap.input_amount)
+                    .sum(),
+                swaps
+                    .iter()
+                    .filter(|swap| swap.output_mint == output_mint)
+                    .map(|swap| swap.output_amount)
+                    .sum(),
+            )
+        };
+
+        Ok(DecodedJupiterRouteEvent {
+            user,
+            input_mint,
+            output_mint,
+            input_amount,
+            output_amount,
+            route_type,
+            swaps,
+            hops: Vec::new(),
+        })
+    }
+
+    /// `SwapEvent`: amm, input_mint, input_amount, output_mint, output_amount
+    pub fn decode_swap_log(data: &[u8]) -> Result<DecodedJupiterSwapLog, DecodeErrorKind> {
+        let amm = read_pubkey(data, 0)?;
+        let mut swap_log = Self::decode_swap_log_v2(data, 32)?;
+        swap_log.amm = Some(amm);
+        Ok(swap_log)
+    }
+
+    /// `SwapsEvent`: a vector of input_mint, input_amount, output_mint, output_amount
+    pub fn decode_swaps_log(data: &[u8]) -> Result<Vec<DecodedJupiterSwapLog>, DecodeErrorKind> {
+        let len = read_u32(data, 0)? as usize;
+        let mut swaps = Vec::new();
+        for index in 0..len {
+            swaps.push(Self::decode_swap_log_v2(data, 4 + index * 80)?);
+        }
+        Ok(swaps)
+    }
+
+    fn decode_swap_log_v2(
+        data: &[u8],
+        mut offset: usize,
+    ) -> Result<DecodedJupiterSwapLog, DecodeErrorKind> {
+        let input_mint = read_pubkey(data, offset)?;
+        offset += 32;
+        let input_amount = read_u64(data, offset)?;
+        offset += 8;
+        let output_mint = read_pubkey(data, offset)?;
+        offset += 32;
+        let output_amount = read_u64(data, offset)?;
+        Ok(DecodedJupiterSwapLog {
+            amm: None,
+            input_mint,
+            input_amount,
+            output_mint,
+            output_amount,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::testing::{USDC_MINT, USER, WSOL_MINT, event_cpi, pubkey};
+
+    const WHIRLPOOL: &str = "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc";
+
+    /// A hop as `SwapsEvent` lays it out; `SwapEvent` prefixes it with the AMM.
+    fn hop(input_mint: &str, input_amount: u64, output_mint: &str, output_amount: u64) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend(pubkey(input_mint));
+        data.extend(input_amount.to_le_bytes());
+        data.extend(pubkey(output_mint));
+        data.extend(output_amount.to_le_bytes());
+        data
+    }
+
+    /// A route whose user_transfer_authority is account 0, emitting `events` through self-CPIs.
+    fn route(discriminator: [u8; 8], events: Vec<Vec<u8>>) -> StructuredInstruction {
+        let inner_instructions = events
+            .into_iter()
+            .enumerate()
+            .map(|(index, event)| event_cpi(1, vec![0, index], &event))
+            .collect();
+        StructuredInstruction {
+            account_key_indexes: vec![0, 0, 2],
+            program_id_index: 1,
+            data: discriminator.to_vec(),
+            inner_instructions,
+            stack_height: 1,
+            path: vec![0],
+            program_data_logs: Vec::new(),
+        }
+    }
+
+    fn account_keys() -> Vec<String> {
+        vec![
+            USER.to_string(),
+            JupiterInstructionParser::PROGRAM_ID.to_string(),
+            "D8cy77BBepLMngZx6ZukaTff5hCt1HrWyKk3Hnd9oitf".to_string(), // event_authority
+        ]
+    }
+
+    fn decode(instruction: &StructuredInstruction) -> DecodedJupiterRouteEvent {
+        match JupiterInstructionParser::new().decode_instruction(instruction, &account_keys()) {
+            Ok(Some(DecodedJupiterEvent::Route(route))) => route,
+            other => panic!("expected a route, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn decodes_a_swap_log() {
+        let mut data = pubkey(WHIRLPOOL).to_vec();
+        data.extend(hop(WSOL_MINT, 1_000_000_000, USDC_MINT, 151_234_567));
+
+        let swap = JupiterInstructionParser::decode_swap_log(&data).unwrap();
+        assert_eq!(swap.amm.as_deref(), Some(WHIRLPOOL));
+        assert_eq!(swap.input_mint, WSOL_MINT);
+        assert_eq!(swap.input_amount, 1_000_000_000);
+        assert_eq!(swap.output_mint, USDC_MINT);
+        assert_eq!(swap.output_amount, 151_234_567);
+    }
+
+    #[test]
+    fn sums_the_legs_of_a_split_route() {
+        let mut swaps = JupiterInstructionParser::SWAPS_EVENT_DISCRIMINATOR.to_vec();
+        swaps.extend(2u32.to_le_bytes());
+        swaps.extend(hop(WSOL_MINT, 600_000_000, USDC_MINT, 90_800_000));
+        swaps.extend(hop(WSOL_MINT, 400_000_000, USDC_MINT, 60_500_000));
+        let route = decode(&route(JupiterInstructionParser::ROUTE_V2_DISCRIMINATOR, vec![swaps]));
+
+        assert_eq!(route.user, USER);
+        assert!(matches!(route.route_type, JupiterRouteType::RouteV2));
+        assert_eq!(route.swaps.len(), 2);
+        assert!(route.swaps.iter().all(|swap| swap.amm.is_none()));
+        assert_eq!(route.input_mint, WSOL_MINT);
+        assert_eq!(route.input_amount, 1_000_000_000);
+        assert_eq!(route.output_mint, USDC_MINT);
+        assert_eq!(route.output_amount, 151_300_000);
+    }
+
+    #[test]
+    fn takes_the_first_and_last_hop_of_a_circular_route() {
+        let swap_event = |input_mint, input_amount, output_mint, output_amount| {
+            let mut data = JupiterInstructionParser::SWAP_EVENT_DISCRIMINATOR.to_vec();
+            data.extend(pubkey(WHIRLPOOL));
+            data.extend(hop(input_mint, input_amount, output_mint, output_amount));
+            data
+        };
+        let route = decode(&route(
+            JupiterInstructionParser::ROUTE_DISCRIMINATOR,
+            vec![
+                swap_event(WSOL_MINT, 1_000_000_000, USDC_MINT, 151_234_567),
+                swap_event(USDC_MINT, 151_234_567, WSOL_MINT, 1_000_450_000),
+            ],
+        ));
+
+        assert_eq!(route.input_mint, WSOL_MINT);
+        assert_eq!(route.input_amount, 1_000_000_000);
+        assert_eq!(route.output_mint, WSOL_MINT);
+        assert_eq!(route.output_amount, 1_000_450_000);
+    }
+}
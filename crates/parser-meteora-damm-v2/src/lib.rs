@@ -0,0 +1,369 @@
+use instruction_parser::InstructionParser;
+use types::{
+    DecodeError, DecodeErrorKind, DecodeReport, DecodedEvent, DecodedInstruction,
+    DecodedMeteoraDammV2CreatePoolEvent, DecodedMeteoraDammV2Event,
+    DecodedMeteoraDammV2LiquidityEvent, DecodedMeteoraDammV2SwapEvent,
+    DecodedMeteoraDammV2SwapLog, StructuredInstruction,
+};
+use utils::{
+    find_event, find_token_transfer, get_account, get_instruction_token_balance,
+    get_token_transfers, read_bool, read_pubkey, read_u8, read_u64, read_u128,
+};
+use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
+
+#[derive(Clone, Debug, Default)]
+pub struct MeteoraDammV2InstructionParser {}
+
+impl InstructionParser for MeteoraDammV2InstructionParser {
+    fn get_program_id(&self) -> &str {
+        Self::PROGRAM_ID
+    }
+
+    fn decode_instructions(
+        &self,
+        instructions: Vec<StructuredInstruction>,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> DecodeReport {
+        let mut report = DecodeReport::default();
+        for instruction in instructions.iter() {
+            match self.decode_instruction(instruction, account_keys, transaction) {
+                Ok(Some(decoded_instruction)) => report.events.push(DecodedInstruction::new(
+                    Self::PROGRAM_ID,
+                    instruction,
+                    DecodedEvent::MeteoraDammV2(decoded_instruction),
+                )),
+                Ok(None) => {}
+                Err(kind) => report
+                    .errors
+                    .push(DecodeError::new(Self::PROGRAM_ID, instruction, 8, kind)),
+            }
+        }
+        report
+    }
+}
+
+impl MeteoraDammV2InstructionParser {
+    const PROGRAM_ID: &'static str = "cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG";
+    const SWAP_DISCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+    const ADD_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [181, 157, 89, 67, 143, 182, 52, 72];
+    const REMOVE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [80, 85, 209, 72, 24, 206, 177, 108];
+    const REMOVE_ALL_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [10, 51, 61, 35, 112, 105, 24, 85];
+    const INITIALIZE_POOL_DISCRIMINATOR: [u8; 8] = [95, 180, 10, 172, 84, 174, 232, 40];
+    const INITIALIZE_CUSTOMIZABLE_POOL_DISCRIMINATOR: [u8; 8] = [20, 161, 241, 24, 189, 221, 180, 2];
+    const INITIALIZE_POOL_WITH_DYNAMIC_CONFIG_DISCRIMINATOR: [u8; 8] =
+        [149, 82, 72, 197, 253, 252, 68, 15];
+    const SWAP_EVENT_DISCRIMINATOR: [u8; 8] = [27, 60, 21, 213, 138, 170, 187, 147];
+    const ADD_LIQUIDITY_EVENT_DISCRIMINATOR: [u8; 8] = [175, 242, 8, 157, 30, 247, 185, 169];
+    const REMOVE_LIQUIDITY_EVENT_DISCRIMINATOR: [u8; 8] = [87, 46, 88, 98, 175, 96, 34, 91];
+
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn decode_instruction(
+        &self,
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> Result<Option<DecodedMeteoraDammV2Event>, DecodeErrorKind> {
+        let discriminator = match instruction.data.get(..8) {
+            Some(discriminator) => discriminator,
+            None => return Ok(None),
+        };
+        if discriminator == Self::SWAP_DISCRIMINATOR {
+            return Ok(Some(DecodedMeteoraDammV2Event::Swap(Self::decode_swap_event(
+                instruction,
+                account_keys,
+                transaction,
+            )?)));
+        } else if discriminator == Self::ADD_LIQUIDITY_DISCRIMINATOR {
+            // [pool, position, token_a_account, token_b_account, token_a_vault, token_b_vault,
+            // token_a_mint, token_b_mint, position_nft_account, owner, ...]
+            return Ok(Some(DecodedMeteoraDammV2Event::AddLiquidity(
+                Self::decode_liquidity_event(instruction, account_keys, transaction, 0, true)?,
+            )));
+        } else if discriminator == Self::REMOVE_LIQUIDITY_DISCRIMINATOR
+            || discriminator == Self::REMOVE_ALL_LIQUIDITY_DISCRIMINATOR
+        {
+            // [pool_authority, pool, position, token_a_account, token_b_account, token_a_vault,
+            // token_b_vault, token_a_mint, token_b_mint, position_nft_account, owner, ...]
+            return Ok(Some(DecodedMeteoraDammV2Event::RemoveLiquidity(
+                Self::decode_liquidity_event(instruction, account_keys, transaction, 1, false)?,
+            )));
+        } else if discriminator == Self::INITIALIZE_POOL_DISCRIMINATOR {
+            // [creator, position_nft_mint, position_nft_account, payer, config, pool_authority,
+            // pool, position, token_a_mint, token_b_mint, token_a_vault, token_b_vault, ...]
+            return Ok(Some(DecodedMeteoraDammV2Event::CreatePool(
+                Self::decode_pool_creation_event(instruction, account_keys, 6)?,
+            )));
+        } else if discriminator == Self::INITIALIZE_CUSTOMIZABLE_POOL_DISCRIMINATOR {
+            // [creator, position_nft_mint, position_nft_account, payer, pool_authority, pool,
+            // position, token_a_mint, token_b_mint, token_a_vault, token_b_vault, ...]
+            return Ok(Some(DecodedMeteoraDammV2Event::CreatePool(
+                Self::decode_pool_creation_event(instruction, account_keys, 5)?,
+            )));
+        } else if discriminator == Self::INITIALIZE_POOL_WITH_DYNAMIC_CONFIG_DISCRIMINATOR {
+            // [creator, position_nft_mint, position_nft_account, payer, pool_creator_authority,
+            // config, pool_authority, pool, position, token_a_mint, token_b_mint, token_a_vault,
+            // token_b_vault, ...]
+            return Ok(Some(DecodedMeteoraDammV2Event::CreatePool(
+                Self::decode_pool_creation_event(instruction, account_keys, 7)?,
+            )));
+        }
+        Ok(None)
+    }
+
+    /// Accounts: [pool_authority, pool, input_token_account, output_token_account,
+    /// token_a_vault, token_b_vault, token_a_mint, token_b_mint, payer, ...]
+    pub fn decode_swap_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> Result<DecodedMeteoraDammV2SwapEvent, DecodeErrorKind> {
+        let pool = get_account(instruction, account_keys, 1)?;
+        let user = get_account(instruction, account_keys, 8)?;
+        let swap_log = find_event(instruction, &Self::SWAP_EVENT_DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)?;
+        let decoded_swap_log = Self::decode_swap_log(swap_log)?;
+        let a_to_b = decoded_swap_log.trade_direction == 0;
+        let (vault_in, vault_out) = if a_to_b { (4, 5) } else { (5, 4) };
+        let (mint_in, mint_in_reserve, in_decimals) =
+            get_instruction_token_balance(instruction, transaction, vault_in)?;
+        let (mint_out, mint_out_reserve, out_decimals) =
+            get_instruction_token_balance(instruction, transaction, vault_out)?;
+
+        Ok(DecodedMeteoraDammV2SwapEvent {
+            pool,
+            user,
+            mint_in,
+            mint_out,
+            in_decimals,
+            out_decimals,
+            mint_in_reserve,
+            mint_out_reserve,
+            amount_in: decoded_swap_log.actual_amount_in,
+            amount_out: decoded_swap_log.output_amount,
+            a_to_b,
+            lp_fee: decoded_swap_log.lp_fee,
+            protocol_fee: decoded_swap_log.protocol_fee,
+            partner_fee: decoded_swap_log.partner_fee,
+            referral_fee: decoded_swap_log.referral_fee,
+            next_sqrt_price: decoded_swap_log.next_sqrt_price,
+        })
+    }
+
+    pub fn decode_swap_log(data: &[u8]) -> Result<DecodedMeteoraDammV2SwapLog, DecodeErrorKind> {
+        let mut offset: usize = 0;
+        let pool = read_pubkey(data, offset)?;
+        offset += 32;
+        let trade_direction = read_u8(data, offset)?;
+        offset += 1;
+        let has_referral = read_bool(data, offset)?;
+        offset += 1;
+        let amount_in = read_u64(data, offset)?;
+        offset += 8;
+        let minimum_amount_out = read_u64(data, offset)?;
+        offset += 8;
+        let output_amount = read_u64(data, offset)?;
+        offset += 8;
+        let next_sqrt_price = read_u128(data, offset)?;
+        offset += 16;
+        let lp_fee = read_u64(data, offset)?;
+        offset += 8;
+        let protocol_fee = read_u64(data, offset)?;
+        offset += 8;
+        let partner_fee = read_u64(data, offset)?;
+        offset += 8;
+        let referral_fee = read_u64(data, offset)?;
+        offset += 8;
+        let actual_amount_in = read_u64(data, offset)?;
+        offset += 8;
+        let current_timestamp = read_u64(data, offset)?;
+        Ok(DecodedMeteoraDammV2SwapLog {
+            pool,
+            trade_direction,
+            has_referral,
+            amount_in,
+            minimum_amount_out,
+            output_amount,
+            next_sqrt_price,
+            lp_fee,
+            protocol_fee,
+            partner_fee,
+            referral_fee,
+            actual_amount_in,
+            current_timestamp,
+        })
+    }
+
+    /// `first` is the position of the pool account; the vaults and mints follow the user's
+    /// token accounts and the owner follows the position NFT account. Pool, position, owner
+    /// and amounts come from the EvtAddLiquidity / EvtRemoveLiquidity event.
+    pub fn decode_liquidity_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+        first: usize,
+        add: bool,
+    ) -> Result<DecodedMeteoraDammV2LiquidityEvent, DecodeErrorKind> {
+        let event_discriminator = if add {
+            Self::ADD_LIQUIDITY_EVENT_DISCRIMINATOR
+        } else {
+            Self::REMOVE_LIQUIDITY_EVENT_DISCRIMINATOR
+        };
+        let data = find_event(instruction, &event_discriminator)
+            .ok_or(DecodeErrorKind::MissingEventLog)?;
+        let mut offset: usize = 0;
+        let pool = read_pubkey(data, offset)?;
+        offset += 32;
+        let position = read_pubkey(data, offset)?;
+        offset += 32;
+        let user = read_pubkey(data, offset)?;
+        offset += 32;
+        // params: liquidity_delta, token_a_amount_threshold, token_b_amount_threshold
+        let liquidity_delta = read_u128(data, offset)?;
+        offset += 32;
+        let token_a_amount = read_u64(data, offset)?;
+        offset += 8;
+        let token_b_amount = read_u64(data, offset)?;
+
+        let token_a_mint = get_account(instruction, account_keys, first + 6)?;
+        let token_b_mint = get_account(instruction, account_keys, first + 7)?;
+        let (_, pool_token_a_reserves, _) =
+            get_instruction_token_balance(instruction, transaction, first + 4)?;
+        let (_, pool_token_b_reserves, _) =
+            get_instruction_token_balance(instruction, transaction, first + 5)?;
+
+        Ok(DecodedMeteoraDammV2LiquidityEvent {
+            pool,
+            user,
+            position,
+            token_a_mint,
+            token_b_mint,
+            liquidity_delta,
+            token_a_amount,
+            token_b_amount,
+            pool_token_a_reserves,
+            pool_token_b_reserves,
+        })
+    }
+
+    /// `pool` is the position of the pool account; position, mints and vaults follow it.
+    pub fn decode_pool_creation_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        pool: usize,
+    ) -> Result<DecodedMeteoraDammV2CreatePoolEvent, DecodeErrorKind> {
+        let creator = get_account(instruction, account_keys, 0)?;
+        let token_a_mint = get_account(instruction, account_keys, pool + 2)?;
+        let token_b_mint = get_account(instruction, account_keys, pool + 3)?;
+        let token_a_vault = get_account(instruction, account_keys, pool + 4)?;
+        let token_b_vault = get_account(instruction, account_keys, pool + 5)?;
+        let pool = get_account(instruction, account_keys, pool)?;
+
+        let transfers = get_token_transfers(instruction, account_keys)?;
+        let token_a_amount = find_token_transfer(&transfers, &token_a_vault, true)?.amount;
+        let token_b_amount = find_token_transfer(&transfers, &token_b_vault, true)?.amount;
+
+        Ok(DecodedMeteoraDammV2CreatePoolEvent {
+            pool,
+            creator,
+            token_a_mint,
+            token_b_mint,
+            token_a_vault,
+            token_b_vault,
+            token_a_amount,
+            token_b_amount,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::testing::{
+        USER, WSOL_MINT, account_keys, event_cpi, pubkey, token_balances_transaction,
+    };
+
+    const POOL: &str = "8Pm2kZpnxD3hoMmt4bjStX2Pw2Z9abpbHzZxMPqxPmie";
+    const TOKEN_MINT: &str = "4k3Dyjzvzp8eMZWUXbBCjEvwSkkk59S5iCNLY3QrkX6R";
+
+    /// The `EvtSwap` of a 1 SOL buy, B (SOL) to A.
+    fn swap_log() -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend(pubkey(POOL));
+        data.extend([1, 0]); // trade_direction, has_referral
+        data.extend(1_000_000_000u64.to_le_bytes()); // amount_in
+        data.extend(3_400_000_000_000u64.to_le_bytes()); // minimum_amount_out
+        data.extend(3_456_789_012_345u64.to_le_bytes()); // output_amount
+        data.extend(6_124_123_456_789_012_345u128.to_le_bytes()); // next_sqrt_price
+        data.extend(8_000_000u64.to_le_bytes()); // lp_fee
+        data.extend(2_000_000u64.to_le_bytes()); // protocol_fee
+        data.extend(0u64.to_le_bytes()); // partner_fee
+        data.extend(0u64.to_le_bytes()); // referral_fee
+        data.extend(1_000_000_000u64.to_le_bytes()); // actual_amount_in
+        data.extend(1_750_012_345u64.to_le_bytes()); // current_timestamp
+        data
+    }
+
+    #[test]
+    fn decodes_a_swap_log() {
+        let log = MeteoraDammV2InstructionParser::decode_swap_log(&swap_log()).unwrap();
+        assert_eq!(log.pool, POOL);
+        assert_eq!(log.trade_direction, 1);
+        assert!(!log.has_referral);
+        assert_eq!(log.amount_in, 1_000_000_000);
+        assert_eq!(log.minimum_amount_out, 3_400_000_000_000);
+        assert_eq!(log.output_amount, 3_456_789_012_345);
+        assert_eq!(log.next_sqrt_price, 6_124_123_456_789_012_345);
+        assert_eq!(log.lp_fee, 8_000_000);
+        assert_eq!(log.protocol_fee, 2_000_000);
+        assert_eq!(log.actual_amount_in, 1_000_000_000);
+        assert_eq!(log.current_timestamp, 1_750_012_345);
+    }
+
+    #[test]
+    fn decodes_a_swap_with_its_vault_reserves() {
+        let mut account_keys = account_keys(9);
+        account_keys[1] = POOL.to_string();
+        account_keys[8] = USER.to_string();
+        account_keys.push(MeteoraDammV2InstructionParser::PROGRAM_ID.to_string());
+        let transaction = token_balances_transaction(&[
+            (4, TOKEN_MINT, 512_345_678_901_234, 6),
+            (5, WSOL_MINT, 148_000_000_000, 9),
+        ]);
+        let mut data = MeteoraDammV2InstructionParser::SWAP_DISCRIMINATOR.to_vec();
+        data.extend(1_000_000_000u64.to_le_bytes()); // amount_in
+        data.extend(3_400_000_000_000u64.to_le_bytes()); // minimum_amount_out
+        let mut event = MeteoraDammV2InstructionParser::SWAP_EVENT_DISCRIMINATOR.to_vec();
+        event.extend(swap_log());
+        let swap = StructuredInstruction {
+            account_key_indexes: (0..9).collect(),
+            program_id_index: 9,
+            data,
+            inner_instructions: vec![event_cpi(9, vec![0, 0], &event)],
+            stack_height: 1,
+            path: vec![0],
+            program_data_logs: Vec::new(),
+        };
+
+        let Some(DecodedMeteoraDammV2Event::Swap(event)) = MeteoraDammV2InstructionParser::new()
+            .decode_instruction(&swap, &account_keys, &transaction)
+            .unwrap()
+        else {
+            panic!("expected a swap");
+        };
+        assert_eq!(event.pool, POOL);
+        assert_eq!(event.user, USER);
+        assert!(!event.a_to_b);
+        assert_eq!(event.mint_in, WSOL_MINT);
+        assert_eq!(event.in_decimals, 9);
+        assert_eq!(event.mint_in_reserve, 148_000_000_000);
+        assert_eq!(event.mint_out, TOKEN_MINT);
+        assert_eq!(event.out_decimals, 6);
+        assert_eq!(event.mint_out_reserve, 512_345_678_901_234);
+        assert_eq!(event.amount_in, 1_000_000_000);
+        assert_eq!(event.amount_out, 3_456_789_012_345);
+    }
+}
@@ -0,0 +1,394 @@
+use instruction_parser::InstructionParser;
+use types::{
+    DecodeError, DecodeErrorKind, DecodeReport, DecodedEvent, DecodedInstruction,
+    DecodedMeteoraDammCreatePoolEvent, DecodedMeteoraDammEvent,
+    DecodedMeteoraDammLiquidityEvent, DecodedMeteoraDammSwapEvent, StructuredInstruction,
+    TokenProgramTransfer,
+};
+use utils::{
+    find_event, get_account, get_instruction_token_balance, get_nested_token_transfers,
+    get_token_mint_or_burn_amount, read_u64,
+};
+use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
+
+#[derive(Clone, Debug, Default)]
+pub struct MeteoraDammInstructionParser {}
+
+impl InstructionParser for MeteoraDammInstructionParser {
+    fn get_program_id(&self) -> &str {
+        Self::PROGRAM_ID
+    }
+
+    fn decode_instructions(
+        &self,
+        instructions: Vec<StructuredInstruction>,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> DecodeReport {
+        let mut report = DecodeReport::default();
+        for instruction in instructions.iter() {
+            match self.decode_instruction(instruction, account_keys, transaction) {
+                Ok(Some(decoded_instruction)) => report.events.push(DecodedInstruction::new(
+                    Self::PROGRAM_ID,
+                    instruction,
+                    DecodedEvent::MeteoraDamm(decoded_instruction),
+                )),
+                Ok(None) => {}
+                Err(kind) => report
+                    .errors
+                    .push(DecodeError::new(Self::PROGRAM_ID, instruction, 8, kind)),
+            }
+        }
+        report
+    }
+}
+
+/// Where a pool initializer keeps the accounts we report.
+struct CreatePoolAccounts {
+    lp_mint: usize,
+    token_a_mint: usize,
+    token_b_mint: usize,
+    payer_token_a: usize,
+    payer_token_b: usize,
+    payer: usize,
+}
+
+impl MeteoraDammInstructionParser {
+    const PROGRAM_ID: &'static str = "Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB";
+    const SWAP_DISCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+    const ADD_BALANCE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [168, 227, 50, 62, 189, 171, 84, 176];
+    const ADD_IMBALANCE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [79, 35, 122, 84, 173, 15, 93, 191];
+    const REMOVE_BALANCE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [133, 109, 44, 179, 56, 238, 114, 33];
+    const INITIALIZE_PERMISSIONLESS_POOL_DISCRIMINATOR: [u8; 8] =
+        [118, 173, 41, 157, 173, 72, 97, 103];
+    const INITIALIZE_PERMISSIONLESS_POOL_WITH_FEE_TIER_DISCRIMINATOR: [u8; 8] =
+        [6, 135, 68, 147, 229, 82, 169, 113];
+    const INITIALIZE_POOL_WITH_CONFIG_DISCRIMINATOR: [u8; 8] =
+        [7, 166, 138, 171, 206, 171, 236, 244];
+    const INITIALIZE_POOL_WITH_CONFIG2_DISCRIMINATOR: [u8; 8] =
+        [48, 149, 220, 130, 61, 11, 9, 178];
+    const SWAP_EVENT_DISCRIMINATOR: [u8; 8] = [81, 108, 227, 190, 205, 208, 10, 196];
+    const ADD_LIQUIDITY_EVENT_DISCRIMINATOR: [u8; 8] = [31, 94, 125, 90, 227, 52, 61, 186];
+    const REMOVE_LIQUIDITY_EVENT_DISCRIMINATOR: [u8; 8] = [116, 244, 97, 232, 103, 31, 152, 58];
+
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// The pool program logs its events with `emit!`, as `Program data:` lines.
+    pub fn decode_instruction(
+        &self,
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> Result<Option<DecodedMeteoraDammEvent>, DecodeErrorKind> {
+        let discriminator = match instruction.data.get(..8) {
+            Some(discriminator) => discriminator,
+            None => return Ok(None),
+        };
+        if discriminator == Self::SWAP_DISCRIMINATOR {
+            return Ok(Some(DecodedMeteoraDammEvent::Swap(Self::decode_swap_event(
+                instruction,
+                account_keys,
+                transaction,
+            )?)));
+        } else if discriminator == Self::ADD_BALANCE_LIQUIDITY_DISCRIMINATOR
+            || discriminator == Self::ADD_IMBALANCE_LIQUIDITY_DISCRIMINATOR
+        {
+            return Ok(Some(DecodedMeteoraDammEvent::AddLiquidity(
+                Self::decode_liquidity_event(instruction, account_keys, transaction, true)?,
+            )));
+        } else if discriminator == Self::REMOVE_BALANCE_LIQUIDITY_DISCRIMINATOR {
+            return Ok(Some(DecodedMeteoraDammEvent::RemoveLiquidity(
+                Self::decode_liquidity_event(instruction, account_keys, transaction, false)?,
+            )));
+        } else if discriminator == Self::INITIALIZE_PERMISSIONLESS_POOL_DISCRIMINATOR
+            || discriminator == Self::INITIALIZE_PERMISSIONLESS_POOL_WITH_FEE_TIER_DISCRIMINATOR
+        {
+            // [pool, lp_mint, token_a_mint, token_b_mint, a_vault, b_vault, a_vault_lp_mint,
+            // b_vault_lp_mint, a_vault_lp, b_vault_lp, payer_token_a, payer_token_b,
+            // payer_pool_lp, protocol_token_a_fee, protocol_token_b_fee, payer, ...]
+            return Ok(Some(DecodedMeteoraDammEvent::CreatePool(
+                Self::decode_pool_creation_event(
+                    instruction,
+                    account_keys,
+                    CreatePoolAccounts {
+                        lp_mint: 1,
+                        token_a_mint: 2,
+                        token_b_mint: 3,
+                        payer_token_a: 10,
+                        payer_token_b: 11,
+                        payer: 15,
+                    },
+                )?,
+            )));
+        } else if discriminator == Self::INITIALIZE_POOL_WITH_CONFIG_DISCRIMINATOR
+            || discriminator == Self::INITIALIZE_POOL_WITH_CONFIG2_DISCRIMINATOR
+        {
+            // [pool, config, lp_mint, token_a_mint, token_b_mint, a_vault, b_vault,
+            // a_token_vault, b_token_vault, a_vault_lp_mint, b_vault_lp_mint, a_vault_lp,
+            // b_vault_lp, payer_token_a, payer_token_b, payer_pool_lp, protocol_token_a_fee,
+            // protocol_token_b_fee, payer, ...]
+            return Ok(Some(DecodedMeteoraDammEvent::CreatePool(
+                Self::decode_pool_creation_event(
+                    instruction,
+                    account_keys,
+                    CreatePoolAccounts {
+                        lp_mint: 2,
+                        token_a_mint: 3,
+                        token_b_mint: 4,
+                        payer_token_a: 13,
+                        payer_token_b: 14,
+                        payer: 18,
+                    },
+                )?,
+            )));
+        }
+        Ok(None)
+    }
+
+    /// Tokens go through the vault program, so one user account may be debited by several
+    /// transfers (vault deposit and protocol fee); their total is what the user paid or got.
+    fn sum_transfers(
+        transfers: &[TokenProgramTransfer],
+        account: &str,
+        to_account: bool,
+    ) -> Result<u64, DecodeErrorKind> {
+        let amounts: Vec<u64> = transfers
+            .iter()
+            .filter(|transfer| {
+                if to_account {
+                    transfer.destination == account
+                } else {
+                    transfer.source == account
+                }
+            })
+            .map(|transfer| transfer.amount)
+            .collect();
+        if amounts.is_empty() {
+            return Err(DecodeErrorKind::MissingTokenTransfer {
+                position: transfers.len(),
+            });
+        }
+        Ok(amounts.iter().sum())
+    }
+
+    /// Accounts: [pool, user_source_token, user_destination_token, a_vault, b_vault,
+    /// a_token_vault, b_token_vault, a_vault_lp_mint, b_vault_lp_mint, a_vault_lp, b_vault_lp,
+    /// protocol_token_fee, user, vault_program, token_program]
+    ///
+    /// `Swap`: in_amount, out_amount, trade_fee, protocol_fee, host_fee
+    pub fn decode_swap_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> Result<DecodedMeteoraDammSwapEvent, DecodeErrorKind> {
+        let pool = get_account(instruction, account_keys, 0)?;
+        let user = get_account(instruction, account_keys, 12)?;
+        let (mint_in, _, in_decimals) = get_instruction_token_balance(instruction, transaction, 1)?;
+        let (mint_out, _, out_decimals) =
+            get_instruction_token_balance(instruction, transaction, 2)?;
+        let (a_mint, a_reserve, _) = get_instruction_token_balance(instruction, transaction, 5)?;
+        let (_, b_reserve, _) = get_instruction_token_balance(instruction, transaction, 6)?;
+        let (mint_in_reserve, mint_out_reserve) = if mint_in == a_mint {
+            (a_reserve, b_reserve)
+        } else {
+            (b_reserve, a_reserve)
+        };
+
+        let data = find_event(instruction, &Self::SWAP_EVENT_DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)?;
+        let amount_in = read_u64(data, 0)?;
+        let amount_out = read_u64(data, 8)?;
+
+        Ok(DecodedMeteoraDammSwapEvent {
+            pool,
+            user,
+            mint_in,
+            mint_out,
+            in_decimals,
+            out_decimals,
+            mint_in_reserve,
+            mint_out_reserve,
+            amount_in,
+            amount_out,
+        })
+    }
+
+    /// Balanced/imbalanced deposits and balanced withdrawals share their accounts:
+    /// [pool, lp_mint, user_pool_lp, a_vault_lp, b_vault_lp, a_vault, b_vault, a_vault_lp_mint,
+    /// b_vault_lp_mint, a_token_vault, b_token_vault, user_a_token, user_b_token, user, ...]
+    ///
+    /// `AddLiquidity`: lp_mint_amount, token_a_amount, token_b_amount
+    ///
+    /// `RemoveLiquidity`: lp_unmint_amount, token_a_out_amount, token_b_out_amount
+    pub fn decode_liquidity_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+        add: bool,
+    ) -> Result<DecodedMeteoraDammLiquidityEvent, DecodeErrorKind> {
+        let pool = get_account(instruction, account_keys, 0)?;
+        let lp_mint = get_account(instruction, account_keys, 1)?;
+        let user = get_account(instruction, account_keys, 13)?;
+        let (token_a_mint, _, _) = get_instruction_token_balance(instruction, transaction, 11)?;
+        let (token_b_mint, _, _) = get_instruction_token_balance(instruction, transaction, 12)?;
+
+        let event_discriminator = if add {
+            &Self::ADD_LIQUIDITY_EVENT_DISCRIMINATOR
+        } else {
+            &Self::REMOVE_LIQUIDITY_EVENT_DISCRIMINATOR
+        };
+        let data =
+            find_event(instruction, event_discriminator).ok_or(DecodeErrorKind::MissingEventLog)?;
+        let lp_amount = read_u64(data, 0)?;
+        let token_a_amount = read_u64(data, 8)?;
+        let token_b_amount = read_u64(data, 16)?;
+
+        Ok(DecodedMeteoraDammLiquidityEvent {
+            pool,
+            user,
+            lp_mint,
+            token_a_mint,
+            token_b_mint,
+            token_a_amount,
+            token_b_amount,
+            lp_amount,
+        })
+    }
+
+    fn decode_pool_creation_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        accounts: CreatePoolAccounts,
+    ) -> Result<DecodedMeteoraDammCreatePoolEvent, DecodeErrorKind> {
+        let pool = get_account(instruction, account_keys, 0)?;
+        let lp_mint = get_account(instruction, account_keys, accounts.lp_mint)?;
+        let token_a_mint = get_account(instruction, account_keys, accounts.token_a_mint)?;
+        let token_b_mint = get_account(instruction, account_keys, accounts.token_b_mint)?;
+        let payer_token_a = get_account(instruction, account_keys, accounts.payer_token_a)?;
+        let payer_token_b = get_account(instruction, account_keys, accounts.payer_token_b)?;
+        let creator = get_account(instruction, account_keys, accounts.payer)?;
+
+        let transfers = get_nested_token_transfers(instruction, account_keys)?;
+        let token_a_amount = Self::sum_transfers(&transfers, &payer_token_a, false)?;
+        let token_b_amount = Self::sum_transfers(&transfers, &payer_token_b, false)?;
+        let lp_amount = get_token_mint_or_burn_amount(instruction, account_keys, false)?;
+
+        Ok(DecodedMeteoraDammCreatePoolEvent {
+            pool,
+            creator,
+            lp_mint,
+            token_a_mint,
+            token_b_mint,
+            token_a_amount,
+            token_b_amount,
+            lp_amount,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::testing::{USDC_MINT, USER, WSOL_MINT, account_keys, token_balances_transaction};
+
+    const POOL: &str = "5yuefgbJJpmFNK2iiYbLSpv1aZXq7F9AUKkZKErTYCvs";
+    const LP_MINT: &str = "xLebAypjbaQ9tmxUKHV6DZU4mY8ATAAP2sfkNNQLs1r";
+
+    /// An instruction of the pool over accounts `0..accounts` that logged `data_logs`.
+    fn instruction(data: Vec<u8>, accounts: u8, data_logs: Vec<Vec<u8>>) -> StructuredInstruction {
+        StructuredInstruction {
+            account_key_indexes: (0..accounts).collect(),
+            program_id_index: accounts,
+            data,
+            inner_instructions: Vec::new(),
+            stack_height: 1,
+            path: vec![0],
+            program_data_logs: data_logs,
+        }
+    }
+
+    fn event(discriminator: [u8; 8], amounts: &[u64]) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+        for amount in amounts {
+            data.extend(amount.to_le_bytes());
+        }
+        data
+    }
+
+    #[test]
+    fn decodes_a_swap_from_its_logged_event() {
+        let mut account_keys = account_keys(15);
+        account_keys[0] = POOL.to_string();
+        account_keys[12] = USER.to_string();
+        account_keys.push(MeteoraDammInstructionParser::PROGRAM_ID.to_string());
+        // selling USDC, the pool's b side, for SOL
+        let transaction = token_balances_transaction(&[
+            (1, USDC_MINT, 0, 6),
+            (2, WSOL_MINT, 1_987_654_321, 9),
+            (5, WSOL_MINT, 5_123_456_789_012, 9),
+            (6, USDC_MINT, 775_000_000_000, 6),
+        ]);
+        let swap_event = event(
+            MeteoraDammInstructionParser::SWAP_EVENT_DISCRIMINATOR,
+            // in_amount, out_amount, trade_fee, protocol_fee, host_fee
+            &[300_000_000, 1_987_654_321, 750_000, 150_000, 0],
+        );
+        let mut data = MeteoraDammInstructionParser::SWAP_DISCRIMINATOR.to_vec();
+        data.extend(300_000_000u64.to_le_bytes()); // in_amount
+        data.extend(1_950_000_000u64.to_le_bytes()); // minimum_out_amount
+        let swap = instruction(data, 15, vec![swap_event]);
+
+        let Some(DecodedMeteoraDammEvent::Swap(event)) = MeteoraDammInstructionParser::new()
+            .decode_instruction(&swap, &account_keys, &transaction)
+            .unwrap()
+        else {
+            panic!("expected a swap");
+        };
+        assert_eq!(event.pool, POOL);
+        assert_eq!(event.user, USER);
+        assert_eq!(event.mint_in, USDC_MINT);
+        assert_eq!(event.in_decimals, 6);
+        assert_eq!(event.mint_in_reserve, 775_000_000_000);
+        assert_eq!(event.mint_out, WSOL_MINT);
+        assert_eq!(event.out_decimals, 9);
+        assert_eq!(event.mint_out_reserve, 5_123_456_789_012);
+        assert_eq!(event.amount_in, 300_000_000);
+        assert_eq!(event.amount_out, 1_987_654_321);
+    }
+
+    #[test]
+    fn decodes_a_withdrawal_from_its_logged_event() {
+        let mut account_keys = account_keys(14);
+        account_keys[0] = POOL.to_string();
+        account_keys[1] = LP_MINT.to_string();
+        account_keys[13] = USER.to_string();
+        account_keys.push(MeteoraDammInstructionParser::PROGRAM_ID.to_string());
+        let transaction =
+            token_balances_transaction(&[(11, WSOL_MINT, 0, 9), (12, USDC_MINT, 0, 6)]);
+        let remove_event = event(
+            MeteoraDammInstructionParser::REMOVE_LIQUIDITY_EVENT_DISCRIMINATOR,
+            // lp_unmint_amount, token_a_out_amount, token_b_out_amount
+            &[45_678_901, 1_000_000_000, 151_000_000],
+        );
+        let data = MeteoraDammInstructionParser::REMOVE_BALANCE_LIQUIDITY_DISCRIMINATOR.to_vec();
+        let remove = instruction(data, 14, vec![remove_event]);
+
+        let Some(DecodedMeteoraDammEvent::RemoveLiquidity(event)) =
+            MeteoraDammInstructionParser::new()
+                .decode_instruction(&remove, &account_keys, &transaction)
+                .unwrap()
+        else {
+            panic!("expected a withdrawal");
+        };
+        assert_eq!(event.pool, POOL);
+        assert_eq!(event.user, USER);
+        assert_eq!(event.lp_mint, LP_MINT);
+        assert_eq!(event.token_a_mint, WSOL_MINT);
+        assert_eq!(event.token_b_mint, USDC_MINT);
+        assert_eq!(event.lp_amount, 45_678_901);
+        assert_eq!(event.token_a_amount, 1_000_000_000);
+        assert_eq!(event.token_b_amount, 151_000_000);
+    }
+}
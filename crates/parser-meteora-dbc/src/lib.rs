@@ -0,0 +1,446 @@
+use instruction_parser::InstructionParser;
+use types::{
+    DecodeError, DecodeErrorKind, DecodeReport, DecodedEvent, DecodedInstruction,
+    DecodedMeteoraDbcCreatePoolEvent, DecodedMeteoraDbcEvent, DecodedMeteoraDbcMigrateEvent,
+    DecodedMeteoraDbcSwapEvent, DecodedMeteoraDbcSwapLog, MeteoraDbcMigrationTarget,
+    StructuredInstruction, SwapEventAccounts, TransactionType,
+};
+use utils::{
+    find_event, get_account, get_instruction_token_balance, get_nested_token_transfers,
+    read_bool, read_pubkey, read_string, read_u8, read_u64, read_u128,
+};
+use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
+
+#[derive(Clone, Debug, Default)]
+pub struct MeteoraDbcInstructionParser {}
+
+impl InstructionParser for MeteoraDbcInstructionParser {
+    fn get_program_id(&self) -> &str {
+        Self::PROGRAM_ID
+    }
+
+    fn decode_instructions(
+        &self,
+        instructions: Vec<StructuredInstruction>,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> DecodeReport {
+        let mut report = DecodeReport::default();
+        for instruction in instructions.iter() {
+            match self.decode_instruction(instruction, account_keys, transaction) {
+                Ok(Some(decoded_instruction)) => report.events.push(DecodedInstruction::new(
+                    Self::PROGRAM_ID,
+                    instruction,
+                    DecodedEvent::MeteoraDbc(decoded_instruction),
+                )),
+                Ok(None) => {}
+                Err(kind) => report
+                    .errors
+                    .push(DecodeError::new(Self::PROGRAM_ID, instruction, 8, kind)),
+            }
+        }
+        report
+    }
+}
+
+impl MeteoraDbcInstructionParser {
+    const PROGRAM_ID: &'static str = "dbcij3LWUppWqq96dh6gJWwBifmcGfLSB5D4DuSMaqN";
+    const INITIALIZE_VIRTUAL_POOL_WITH_SPL_TOKEN_DISCRIMINATOR: [u8; 8] =
+        [140, 85, 215, 176, 102, 54, 104, 79];
+    const INITIALIZE_VIRTUAL_POOL_WITH_TOKEN2022_DISCRIMINATOR: [u8; 8] =
+        [169, 118, 51, 78, 145, 110, 220, 155];
+    const SWAP_DISCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+    const SWAP2_DISCRIMINATOR: [u8; 8] = [65, 75, 63, 76, 235, 91, 91, 136];
+    const MIGRATE_METEORA_DAMM_DISCRIMINATOR: [u8; 8] = [27, 1, 48, 22, 180, 63, 118, 217];
+    const MIGRATION_DAMM_V2_DISCRIMINATOR: [u8; 8] = [156, 169, 230, 103, 53, 228, 80, 64];
+    const SWAP_EVENT_DISCRIMINATOR: [u8; 8] = [27, 60, 21, 213, 138, 170, 187, 147];
+    const SWAP2_EVENT_DISCRIMINATOR: [u8; 8] = [189, 66, 51, 168, 38, 80, 117, 153];
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// `trade_direction` of a quote to base trade.
+    const QUOTE_TO_BASE: u8 = 1;
+
+    pub fn decode_instruction(
+        &self,
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> Result<Option<DecodedMeteoraDbcEvent>, DecodeErrorKind> {
+        let discriminator = match instruction.data.get(..8) {
+            Some(discriminator) => discriminator,
+            None => return Ok(None),
+        };
+        if discriminator == Self::SWAP_DISCRIMINATOR || discriminator == Self::SWAP2_DISCRIMINATOR
+        {
+            return Ok(Some(DecodedMeteoraDbcEvent::Swap(Self::decode_swap_event(
+                instruction,
+                account_keys,
+                transaction,
+            )?)));
+        } else if discriminator == Self::INITIALIZE_VIRTUAL_POOL_WITH_SPL_TOKEN_DISCRIMINATOR
+            || discriminator == Self::INITIALIZE_VIRTUAL_POOL_WITH_TOKEN2022_DISCRIMINATOR
+        {
+            return Ok(Some(DecodedMeteoraDbcEvent::CreatePool(
+                Self::decode_pool_creation_event(instruction, account_keys)?,
+            )));
+        } else if discriminator == Self::MIGRATE_METEORA_DAMM_DISCRIMINATOR {
+            return Ok(Some(DecodedMeteoraDbcEvent::Migrate(Self::decode_migrate_event(
+                instruction,
+                account_keys,
+                MeteoraDbcMigrationTarget::DammV1,
+            )?)));
+        } else if discriminator == Self::MIGRATION_DAMM_V2_DISCRIMINATOR {
+            return Ok(Some(DecodedMeteoraDbcEvent::Migrate(Self::decode_migrate_event(
+                instruction,
+                account_keys,
+                MeteoraDbcMigrationTarget::DammV2,
+            )?)));
+        }
+        Ok(None)
+    }
+
+    /// `swap` and `swap2` share their accounts:
+    /// [pool_authority, config, pool, input_token_account, output_token_account, base_vault,
+    /// quote_vault, base_mint, quote_mint, payer, ...]
+    pub fn decode_swap_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> Result<DecodedMeteoraDbcSwapEvent, DecodeErrorKind> {
+        let pool = get_account(instruction, account_keys, 2)?;
+        let base_mint = get_account(instruction, account_keys, 7)?;
+        let quote_mint = get_account(instruction, account_keys, 8)?;
+        let user = get_account(instruction, account_keys, 9)?;
+        let (_, base_reserve, _) = get_instruction_token_balance(instruction, transaction, 5)?;
+        let (_, quote_reserve, _) = get_instruction_token_balance(instruction, transaction, 6)?;
+
+        // newer program versions emit both events, `EvtSwap2` being the richer one
+        let decoded_swap_log =
+            if let Some(data) = find_event(instruction, &Self::SWAP2_EVENT_DISCRIMINATOR) {
+                Self::decode_swap2_log(data)?
+            } else if let Some(data) = find_event(instruction, &Self::SWAP_EVENT_DISCRIMINATOR) {
+                Self::decode_swap_log(data)?
+            } else {
+                return Err(DecodeErrorKind::MissingEventLog);
+            };
+        let curve_progress = match (
+            decoded_swap_log.quote_reserve_amount,
+            decoded_swap_log.migration_threshold,
+        ) {
+            (Some(quote_reserve_amount), Some(threshold)) if threshold > 0 => {
+                Some((quote_reserve_amount as f64 / threshold as f64).min(1.0))
+            }
+            _ => None,
+        };
+
+        let buy = decoded_swap_log.trade_direction == Self::QUOTE_TO_BASE;
+        let (mint_in, mint_out, mint_in_reserve, mint_out_reserve, event_type) = if buy {
+            (
+                quote_mint.clone(),
+                base_mint.clone(),
+                quote_reserve,
+                base_reserve,
+                TransactionType::Buy,
+            )
+        } else {
+            (
+                base_mint.clone(),
+                quote_mint.clone(),
+                base_reserve,
+                quote_reserve,
+                TransactionType::Sell,
+            )
+        };
+
+        Ok(DecodedMeteoraDbcSwapEvent {
+            accounts: SwapEventAccounts {
+                pool,
+                user,
+                base_mint,
+                quote_mint,
+            },
+            mint_in,
+            mint_out,
+            amount_in: decoded_swap_log.input_amount,
+            amount_out: decoded_swap_log.output_amount,
+            mint_in_reserve,
+            mint_out_reserve,
+            base_reserve,
+            quote_reserve,
+            trading_fee: decoded_swap_log.trading_fee,
+            protocol_fee: decoded_swap_log.protocol_fee,
+            referral_fee: decoded_swap_log.referral_fee,
+            next_sqrt_price: decoded_swap_log.next_sqrt_price,
+            migration_quote_threshold: decoded_swap_log.migration_threshold,
+            curve_progress,
+            event_type,
+        })
+    }
+
+    pub fn decode_swap_log(data: &[u8]) -> Result<DecodedMeteoraDbcSwapLog, DecodeErrorKind> {
+        let mut offset: usize = 0;
+        let pool = read_pubkey(data, offset)?;
+        offset += 32;
+        let config = read_pubkey(data, offset)?;
+        offset += 32;
+        let trade_direction = read_u8(data, offset)?;
+        offset += 1;
+        let has_referral = read_bool(data, offset)?;
+        // params: amount_in, minimum_amount_out
+        offset += 17;
+        let input_amount = read_u64(data, offset)?;
+        offset += 8;
+        let output_amount = read_u64(data, offset)?;
+        offset += 8;
+        let next_sqrt_price = read_u128(data, offset)?;
+        offset += 16;
+        let trading_fee = read_u64(data, offset)?;
+        offset += 8;
+        let protocol_fee = read_u64(data, offset)?;
+        offset += 8;
+        let referral_fee = read_u64(data, offset)?;
+        // amount_in
+        offset += 16;
+        let current_timestamp = read_u64(data, offset)?;
+        Ok(DecodedMeteoraDbcSwapLog {
+            pool,
+            config,
+            trade_direction,
+            has_referral,
+            input_amount,
+            output_amount,
+            next_sqrt_price,
+            trading_fee,
+            protocol_fee,
+            referral_fee,
+            quote_reserve_amount: None,
+            migration_threshold: None,
+            current_timestamp,
+        })
+    }
+
+    pub fn decode_swap2_log(data: &[u8]) -> Result<DecodedMeteoraDbcSwapLog, DecodeErrorKind> {
+        let mut offset: usize = 0;
+        let pool = read_pubkey(data, offset)?;
+        offset += 32;
+        let config = read_pubkey(data, offset)?;
+        offset += 32;
+        let trade_direction = read_u8(data, offset)?;
+        offset += 1;
+        let has_referral = read_bool(data, offset)?;
+        // swap_parameters: amount_0, amount_1, swap_mode
+        offset += 18;
+        let input_amount = read_u64(data, offset)?;
+        // excluded_fee_input_amount, amount_left
+        offset += 24;
+        let output_amount = read_u64(data, offset)?;
+        offset += 8;
+        let next_sqrt_price = read_u128(data, offset)?;
+        offset += 16;
+        let trading_fee = read_u64(data, offset)?;
+        offset += 8;
+        let protocol_fee = read_u64(data, offset)?;
+        offset += 8;
+        let referral_fee = read_u64(data, offset)?;
+        offset += 8;
+        let quote_reserve_amount = read_u64(data, offset)?;
+        offset += 8;
+        let migration_threshold = read_u64(data, offset)?;
+        offset += 8;
+        let current_timestamp = read_u64(data, offset)?;
+        Ok(DecodedMeteoraDbcSwapLog {
+            pool,
+            config,
+            trade_direction,
+            has_referral,
+            input_amount,
+            output_amount,
+            next_sqrt_price,
+            trading_fee,
+            protocol_fee,
+            referral_fee,
+            quote_reserve_amount: Some(quote_reserve_amount),
+            migration_threshold: Some(migration_threshold),
+            current_timestamp,
+        })
+    }
+
+    /// Both initializers share their leading accounts:
+    /// [config, pool_authority, creator, base_mint, quote_mint, pool, base_vault, quote_vault, ...]
+    pub fn decode_pool_creation_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Result<DecodedMeteoraDbcCreatePoolEvent, DecodeErrorKind> {
+        let config = get_account(instruction, account_keys, 0)?;
+        let creator = get_account(instruction, account_keys, 2)?;
+        let base_mint = get_account(instruction, account_keys, 3)?;
+        let quote_mint = get_account(instruction, account_keys, 4)?;
+        let pool = get_account(instruction, account_keys, 5)?;
+        let base_vault = get_account(instruction, account_keys, 6)?;
+        let quote_vault = get_account(instruction, account_keys, 7)?;
+
+        // params: name, symbol, uri
+        let data = &instruction.data;
+        let (name, offset) = read_string(data, 8)?;
+        let (symbol, offset) = read_string(data, offset)?;
+        let (uri, _) = read_string(data, offset)?;
+
+        Ok(DecodedMeteoraDbcCreatePoolEvent {
+            name,
+            symbol,
+            uri,
+            creator,
+            base_mint,
+            quote_mint,
+            pool,
+            base_vault,
+            quote_vault,
+            config,
+            event_type: TransactionType::CreatePool,
+        })
+    }
+
+    /// Account positions of (target pool, base mint, quote mint, base vault, quote vault).
+    fn migrate_account_positions(
+        target: MeteoraDbcMigrationTarget,
+    ) -> (usize, usize, usize, usize, usize) {
+        match target {
+            // [virtual_pool, migration_metadata, config, pool_authority, pool, damm_config,
+            // lp_mint, token_a_mint, token_b_mint, a_vault, b_vault, a_token_vault,
+            // b_token_vault, a_vault_lp_mint, b_vault_lp_mint, a_vault_lp, b_vault_lp,
+            // base_vault, quote_vault, ...]
+            MeteoraDbcMigrationTarget::DammV1 => (4, 7, 8, 17, 18),
+            // [virtual_pool, migration_metadata, config, pool_authority, pool,
+            // first_position_nft_mint, first_position_nft_account, first_position,
+            // second_position_nft_mint, second_position_nft_account, second_position,
+            // damm_pool_authority, amm_program, base_mint, quote_mint, token_a_vault,
+            // token_b_vault, base_vault, quote_vault, ...]
+            MeteoraDbcMigrationTarget::DammV2 => (4, 13, 14, 17, 18),
+        }
+    }
+
+    pub fn decode_migrate_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        target: MeteoraDbcMigrationTarget,
+    ) -> Result<DecodedMeteoraDbcMigrateEvent, DecodeErrorKind> {
+        let (
+            target_pool_position,
+            base_mint_position,
+            quote_mint_position,
+            base_vault_position,
+            quote_vault_position,
+        ) = Self::migrate_account_positions(target);
+        let pool = get_account(instruction, account_keys, 0)?;
+        let target_pool = get_account(instruction, account_keys, target_pool_position)?;
+        let base_mint = get_account(instruction, account_keys, base_mint_position)?;
+        let quote_mint = get_account(instruction, account_keys, quote_mint_position)?;
+        let base_vault = get_account(instruction, account_keys, base_vault_position)?;
+        let quote_vault = get_account(instruction, account_keys, quote_vault_position)?;
+
+        // the curve vaults are drained through the DAMM (and vault) programs
+        let transfers = get_nested_token_transfers(instruction, account_keys)?;
+        let base_amount = transfers
+            .iter()
+            .filter(|transfer| transfer.source == base_vault)
+            .map(|transfer| transfer.amount)
+            .sum();
+        let quote_amount = transfers
+            .iter()
+            .filter(|transfer| transfer.source == quote_vault)
+            .map(|transfer| transfer.amount)
+            .sum();
+
+        Ok(DecodedMeteoraDbcMigrateEvent {
+            pool,
+            base_mint,
+            quote_mint,
+            target,
+            target_pool,
+            base_amount,
+            quote_amount,
+            event_type: TransactionType::Migrate,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::testing::pubkey;
+
+    const POOL: &str = "5gB4NPgFB3MHFHSeKN4sbaY6t9MB8ikCe9HyiKYid4Td";
+    const CONFIG: &str = "FiENCCbPi3rFh5pW2AJ59HC53yM32eLaCjMKxRqanKFJ";
+
+    /// `pool`, `config`, `trade_direction` and `has_referral`, shared by both events.
+    fn swap_log_header(trade_direction: u8) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend(pubkey(POOL));
+        data.extend(pubkey(CONFIG));
+        data.extend([trade_direction, 0]);
+        data
+    }
+
+    #[test]
+    fn decodes_an_evt_swap() {
+        let mut data = swap_log_header(MeteoraDbcInstructionParser::QUOTE_TO_BASE);
+        data.extend(500_000_000u64.to_le_bytes()); // amount_in
+        data.extend(12_000_000_000_000u64.to_le_bytes()); // minimum_amount_out
+        data.extend(495_000_000u64.to_le_bytes()); // actual_input_amount
+        data.extend(12_345_678_901_234u64.to_le_bytes()); // output_amount
+        data.extend(1_234_567_890_123_456_789u128.to_le_bytes()); // next_sqrt_price
+        data.extend(4_000_000u64.to_le_bytes()); // trading_fee
+        data.extend(1_000_000u64.to_le_bytes()); // protocol_fee
+        data.extend(0u64.to_le_bytes()); // referral_fee
+        data.extend(500_000_000u64.to_le_bytes()); // amount_in
+        data.extend(1_750_012_345u64.to_le_bytes()); // current_timestamp
+
+        let log = MeteoraDbcInstructionParser::decode_swap_log(&data).unwrap();
+        assert_eq!(log.pool, POOL);
+        assert_eq!(log.config, CONFIG);
+        assert_eq!(log.trade_direction, MeteoraDbcInstructionParser::QUOTE_TO_BASE);
+        assert!(!log.has_referral);
+        assert_eq!(log.input_amount, 495_000_000);
+        assert_eq!(log.output_amount, 12_345_678_901_234);
+        assert_eq!(log.next_sqrt_price, 1_234_567_890_123_456_789);
+        assert_eq!(log.trading_fee, 4_000_000);
+        assert_eq!(log.protocol_fee, 1_000_000);
+        assert_eq!(log.referral_fee, 0);
+        assert_eq!(log.quote_reserve_amount, None);
+        assert_eq!(log.migration_threshold, None);
+        assert_eq!(log.current_timestamp, 1_750_012_345);
+    }
+
+    #[test]
+    fn decodes_an_evt_swap2() {
+        let mut data = swap_log_header(0);
+        data.extend(12_345_678_901_234u64.to_le_bytes()); // amount_0
+        data.extend(480_000_000u64.to_le_bytes()); // amount_1
+        data.push(0); // swap_mode
+        data.extend(12_345_678_901_234u64.to_le_bytes()); // included_fee_input_amount
+        data.extend(12_345_678_901_234u64.to_le_bytes()); // excluded_fee_input_amount
+        data.extend(0u64.to_le_bytes()); // amount_left
+        data.extend(489_000_000u64.to_le_bytes()); // output_amount
+        data.extend(1_134_567_890_123_456_789u128.to_le_bytes()); // next_sqrt_price
+        data.extend(4_900_000u64.to_le_bytes()); // trading_fee
+        data.extend(1_100_000u64.to_le_bytes()); // protocol_fee
+        data.extend(0u64.to_le_bytes()); // referral_fee
+        data.extend(42_000_000_000u64.to_le_bytes()); // quote_reserve_amount
+        data.extend(85_000_000_000u64.to_le_bytes()); // migration_threshold
+        data.extend(1_750_012_400u64.to_le_bytes()); // current_timestamp
+
+        let log = MeteoraDbcInstructionParser::decode_swap2_log(&data).unwrap();
+        assert_eq!(log.pool, POOL);
+        assert_eq!(log.trade_direction, 0);
+        assert_eq!(log.input_amount, 12_345_678_901_234);
+        assert_eq!(log.output_amount, 489_000_000);
+        assert_eq!(log.next_sqrt_price, 1_134_567_890_123_456_789);
+        assert_eq!(log.trading_fee, 4_900_000);
+        assert_eq!(log.protocol_fee, 1_100_000);
+        assert_eq!(log.quote_reserve_amount, Some(42_000_000_000));
+        assert_eq!(log.migration_threshold, Some(85_000_000_000));
+        assert_eq!(log.current_timestamp, 1_750_012_400);
+    }
+}
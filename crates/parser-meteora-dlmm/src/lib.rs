@@ -0,0 +1,445 @@
+use instruction_parser::InstructionParser;
+use types::{
+    DecodeError, DecodeErrorKind, DecodeReport, DecodedEvent, DecodedInstruction,
+    DecodedMeteoraDlmmCreatePoolEvent, DecodedMeteoraDlmmEvent,
+    DecodedMeteoraDlmmLiquidityEvent, DecodedMeteoraDlmmSwapEvent, DecodedMeteoraDlmmSwapLog,
+    MeteoraDlmmSwapType, StructuredInstruction,
+};
+use utils::{
+    find_event, get_account, get_instruction_token_balance, read_bool, read_i32,
+    read_pubkey, read_u16, read_u64, read_u128,
+};
+use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
+
+#[derive(Clone, Debug, Default)]
+pub struct MeteoraDlmmInstructionParser {}
+
+impl InstructionParser for MeteoraDlmmInstructionParser {
+    fn get_program_id(&self) -> &str {
+        Self::PROGRAM_ID
+    }
+
+    fn decode_instructions(
+        &self,
+        instructions: Vec<StructuredInstruction>,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> DecodeReport {
+        let mut report = DecodeReport::default();
+        for instruction in instructions.iter() {
+            match self.decode_instruction(instruction, account_keys, transaction) {
+                Ok(Some(decoded_instruction)) => report.events.push(DecodedInstruction::new(
+                    Self::PROGRAM_ID,
+                    instruction,
+                    DecodedEvent::MeteoraDlmm(decoded_instruction),
+                )),
+                Ok(None) => {}
+                Err(kind) => report
+                    .errors
+                    .push(DecodeError::new(Self::PROGRAM_ID, instruction, 8, kind)),
+            }
+        }
+        report
+    }
+}
+
+impl MeteoraDlmmInstructionParser {
+    const PROGRAM_ID: &'static str = "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo";
+    const SWAP_DISCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+    const SWAP_EXACT_OUT_DISCRIMINATOR: [u8; 8] = [250, 73, 101, 33, 38, 207, 75, 184];
+    const SWAP_WITH_PRICE_IMPACT_DISCRIMINATOR: [u8; 8] = [56, 173, 230, 208, 173, 228, 156, 205];
+    const SWAP2_DISCRIMINATOR: [u8; 8] = [65, 75, 63, 76, 235, 91, 91, 136];
+    const SWAP_EXACT_OUT2_DISCRIMINATOR: [u8; 8] = [43, 215, 247, 132, 137, 60, 243, 81];
+    const SWAP_WITH_PRICE_IMPACT2_DISCRIMINATOR: [u8; 8] = [74, 98, 192, 214, 177, 51, 75, 51];
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// add_liquidity, add_liquidity_by_weight, add_liquidity_by_strategy, add_liquidity2,
+    /// add_liquidity_by_strategy2
+    const ADD_LIQUIDITY_DISCRIMINATORS: [[u8; 8]; 5] = [
+        [181, 157, 89, 67, 143, 182, 52, 72],
+        [28, 140, 238, 99, 231, 162, 21, 149],
+        [7, 3, 150, 127, 148, 40, 61, 200],
+        [228, 162, 78, 28, 70, 219, 116, 115],
+        [3, 221, 149, 218, 111, 141, 118, 213],
+    ];
+    /// add_liquidity_one_side, add_liquidity_by_strategy_one_side,
+    /// add_liquidity_one_side_precise, add_liquidity_one_side_precise2
+    const ADD_LIQUIDITY_ONE_SIDE_DISCRIMINATORS: [[u8; 8]; 4] = [
+        [94, 155, 103, 151, 70, 95, 220, 165],
+        [41, 5, 238, 175, 100, 225, 6, 205],
+        [161, 194, 103, 84, 171, 71, 250, 154],
+        [33, 51, 163, 201, 117, 98, 125, 231],
+    ];
+    /// remove_liquidity, remove_all_liquidity, remove_liquidity_by_range, remove_liquidity2,
+    /// remove_liquidity_by_range2
+    const REMOVE_LIQUIDITY_DISCRIMINATORS: [[u8; 8]; 5] = [
+        [80, 85, 209, 72, 24, 206, 177, 108],
+        [10, 51, 61, 35, 112, 105, 24, 85],
+        [26, 82, 102, 152, 240, 74, 105, 26],
+        [230, 215, 82, 127, 241, 101, 227, 146],
+        [204, 2, 195, 145, 53, 145, 145, 205],
+    ];
+    /// initialize_lb_pair, initialize_lb_pair2, initialize_customizable_permissionless_lb_pair,
+    /// initialize_customizable_permissionless_lb_pair2
+    const INITIALIZE_LB_PAIR_DISCRIMINATORS: [[u8; 8]; 4] = [
+        [45, 154, 237, 210, 221, 15, 166, 92],
+        [73, 59, 36, 120, 237, 83, 108, 198],
+        [46, 39, 41, 135, 111, 183, 200, 64],
+        [243, 73, 129, 126, 51, 19, 241, 107],
+    ];
+    const SWAP_EVENT_DISCRIMINATOR: [u8; 8] = [81, 108, 227, 190, 205, 208, 10, 196];
+    const ADD_LIQUIDITY_EVENT_DISCRIMINATOR: [u8; 8] = [31, 94, 125, 90, 227, 52, 61, 186];
+    const REMOVE_LIQUIDITY_EVENT_DISCRIMINATOR: [u8; 8] = [116, 244, 97, 232, 103, 31, 152, 58];
+    const LB_PAIR_CREATE_EVENT_DISCRIMINATOR: [u8; 8] = [185, 74, 252, 125, 27, 215, 188, 111];
+
+    pub fn decode_instruction(
+        &self,
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> Result<Option<DecodedMeteoraDlmmEvent>, DecodeErrorKind> {
+        let discriminator = match instruction.data.get(..8) {
+            Some(discriminator) => discriminator,
+            None => return Ok(None),
+        };
+        let swap_type = if discriminator == Self::SWAP_DISCRIMINATOR {
+            Some(MeteoraDlmmSwapType::ExactIn)
+        } else if discriminator == Self::SWAP_EXACT_OUT_DISCRIMINATOR {
+            Some(MeteoraDlmmSwapType::ExactOut)
+        } else if discriminator == Self::SWAP_WITH_PRICE_IMPACT_DISCRIMINATOR {
+            Some(MeteoraDlmmSwapType::WithPriceImpact)
+        } else if discriminator == Self::SWAP2_DISCRIMINATOR {
+            Some(MeteoraDlmmSwapType::ExactInV2)
+        } else if discriminator == Self::SWAP_EXACT_OUT2_DISCRIMINATOR {
+            Some(MeteoraDlmmSwapType::ExactOutV2)
+        } else if discriminator == Self::SWAP_WITH_PRICE_IMPACT2_DISCRIMINATOR {
+            Some(MeteoraDlmmSwapType::WithPriceImpactV2)
+        } else {
+            None
+        };
+        if let Some(swap_type) = swap_type {
+            return Ok(Some(DecodedMeteoraDlmmEvent::Swap(Self::decode_swap_event(
+                instruction,
+                account_keys,
+                transaction,
+                swap_type,
+            )?)));
+        } else if Self::ADD_LIQUIDITY_DISCRIMINATORS.iter().any(|known| known == discriminator) {
+            return Ok(Some(DecodedMeteoraDlmmEvent::AddLiquidity(
+                Self::decode_liquidity_event(instruction, account_keys, true, false)?,
+            )));
+        } else if Self::ADD_LIQUIDITY_ONE_SIDE_DISCRIMINATORS.iter().any(|known| known == discriminator) {
+            return Ok(Some(DecodedMeteoraDlmmEvent::AddLiquidity(
+                Self::decode_liquidity_event(instruction, account_keys, true, true)?,
+            )));
+        } else if Self::REMOVE_LIQUIDITY_DISCRIMINATORS.iter().any(|known| known == discriminator) {
+            return Ok(Some(DecodedMeteoraDlmmEvent::RemoveLiquidity(
+                Self::decode_liquidity_event(instruction, account_keys, false, false)?,
+            )));
+        } else if Self::INITIALIZE_LB_PAIR_DISCRIMINATORS.iter().any(|known| known == discriminator) {
+            return Ok(Some(DecodedMeteoraDlmmEvent::CreatePool(
+                Self::decode_pool_creation_event(instruction, account_keys)?,
+            )));
+        }
+        Ok(None)
+    }
+
+    /// Every swap variant shares its leading accounts:
+    /// [lb_pair, bin_array_bitmap_extension, reserve_x, reserve_y, user_token_in,
+    /// user_token_out, token_x_mint, token_y_mint, oracle, host_fee_in, user, ...]
+    pub fn decode_swap_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+        swap_type: MeteoraDlmmSwapType,
+    ) -> Result<DecodedMeteoraDlmmSwapEvent, DecodeErrorKind> {
+        let pool = get_account(instruction, account_keys, 0)?;
+        let user = get_account(instruction, account_keys, 10)?;
+        let swap_log = find_event(instruction, &Self::SWAP_EVENT_DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)?;
+        let decoded_swap_log = Self::decode_swap_log(swap_log)?;
+        let (reserve_in, reserve_out) = if decoded_swap_log.swap_for_y { (2, 3) } else { (3, 2) };
+        let (mint_in, mint_in_reserve, in_decimals) =
+            get_instruction_token_balance(instruction, transaction, reserve_in)?;
+        let (mint_out, mint_out_reserve, out_decimals) =
+            get_instruction_token_balance(instruction, transaction, reserve_out)?;
+
+        Ok(DecodedMeteoraDlmmSwapEvent {
+            pool,
+            user,
+            mint_in,
+            mint_out,
+            in_decimals,
+            out_decimals,
+            mint_in_reserve,
+            mint_out_reserve,
+            amount_in: decoded_swap_log.amount_in,
+            amount_out: decoded_swap_log.amount_out,
+            swap_for_y: decoded_swap_log.swap_for_y,
+            start_bin_id: decoded_swap_log.start_bin_id,
+            active_bin_id: decoded_swap_log.end_bin_id,
+            fee: decoded_swap_log.fee,
+            protocol_fee: decoded_swap_log.protocol_fee,
+            host_fee: decoded_swap_log.host_fee,
+            fee_bps: decoded_swap_log.fee_bps,
+            swap_type,
+        })
+    }
+
+    pub fn decode_swap_log(data: &[u8]) -> Result<DecodedMeteoraDlmmSwapLog, DecodeErrorKind> {
+        let mut offset: usize = 0;
+        let lb_pair = read_pubkey(data, offset)?;
+        offset += 32;
+        let from = read_pubkey(data, offset)?;
+        offset += 32;
+        let start_bin_id = read_i32(data, offset)?;
+        offset += 4;
+        let end_bin_id = read_i32(data, offset)?;
+        offset += 4;
+        let amount_in = read_u64(data, offset)?;
+        offset += 8;
+        let amount_out = read_u64(data, offset)?;
+        offset += 8;
+        let swap_for_y = read_bool(data, offset)?;
+        offset += 1;
+        let fee = read_u64(data, offset)?;
+        offset += 8;
+        let protocol_fee = read_u64(data, offset)?;
+        offset += 8;
+        let fee_bps = read_u128(data, offset)?;
+        offset += 16;
+        let host_fee = read_u64(data, offset)?;
+        Ok(DecodedMeteoraDlmmSwapLog {
+            lb_pair,
+            from,
+            start_bin_id,
+            end_bin_id,
+            amount_in,
+            amount_out,
+            swap_for_y,
+            fee,
+            protocol_fee,
+            fee_bps,
+            host_fee,
+        })
+    }
+
+    /// Two-sided variants carry [position, lb_pair, bin_array_bitmap_extension, user_token_x,
+    /// user_token_y, reserve_x, reserve_y, token_x_mint, token_y_mint, ...]
+    ///
+    /// One-sided variants carry [position, lb_pair, bin_array_bitmap_extension, user_token,
+    /// reserve, token_mint, ...]
+    ///
+    /// Pair, owner, position and amounts come from the AddLiquidity / RemoveLiquidity event.
+    pub fn decode_liquidity_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        add: bool,
+        one_side: bool,
+    ) -> Result<DecodedMeteoraDlmmLiquidityEvent, DecodeErrorKind> {
+        let event_discriminator = if add {
+            Self::ADD_LIQUIDITY_EVENT_DISCRIMINATOR
+        } else {
+            Self::REMOVE_LIQUIDITY_EVENT_DISCRIMINATOR
+        };
+        let data = find_event(instruction, &event_discriminator)
+            .ok_or(DecodeErrorKind::MissingEventLog)?;
+        let mut offset: usize = 0;
+        let pool = read_pubkey(data, offset)?;
+        offset += 32;
+        let user = read_pubkey(data, offset)?;
+        offset += 32;
+        let position = read_pubkey(data, offset)?;
+        offset += 32;
+        let amount_x = read_u64(data, offset)?;
+        offset += 8;
+        let amount_y = read_u64(data, offset)?;
+        offset += 8;
+        let active_bin_id = read_i32(data, offset)?;
+
+        let (token_x_mint, token_y_mint) = if one_side {
+            let token_mint = get_account(instruction, account_keys, 5)?;
+            if amount_x > 0 {
+                (Some(token_mint), None)
+            } else {
+                (None, Some(token_mint))
+            }
+        } else {
+            (
+                Some(get_account(instruction, account_keys, 7)?),
+                Some(get_account(instruction, account_keys, 8)?),
+            )
+        };
+
+        Ok(DecodedMeteoraDlmmLiquidityEvent {
+            pool,
+            user,
+            position,
+            token_x_mint,
+            token_y_mint,
+            amount_x,
+            amount_y,
+            active_bin_id,
+        })
+    }
+
+    /// All supported initializers share their leading accounts:
+    /// [lb_pair, bin_array_bitmap_extension, token_mint_x, token_mint_y, reserve_x, reserve_y,
+    /// oracle, preset_parameter / user_token_x, funder, ...]
+    pub fn decode_pool_creation_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Result<DecodedMeteoraDlmmCreatePoolEvent, DecodeErrorKind> {
+        let pool = get_account(instruction, account_keys, 0)?;
+        let token_x_mint = get_account(instruction, account_keys, 2)?;
+        let token_y_mint = get_account(instruction, account_keys, 3)?;
+        let reserve_x = get_account(instruction, account_keys, 4)?;
+        let reserve_y = get_account(instruction, account_keys, 5)?;
+        let creator = get_account(instruction, account_keys, 8)?;
+
+        // every initializer starts its args with active_id
+        let active_bin_id = read_i32(&instruction.data, 8)?;
+        let create_log = find_event(instruction, &Self::LB_PAIR_CREATE_EVENT_DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)?;
+        let bin_step = read_u16(create_log, 32)?;
+
+        Ok(DecodedMeteoraDlmmCreatePoolEvent {
+            pool,
+            creator,
+            token_x_mint,
+            token_y_mint,
+            reserve_x,
+            reserve_y,
+            bin_step,
+            active_bin_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::testing::{USDC_MINT, USER, WSOL_MINT, account_keys, event_cpi, pubkey};
+
+    const LB_PAIR: &str = "5rCf1DM8LjKTw4YqhnoLcngyZYeNnQqztScTogYHAS6";
+    const POSITION: &str = "3Kzk1Fp3JH7ndXnBV2fDTf8e1qAr1mCdZTqXsYEr8Y2Q";
+
+    /// An instruction of the pair with accounts `0..accounts`, emitting `events` through
+    /// self-CPIs.
+    fn instruction(data: Vec<u8>, accounts: u8, events: Vec<Vec<u8>>) -> StructuredInstruction {
+        let inner_instructions = events
+            .into_iter()
+            .enumerate()
+            .map(|(index, event)| event_cpi(accounts, vec![0, index], &event))
+            .collect();
+        StructuredInstruction {
+            account_key_indexes: (0..accounts).collect(),
+            program_id_index: accounts,
+            data,
+            inner_instructions,
+            stack_height: 1,
+            path: vec![0],
+            program_data_logs: Vec::new(),
+        }
+    }
+
+    fn decode(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Option<DecodedMeteoraDlmmEvent> {
+        MeteoraDlmmInstructionParser::new()
+            .decode_instruction(instruction, account_keys, &SubscribeUpdateTransaction::default())
+            .unwrap()
+    }
+
+    #[test]
+    fn decodes_a_swap_log() {
+        let mut data = Vec::new();
+        data.extend(pubkey(LB_PAIR));
+        data.extend(pubkey(USER));
+        data.extend((-1_742i32).to_le_bytes()); // start_bin_id
+        data.extend((-1_745i32).to_le_bytes()); // end_bin_id
+        data.extend(2_000_000_000u64.to_le_bytes()); // amount_in
+        data.extend(301_234_567u64.to_le_bytes()); // amount_out
+        data.push(1); // swap_for_y
+        data.extend(2_000_000u64.to_le_bytes()); // fee
+        data.extend(100_000u64.to_le_bytes()); // protocol_fee
+        data.extend(1_000_000_000_000_000u128.to_le_bytes()); // fee_bps
+        data.extend(0u64.to_le_bytes()); // host_fee
+
+        let log = MeteoraDlmmInstructionParser::decode_swap_log(&data).unwrap();
+        assert_eq!(log.lb_pair, LB_PAIR);
+        assert_eq!(log.from, USER);
+        assert_eq!(log.start_bin_id, -1_742);
+        assert_eq!(log.end_bin_id, -1_745);
+        assert_eq!(log.amount_in, 2_000_000_000);
+        assert_eq!(log.amount_out, 301_234_567);
+        assert!(log.swap_for_y);
+        assert_eq!(log.fee, 2_000_000);
+        assert_eq!(log.protocol_fee, 100_000);
+        assert_eq!(log.fee_bps, 1_000_000_000_000_000);
+        assert_eq!(log.host_fee, 0);
+    }
+
+    #[test]
+    fn decodes_a_one_sided_deposit_from_its_add_liquidity_event() {
+        let mut account_keys = account_keys(12);
+        account_keys[5] = USDC_MINT.to_string();
+        account_keys.push(MeteoraDlmmInstructionParser::PROGRAM_ID.to_string());
+
+        let mut event = MeteoraDlmmInstructionParser::ADD_LIQUIDITY_EVENT_DISCRIMINATOR.to_vec();
+        event.extend(pubkey(LB_PAIR));
+        event.extend(pubkey(USER));
+        event.extend(pubkey(POSITION));
+        event.extend(0u64.to_le_bytes()); // amounts[0]
+        event.extend(150_000_000u64.to_le_bytes()); // amounts[1]
+        event.extend((-1_744i32).to_le_bytes()); // active_bin_id
+        let data = MeteoraDlmmInstructionParser::ADD_LIQUIDITY_ONE_SIDE_DISCRIMINATORS[1].to_vec();
+        let add = instruction(data, 12, vec![event]);
+
+        let Some(DecodedMeteoraDlmmEvent::AddLiquidity(liquidity)) = decode(&add, &account_keys)
+        else {
+            panic!("expected a deposit");
+        };
+        assert_eq!(liquidity.pool, LB_PAIR);
+        assert_eq!(liquidity.user, USER);
+        assert_eq!(liquidity.position, POSITION);
+        assert_eq!(liquidity.token_x_mint, None);
+        assert_eq!(liquidity.token_y_mint.as_deref(), Some(USDC_MINT));
+        assert_eq!(liquidity.amount_x, 0);
+        assert_eq!(liquidity.amount_y, 150_000_000);
+        assert_eq!(liquidity.active_bin_id, -1_744);
+    }
+
+    #[test]
+    fn decodes_a_pair_creation_from_its_lb_pair_create_event() {
+        let mut account_keys = account_keys(9);
+        account_keys[0] = LB_PAIR.to_string();
+        account_keys[2] = WSOL_MINT.to_string();
+        account_keys[3] = USDC_MINT.to_string();
+        account_keys[8] = USER.to_string();
+        account_keys.push(MeteoraDlmmInstructionParser::PROGRAM_ID.to_string());
+
+        let mut event = MeteoraDlmmInstructionParser::LB_PAIR_CREATE_EVENT_DISCRIMINATOR.to_vec();
+        event.extend(pubkey(LB_PAIR));
+        event.extend(10u16.to_le_bytes()); // bin_step
+        event.extend(pubkey(WSOL_MINT));
+        event.extend(pubkey(USDC_MINT));
+        let mut data = MeteoraDlmmInstructionParser::INITIALIZE_LB_PAIR_DISCRIMINATORS[0].to_vec();
+        data.extend((-1_744i32).to_le_bytes()); // active_id
+        data.extend(10u16.to_le_bytes()); // bin_step
+        let create = instruction(data, 9, vec![event]);
+
+        let Some(DecodedMeteoraDlmmEvent::CreatePool(pool)) = decode(&create, &account_keys) else {
+            panic!("expected a pair creation");
+        };
+        assert_eq!(pool.pool, LB_PAIR);
+        assert_eq!(pool.creator, USER);
+        assert_eq!(pool.token_x_mint, WSOL_MINT);
+        assert_eq!(pool.token_y_mint, USDC_MINT);
+        assert_eq!(pool.bin_step, 10);
+        assert_eq!(pool.active_bin_id, -1_744);
+    }
+}
@@ -0,0 +1,381 @@
+use instruction_parser::InstructionParser;
+use types::{
+    DecodeError, DecodeErrorKind, DecodeReport, DecodedEvent, DecodedInstruction,
+    DecodedMoonshotCreatePoolEvent, DecodedMoonshotEvent, DecodedMoonshotMigrateEvent,
+    DecodedMoonshotSwapEvent, StructuredInstruction, SwapEventAccounts, TransactionType,
+};
+use utils::{
+    find_program_data_logs, get_account, get_account_lamports, get_instruction_token_balance,
+    read_pubkey, read_string, read_u8, read_u64,
+};
+use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
+
+#[derive(Clone, Debug, Default)]
+pub struct MoonshotInstructionParser {}
+
+impl InstructionParser for MoonshotInstructionParser {
+    fn get_program_id(&self) -> &str {
+        Self::PROGRAM_ID
+    }
+
+    fn decode_instructions(
+        &self,
+        instructions: Vec<StructuredInstruction>,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> DecodeReport {
+        let mut report = DecodeReport::default();
+        for instruction in instructions.iter() {
+            match self.decode_instruction(instruction, account_keys, transaction) {
+                Ok(Some(decoded_instruction)) => report.events.push(DecodedInstruction::new(
+                    Self::PROGRAM_ID,
+                    instruction,
+                    DecodedEvent::Moonshot(decoded_instruction),
+                )),
+                Ok(None) => {}
+                Err(kind) => report
+                    .errors
+                    .push(DecodeError::new(Self::PROGRAM_ID, instruction, 8, kind)),
+            }
+        }
+        report
+    }
+}
+
+impl MoonshotInstructionParser {
+    const PROGRAM_ID: &'static str = "MoonCVVNZFSYkqNXP6bxHLPL6QQJiMagDL3qcqUQTrG";
+    const WSOL_ADDRESS: &'static str = "So11111111111111111111111111111111111111112";
+    const TOKEN_MINT_DISCRIMINATOR: [u8; 8] = [3, 44, 164, 184, 123, 13, 245, 179];
+    const BUY_DISCRIMINATOR: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
+    const SELL_DISCRIMINATOR: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+    const MIGRATE_FUNDS_DISCRIMINATOR: [u8; 8] = [42, 229, 10, 231, 189, 62, 193, 174];
+    const TRADE_EVENT_DISCRIMINATOR: [u8; 8] = [189, 219, 127, 211, 78, 230, 97, 238];
+    const MIGRATION_EVENT_DISCRIMINATOR: [u8; 8] = [255, 202, 76, 147, 91, 231, 73, 22];
+
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Moonshot emits its events with `emit!`, so they are read back from the log messages.
+    pub fn decode_instruction(
+        &self,
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> Result<Option<DecodedMoonshotEvent>, DecodeErrorKind> {
+        let discriminator = match instruction.data.get(..8) {
+            Some(discriminator) => discriminator,
+            None => return Ok(None),
+        };
+        if discriminator == Self::BUY_DISCRIMINATOR {
+            return Ok(Some(DecodedMoonshotEvent::Swap(Self::decode_swap_event(
+                instruction,
+                account_keys,
+                transaction,
+                true,
+            )?)));
+        } else if discriminator == Self::SELL_DISCRIMINATOR {
+            return Ok(Some(DecodedMoonshotEvent::Swap(Self::decode_swap_event(
+                instruction,
+                account_keys,
+                transaction,
+                false,
+            )?)));
+        } else if discriminator == Self::TOKEN_MINT_DISCRIMINATOR {
+            return Ok(Some(DecodedMoonshotEvent::CreatePool(
+                Self::decode_token_mint_event(instruction, account_keys)?,
+            )));
+        } else if discriminator == Self::MIGRATE_FUNDS_DISCRIMINATOR {
+            return Ok(Some(DecodedMoonshotEvent::Migrate(Self::decode_migrate_event(
+                instruction,
+                account_keys,
+            )?)));
+        }
+        Ok(None)
+    }
+
+    fn find_event_log(
+        instruction: &StructuredInstruction,
+        discriminator: &[u8; 8],
+    ) -> Result<Vec<u8>, DecodeErrorKind> {
+        find_program_data_logs(instruction)
+            .iter()
+            .find(|data| data.get(..8) == Some(&discriminator[..]))
+            .map(|data| data[8..].to_vec())
+            .ok_or(DecodeErrorKind::MissingEventLog)
+    }
+
+    /// `buy` and `sell` share their accounts:
+    /// [sender, sender_token_account, curve_account, curve_token_account, dex_fee, helio_fee,
+    /// mint, config_account, token_program, associated_token_program, system_program]
+    ///
+    /// `TradeEvent`: amount, collateral_amount, dex_fee, helio_fee, allocation, curve,
+    /// cost_token, sender, type, label
+    pub fn decode_swap_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+        buy: bool,
+    ) -> Result<DecodedMoonshotSwapEvent, DecodeErrorKind> {
+        let bonding_curve = get_account(instruction, account_keys, 2)?;
+        let mint = get_account(instruction, account_keys, 6)?;
+        let bonding_curve_index = *instruction
+            .account_key_indexes
+            .get(2)
+            .ok_or(DecodeErrorKind::MissingAccount { position: 2 })?;
+        let sol_reserve = get_account_lamports(transaction, bonding_curve_index as u32)?;
+        let (_, token_reserve, _) = get_instruction_token_balance(instruction, transaction, 3)?;
+
+        let data = Self::find_event_log(instruction, &Self::TRADE_EVENT_DISCRIMINATOR)?;
+        let mut offset: usize = 0;
+        let token_amount = read_u64(&data, offset)?;
+        offset += 8;
+        let collateral_amount = read_u64(&data, offset)?;
+        offset += 8;
+        let dex_fee = read_u64(&data, offset)?;
+        offset += 8;
+        let helio_fee = read_u64(&data, offset)?;
+        // allocation, curve, cost_token
+        offset += 80;
+        let user = read_pubkey(&data, offset)?;
+
+        let wsol = Self::WSOL_ADDRESS.to_string();
+        let (mint_in, mint_out, amount_in, amount_out, mint_in_reserve, mint_out_reserve, event_type) =
+            if buy {
+                (
+                    wsol.clone(),
+                    mint.clone(),
+                    collateral_amount,
+                    token_amount,
+                    sol_reserve,
+                    token_reserve,
+                    TransactionType::Buy,
+                )
+            } else {
+                (
+                    mint.clone(),
+                    wsol.clone(),
+                    token_amount,
+                    collateral_amount,
+                    token_reserve,
+                    sol_reserve,
+                    TransactionType::Sell,
+                )
+            };
+
+        Ok(DecodedMoonshotSwapEvent {
+            accounts: SwapEventAccounts {
+                pool: bonding_curve,
+                user,
+                base_mint: mint,
+                quote_mint: wsol,
+            },
+            mint_in,
+            mint_out,
+            amount_in,
+            amount_out,
+            mint_in_reserve,
+            mint_out_reserve,
+            dex_fee,
+            helio_fee,
+            event_type,
+        })
+    }
+
+    /// Accounts: [sender, backend_authority, curve_account, mint, mint_metadata,
+    /// curve_token_account, config_account, ...]
+    ///
+    /// Args: name, symbol, uri, decimals, collateral_currency, amount, curve_type,
+    /// migration_target
+    pub fn decode_token_mint_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Result<DecodedMoonshotCreatePoolEvent, DecodeErrorKind> {
+        let data: &Vec<u8> = &instruction.data;
+        let offset = 8;
+        let (name, offset) = read_string(data, offset)?;
+        let (symbol, offset) = read_string(data, offset)?;
+        let (uri, offset) = read_string(data, offset)?;
+        let decimals = read_u8(data, offset)?;
+
+        let creator = get_account(instruction, account_keys, 0)?;
+        let bonding_curve = get_account(instruction, account_keys, 2)?;
+        let mint = get_account(instruction, account_keys, 3)?;
+        let bonding_curve_token_account = get_account(instruction, account_keys, 5)?;
+
+        Ok(DecodedMoonshotCreatePoolEvent {
+            name,
+            symbol,
+            uri,
+            creator,
+            base_mint: mint,
+            quote_mint: Self::WSOL_ADDRESS.to_string(),
+            bonding_curve,
+            bonding_curve_token_account,
+            decimals,
+            event_type: TransactionType::CreatePool,
+        })
+    }
+
+    /// Accounts: [backend_authority, migration_authority, curve_account, curve_token_account,
+    /// migration_authority_token_account, mint, ...]
+    ///
+    /// `MigrationEvent`: tokens_migrated, tokens_burned, collateral_migrated, fee, label
+    pub fn decode_migrate_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Result<DecodedMoonshotMigrateEvent, DecodeErrorKind> {
+        let bonding_curve = get_account(instruction, account_keys, 2)?;
+        let mint = get_account(instruction, account_keys, 5)?;
+        let data =
+            Self::find_event_log(instruction, &Self::MIGRATION_EVENT_DISCRIMINATOR)?;
+        let mut offset: usize = 0;
+        let tokens_migrated = read_u64(&data, offset)?;
+        offset += 8;
+        let tokens_burned = read_u64(&data, offset)?;
+        offset += 8;
+        let collateral_migrated = read_u64(&data, offset)?;
+        offset += 8;
+        let fee = read_u64(&data, offset)?;
+        Ok(DecodedMoonshotMigrateEvent {
+            base_mint: mint,
+            bonding_curve,
+            tokens_migrated,
+            tokens_burned,
+            collateral_migrated,
+            fee,
+            event_type: TransactionType::Migrate,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::testing::{USER, pubkey};
+    use yellowstone_grpc_proto::prelude::{
+        SubscribeUpdateTransactionInfo, TokenBalance, TransactionStatusMeta, UiTokenAmount,
+    };
+
+    const CURVE: &str = "3Kzk1Fp3JH7ndXnBV2fDTf8e1qAr1mCdZTqXsYEr8Y2Q";
+    const MINT: &str = "4k3Dyjzvzp8eMZWUXbBCjEvwSkkk59S5iCNLY3QrkX6R";
+
+    /// The accounts of `buy` and `sell`, followed by the program.
+    fn account_keys() -> Vec<String> {
+        let mut account_keys: Vec<String> =
+            (0..11).map(|index| format!("account{index}")).collect();
+        account_keys[0] = USER.to_string();
+        account_keys[2] = CURVE.to_string();
+        account_keys[6] = MINT.to_string();
+        account_keys.push(MoonshotInstructionParser::PROGRAM_ID.to_string());
+        account_keys
+    }
+
+    fn instruction(data: Vec<u8>, data_logs: Vec<Vec<u8>>) -> StructuredInstruction {
+        StructuredInstruction {
+            account_key_indexes: (0..11).collect(),
+            program_id_index: 11,
+            data,
+            inner_instructions: Vec::new(),
+            stack_height: 1,
+            path: vec![0],
+            program_data_logs: data_logs,
+        }
+    }
+
+    #[test]
+    fn decodes_a_buy_from_its_logged_trade_event() {
+        let mut post_balances = vec![0; 11];
+        post_balances[2] = 12_500_000_000;
+        let transaction = SubscribeUpdateTransaction {
+            transaction: Some(SubscribeUpdateTransactionInfo {
+                meta: Some(TransactionStatusMeta {
+                    post_balances,
+                    post_token_balances: vec![TokenBalance {
+                        account_index: 3,
+                        mint: MINT.to_string(),
+                        ui_token_amount: Some(UiTokenAmount {
+                            amount: "612345678901234567".to_string(),
+                            decimals: 9,
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let mut trade_event = MoonshotInstructionParser::TRADE_EVENT_DISCRIMINATOR.to_vec();
+        trade_event.extend(12_345_678_901_234u64.to_le_bytes()); // amount
+        trade_event.extend(500_000_000u64.to_le_bytes()); // collateral_amount
+        trade_event.extend(4_000_000u64.to_le_bytes()); // dex_fee
+        trade_event.extend(1_000_000u64.to_le_bytes()); // helio_fee
+        trade_event.extend(0u64.to_le_bytes()); // allocation
+        trade_event.extend(pubkey(CURVE));
+        trade_event.extend(pubkey(MoonshotInstructionParser::WSOL_ADDRESS)); // cost_token
+        trade_event.extend(pubkey(USER)); // sender
+        trade_event.push(0); // type
+        trade_event.extend(4u32.to_le_bytes());
+        trade_event.extend(b"moon"); // label
+        let mut data = MoonshotInstructionParser::BUY_DISCRIMINATOR.to_vec();
+        data.extend(12_345_678_901_234u64.to_le_bytes()); // token_amount
+        data.extend(505_000_000u64.to_le_bytes()); // collateral_amount
+        data.push(0); // fixed_side
+        data.extend(100u64.to_le_bytes()); // slippage_bps
+        let buy = instruction(data, vec![trade_event]);
+
+        let Some(DecodedMoonshotEvent::Swap(event)) = MoonshotInstructionParser::new()
+            .decode_instruction(&buy, &account_keys(), &transaction)
+            .unwrap()
+        else {
+            panic!("expected a swap");
+        };
+        assert_eq!(event.accounts.pool, CURVE);
+        assert_eq!(event.accounts.user, USER);
+        assert_eq!(event.accounts.base_mint, MINT);
+        assert_eq!(event.mint_in, MoonshotInstructionParser::WSOL_ADDRESS);
+        assert_eq!(event.mint_out, MINT);
+        assert_eq!(event.amount_in, 500_000_000);
+        assert_eq!(event.amount_out, 12_345_678_901_234);
+        assert_eq!(event.mint_in_reserve, 12_500_000_000);
+        assert_eq!(event.mint_out_reserve, 612_345_678_901_234_567);
+        assert_eq!(event.dex_fee, 4_000_000);
+        assert_eq!(event.helio_fee, 1_000_000);
+        assert!(matches!(event.event_type, TransactionType::Buy));
+    }
+
+    #[test]
+    fn decodes_a_migration_from_its_logged_event() {
+        let mut account_keys = account_keys();
+        account_keys.swap(5, 6); // the mint comes sixth in migrate_funds
+        let mut migration_event =
+            MoonshotInstructionParser::MIGRATION_EVENT_DISCRIMINATOR.to_vec();
+        for amount in [
+            200_000_000_000_000_000u64, // tokens_migrated
+            10_000_000_000_000_000,     // tokens_burned
+            80_000_000_000,             // collateral_migrated
+            2_400_000_000,              // fee
+        ] {
+            migration_event.extend(amount.to_le_bytes());
+        }
+        migration_event.extend(4u32.to_le_bytes());
+        migration_event.extend(b"moon"); // label
+        let data = MoonshotInstructionParser::MIGRATE_FUNDS_DISCRIMINATOR.to_vec();
+        let migrate = instruction(data, vec![migration_event]);
+
+        let Some(DecodedMoonshotEvent::Migrate(event)) = MoonshotInstructionParser::new()
+            .decode_instruction(&migrate, &account_keys, &SubscribeUpdateTransaction::default())
+            .unwrap()
+        else {
+            panic!("expected a migration");
+        };
+        assert_eq!(event.bonding_curve, CURVE);
+        assert_eq!(event.base_mint, MINT);
+        assert_eq!(event.tokens_migrated, 200_000_000_000_000_000);
+        assert_eq!(event.tokens_burned, 10_000_000_000_000_000);
+        assert_eq!(event.collateral_migrated, 80_000_000_000);
+        assert_eq!(event.fee, 2_400_000_000);
+    }
+}
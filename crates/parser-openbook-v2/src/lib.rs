@@ -0,0 +1,446 @@
+use instruction_parser::InstructionParser;
+use types::{
+    DecodeError, DecodeErrorKind, DecodeReport, DecodedEvent, DecodedInstruction,
+    DecodedOpenBookV2CancelOrderEvent, DecodedOpenBookV2ConsumeEventsEvent,
+    DecodedOpenBookV2Event, DecodedOpenBookV2FillEvent, DecodedOpenBookV2PlaceOrderEvent,
+    OpenBookV2PlaceOrderType, OpenBookV2Side, StructuredInstruction,
+};
+use utils::{
+    find_program_data_logs, find_token_transfer, get_account, get_token_transfers, read_bool,
+    read_i64, read_pubkey, read_u8, read_u64, read_u128,
+};
+use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
+
+#[derive(Clone, Debug, Default)]
+pub struct OpenBookV2InstructionParser {}
+
+impl InstructionParser for OpenBookV2InstructionParser {
+    fn get_program_id(&self) -> &str {
+        Self::PROGRAM_ID
+    }
+
+    fn decode_instructions(
+        &self,
+        instructions: Vec<StructuredInstruction>,
+        account_keys: &[String],
+        _transaction: &SubscribeUpdateTransaction,
+    ) -> DecodeReport {
+        let mut report = DecodeReport::default();
+        for instruction in instructions.iter() {
+            match self.decode_instruction(instruction, account_keys) {
+                Ok(Some(decoded_instruction)) => report.events.push(DecodedInstruction::new(
+                    Self::PROGRAM_ID,
+                    instruction,
+                    DecodedEvent::OpenBookV2(decoded_instruction),
+                )),
+                Ok(None) => {}
+                Err(kind) => report
+                    .errors
+                    .push(DecodeError::new(Self::PROGRAM_ID, instruction, 8, kind)),
+            }
+        }
+        report
+    }
+}
+
+impl OpenBookV2InstructionParser {
+    const PROGRAM_ID: &'static str = "opnb2LAfJYbRMAHHvqjCwQxanZn7ReEHp1k81EohpZb";
+    const PLACE_ORDER_DISCRIMINATOR: [u8; 8] = [51, 194, 155, 175, 109, 130, 96, 106];
+    const PLACE_TAKE_ORDER_DISCRIMINATOR: [u8; 8] = [3, 44, 71, 3, 26, 199, 203, 85];
+    const CONSUME_EVENTS_DISCRIMINATOR: [u8; 8] = [221, 145, 177, 52, 31, 47, 63, 201];
+    const CONSUME_GIVEN_EVENTS_DISCRIMINATOR: [u8; 8] = [209, 227, 54, 4, 109, 172, 41, 71];
+    const CANCEL_ORDER_DISCRIMINATOR: [u8; 8] = [95, 129, 237, 240, 8, 49, 223, 132];
+    const CANCEL_ORDER_BY_CLIENT_ORDER_ID_DISCRIMINATOR: [u8; 8] =
+        [115, 178, 201, 8, 175, 183, 123, 119];
+    const FILL_LOG_DISCRIMINATOR: [u8; 8] = [150, 23, 41, 148, 152, 162, 215, 64];
+    const TAKER_SIGNATURE_LOG_DISCRIMINATOR: [u8; 8] = [125, 167, 182, 246, 249, 3, 57, 77];
+
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// OpenBook v2 logs its events with `sol_log_data` rather than a self-CPI, so they are read
+    /// back from the `Program data:` logs of the instruction.
+    pub fn decode_instruction(
+        &self,
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Result<Option<DecodedOpenBookV2Event>, DecodeErrorKind> {
+        let discriminator = match instruction.data.get(..8) {
+            Some(discriminator) => discriminator,
+            None => return Ok(None),
+        };
+        if discriminator == Self::PLACE_ORDER_DISCRIMINATOR {
+            return Ok(Some(DecodedOpenBookV2Event::PlaceOrder(Self::decode_place_order_event(
+                instruction,
+                account_keys,
+            )?)));
+        } else if discriminator == Self::PLACE_TAKE_ORDER_DISCRIMINATOR {
+            return Ok(Some(DecodedOpenBookV2Event::PlaceOrder(
+                Self::decode_place_take_order_event(instruction, account_keys)?,
+            )));
+        } else if discriminator == Self::CONSUME_EVENTS_DISCRIMINATOR
+            || discriminator == Self::CONSUME_GIVEN_EVENTS_DISCRIMINATOR
+        {
+            return Ok(Some(DecodedOpenBookV2Event::ConsumeEvents(
+                Self::decode_consume_events_event(instruction, account_keys)?,
+            )));
+        } else if discriminator == Self::CANCEL_ORDER_DISCRIMINATOR
+            || discriminator == Self::CANCEL_ORDER_BY_CLIENT_ORDER_ID_DISCRIMINATOR
+        {
+            return Ok(Some(DecodedOpenBookV2Event::CancelOrder(
+                Self::decode_cancel_order_event(instruction, account_keys)?,
+            )));
+        }
+        Ok(None)
+    }
+
+    /// Accounts: [signer, open_orders_account, open_orders_admin, user_token_account, market,
+    /// bids, asks, event_heap, market_vault, oracle_a, oracle_b, token_program]
+    ///
+    /// Args: side, price_lots, max_base_lots, max_quote_lots_including_fees, client_order_id,
+    /// order_type, expiry_timestamp, self_trade_behavior, limit
+    pub fn decode_place_order_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Result<DecodedOpenBookV2PlaceOrderEvent, DecodeErrorKind> {
+        let owner = get_account(instruction, account_keys, 0)?;
+        let open_orders_account = get_account(instruction, account_keys, 1)?;
+        let market = get_account(instruction, account_keys, 4)?;
+        let data = &instruction.data;
+        let mut offset: usize = 8;
+        let side = Self::side(read_u8(data, offset)?);
+        offset += 1;
+        let price_lots = read_i64(data, offset)?;
+        offset += 8;
+        let max_base_lots = read_i64(data, offset)?;
+        offset += 8;
+        let max_quote_lots_including_fees = read_i64(data, offset)?;
+        offset += 8;
+        let client_order_id = read_u64(data, offset)?;
+        offset += 8;
+        let order_type = read_u8(data, offset)?;
+        let (fills, pending_fills) = Self::decode_taker_logs(instruction)?;
+        Ok(DecodedOpenBookV2PlaceOrderEvent {
+            market,
+            owner,
+            open_orders_account: Some(open_orders_account),
+            side,
+            price_lots,
+            max_base_lots,
+            max_quote_lots_including_fees,
+            client_order_id: Some(client_order_id),
+            order_type,
+            place_order_type: OpenBookV2PlaceOrderType::PlaceOrder,
+            fills,
+            pending_fills,
+            base_amount: None,
+            quote_amount: None,
+        })
+    }
+
+    /// Accounts: [signer, penalty_payer, market, market_authority, bids, asks,
+    /// market_base_vault, market_quote_vault, event_heap, user_base_account,
+    /// user_quote_account, oracle_a, oracle_b, token_program, system_program,
+    /// open_orders_admin]
+    ///
+    /// Args: side, price_lots, max_base_lots, max_quote_lots_including_fees, order_type, limit
+    pub fn decode_place_take_order_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Result<DecodedOpenBookV2PlaceOrderEvent, DecodeErrorKind> {
+        let owner = get_account(instruction, account_keys, 0)?;
+        let market = get_account(instruction, account_keys, 2)?;
+        let base_vault = get_account(instruction, account_keys, 6)?;
+        let quote_vault = get_account(instruction, account_keys, 7)?;
+        let data = &instruction.data;
+        let mut offset: usize = 8;
+        let side = Self::side(read_u8(data, offset)?);
+        offset += 1;
+        let price_lots = read_i64(data, offset)?;
+        offset += 8;
+        let max_base_lots = read_i64(data, offset)?;
+        offset += 8;
+        let max_quote_lots_including_fees = read_i64(data, offset)?;
+        offset += 8;
+        let order_type = read_u8(data, offset)?;
+        let (fills, pending_fills) = Self::decode_taker_logs(instruction)?;
+
+        // a bid pays quote into the market and receives base, an ask the other way around
+        let transfers = get_token_transfers(instruction, account_keys)?;
+        let buy = side == OpenBookV2Side::Bid;
+        let base_amount = find_token_transfer(&transfers, &base_vault, !buy)
+            .ok()
+            .map(|transfer| transfer.amount);
+        let quote_amount = find_token_transfer(&transfers, &quote_vault, buy)
+            .ok()
+            .map(|transfer| transfer.amount);
+
+        Ok(DecodedOpenBookV2PlaceOrderEvent {
+            market,
+            owner,
+            open_orders_account: None,
+            side,
+            price_lots,
+            max_base_lots,
+            max_quote_lots_including_fees,
+            client_order_id: None,
+            order_type,
+            place_order_type: OpenBookV2PlaceOrderType::PlaceTakeOrder,
+            fills,
+            pending_fills,
+            base_amount,
+            quote_amount,
+        })
+    }
+
+    /// Accounts: [consume_events_admin, market, event_heap, ...open_orders_accounts]
+    pub fn decode_consume_events_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Result<DecodedOpenBookV2ConsumeEventsEvent, DecodeErrorKind> {
+        let market = get_account(instruction, account_keys, 1)?;
+        let mut fills = Vec::new();
+        for data in find_program_data_logs(instruction) {
+            if data.get(..8) == Some(&Self::FILL_LOG_DISCRIMINATOR[..]) {
+                fills.push(Self::decode_fill_log(&data[8..])?);
+            }
+        }
+        Ok(DecodedOpenBookV2ConsumeEventsEvent { market, fills })
+    }
+
+    /// Accounts: [signer, open_orders_account, market, bids, asks]
+    ///
+    /// Args: order_id for `cancel_order`, client_order_id for
+    /// `cancel_order_by_client_order_id`
+    pub fn decode_cancel_order_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Result<DecodedOpenBookV2CancelOrderEvent, DecodeErrorKind> {
+        let owner = get_account(instruction, account_keys, 0)?;
+        let open_orders_account = get_account(instruction, account_keys, 1)?;
+        let market = get_account(instruction, account_keys, 2)?;
+        let (order_id, client_order_id) =
+            if instruction.data[..8] == Self::CANCEL_ORDER_DISCRIMINATOR {
+                (Some(read_u128(&instruction.data, 8)?), None)
+            } else {
+                (None, Some(read_u64(&instruction.data, 8)?))
+            };
+        Ok(DecodedOpenBookV2CancelOrderEvent {
+            market,
+            owner,
+            open_orders_account,
+            order_id,
+            client_order_id,
+        })
+    }
+
+    /// Every match logs a `TakerSignatureLog` with the sequence number of its fill. The fill
+    /// is settled right away, logging a `FillLog`, when the maker's open orders account was
+    /// passed along; otherwise it waits on the event heap.
+    fn decode_taker_logs(
+        instruction: &StructuredInstruction,
+    ) -> Result<(Vec<DecodedOpenBookV2FillEvent>, Vec<u64>), DecodeErrorKind> {
+        let mut fills = Vec::new();
+        let mut seq_nums = Vec::new();
+        for data in find_program_data_logs(instruction) {
+            let discriminator = match data.get(..8) {
+                Some(discriminator) => discriminator,
+                None => continue,
+            };
+            if discriminator == Self::FILL_LOG_DISCRIMINATOR {
+                fills.push(Self::decode_fill_log(&data[8..])?);
+            } else if discriminator == Self::TAKER_SIGNATURE_LOG_DISCRIMINATOR {
+                // market
+                seq_nums.push(read_u64(data, 8 + 32)?);
+            }
+        }
+        let pending_fills = seq_nums
+            .into_iter()
+            .filter(|seq_num| fills.iter().all(|fill| fill.seq_num != *seq_num))
+            .collect();
+        Ok((fills, pending_fills))
+    }
+
+    /// `FillLog`: market, taker_side, maker_slot, maker_out, timestamp, seq_num, maker,
+    /// maker_client_order_id, maker_fee, maker_timestamp, taker, taker_client_order_id,
+    /// taker_fee_ceil, price, quantity
+    pub fn decode_fill_log(data: &[u8]) -> Result<DecodedOpenBookV2FillEvent, DecodeErrorKind> {
+        let mut offset: usize = 0;
+        let market = read_pubkey(data, offset)?;
+        offset += 32;
+        let taker_side = Self::side(read_u8(data, offset)?);
+        offset += 1;
+        let maker_slot = read_u8(data, offset)?;
+        offset += 1;
+        let maker_out = read_bool(data, offset)?;
+        offset += 1;
+        let timestamp = read_u64(data, offset)?;
+        offset += 8;
+        let seq_num = read_u64(data, offset)?;
+        offset += 8;
+        let maker = read_pubkey(data, offset)?;
+        offset += 32;
+        let maker_client_order_id = read_u64(data, offset)?;
+        offset += 8;
+        let maker_fee = read_u64(data, offset)?;
+        offset += 8;
+        let maker_timestamp = read_u64(data, offset)?;
+        offset += 8;
+        let taker = read_pubkey(data, offset)?;
+        offset += 32;
+        let taker_client_order_id = read_u64(data, offset)?;
+        offset += 8;
+        let taker_fee_ceil = read_u64(data, offset)?;
+        offset += 8;
+        let price_lots = read_i64(data, offset)?;
+        offset += 8;
+        let base_lots = read_i64(data, offset)?;
+        Ok(DecodedOpenBookV2FillEvent {
+            market,
+            taker_side,
+            maker_slot,
+            maker_out,
+            timestamp,
+            seq_num,
+            maker,
+            maker_client_order_id,
+            maker_fee,
+            maker_timestamp,
+            taker,
+            taker_client_order_id,
+            taker_fee_ceil,
+            price_lots,
+            base_lots,
+        })
+    }
+
+    fn side(side: u8) -> OpenBookV2Side {
+        if side == 0 {
+            OpenBookV2Side::Bid
+        } else {
+            OpenBookV2Side::Ask
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::TOKEN_PROGRAM_ID;
+    use utils::testing::{account_keys, pubkey};
+
+    const MARKET: &str = "CFSMrBssNG8Ud1edW59jNLnq2cwrQ9uY5cM3wXmqRJj3";
+    const TAKER: &str = "7u7cD7NxcZEuzRCBaYo8uVpotRdqZwez47vvuwzCov43";
+    const MAKER: &str = "5Y3ZhsLXDvk4xJSMKzPCWHs2xTA3KtVVr6Q8mG5jJ4Kn";
+
+    fn instruction(
+        path: Vec<usize>,
+        program_id_index: u8,
+        account_key_indexes: Vec<u8>,
+        data: Vec<u8>,
+    ) -> StructuredInstruction {
+        StructuredInstruction {
+            account_key_indexes,
+            program_id_index,
+            data,
+            inner_instructions: Vec::new(),
+            stack_height: path.len() as u8,
+            path,
+            program_data_logs: Vec::new(),
+        }
+    }
+
+    /// A `FillLog` of a taker buying 25 base lots at 1_512 quote lots from an ask.
+    fn fill_log(seq_num: u64) -> Vec<u8> {
+        let mut data = OpenBookV2InstructionParser::FILL_LOG_DISCRIMINATOR.to_vec();
+        data.extend(pubkey(MARKET));
+        data.extend([0, 3, 1]); // taker_side, maker_slot, maker_out
+        data.extend(1_750_012_345u64.to_le_bytes()); // timestamp
+        data.extend(seq_num.to_le_bytes());
+        data.extend(pubkey(MAKER));
+        data.extend(9_001u64.to_le_bytes()); // maker_client_order_id
+        data.extend(0u64.to_le_bytes()); // maker_fee
+        data.extend(1_750_000_000u64.to_le_bytes()); // maker_timestamp
+        data.extend(pubkey(TAKER));
+        data.extend(42u64.to_le_bytes()); // taker_client_order_id
+        data.extend(38u64.to_le_bytes()); // taker_fee_ceil
+        data.extend(1_512i64.to_le_bytes()); // price
+        data.extend(25i64.to_le_bytes()); // quantity
+        data
+    }
+
+    fn taker_signature_log(seq_num: u64) -> Vec<u8> {
+        let mut data = OpenBookV2InstructionParser::TAKER_SIGNATURE_LOG_DISCRIMINATOR.to_vec();
+        data.extend(pubkey(MARKET));
+        data.extend(seq_num.to_le_bytes());
+        data.extend([0; 32]); // signature
+        data
+    }
+
+    #[test]
+    fn decodes_a_fill_log() {
+        let fill = OpenBookV2InstructionParser::decode_fill_log(&fill_log(7)[8..]).unwrap();
+        assert_eq!(fill.market, MARKET);
+        assert_eq!(fill.taker_side, OpenBookV2Side::Bid);
+        assert_eq!(fill.maker_slot, 3);
+        assert!(fill.maker_out);
+        assert_eq!(fill.timestamp, 1_750_012_345);
+        assert_eq!(fill.seq_num, 7);
+        assert_eq!(fill.maker, MAKER);
+        assert_eq!(fill.maker_client_order_id, 9_001);
+        assert_eq!(fill.maker_timestamp, 1_750_000_000);
+        assert_eq!(fill.taker, TAKER);
+        assert_eq!(fill.taker_client_order_id, 42);
+        assert_eq!(fill.taker_fee_ceil, 38);
+        assert_eq!(fill.price_lots, 1_512);
+        assert_eq!(fill.base_lots, 25);
+    }
+
+    #[test]
+    fn place_take_order_pairs_fills_with_taker_signatures() {
+        let mut account_keys = account_keys(16);
+        account_keys[0] = TAKER.to_string();
+        account_keys[2] = MARKET.to_string();
+        account_keys.push(TOKEN_PROGRAM_ID.to_string()); // 16
+        account_keys.push(OpenBookV2InstructionParser::PROGRAM_ID.to_string()); // 17
+
+        let mut data = OpenBookV2InstructionParser::PLACE_TAKE_ORDER_DISCRIMINATOR.to_vec();
+        data.push(0); // side
+        for value in [1_520i64, 50, 80_000] {
+            // price_lots, max_base_lots, max_quote_lots_including_fees
+            data.extend(value.to_le_bytes());
+        }
+        data.extend([3, 10]); // order_type, limit
+        let mut place = instruction(vec![0], 17, (0..16).collect(), data);
+        let transfer = |index, source, destination, amount: u64| {
+            let mut data = vec![3];
+            data.extend(amount.to_le_bytes());
+            instruction(vec![0, index], 16, vec![source, destination, 0], data)
+        };
+        // the quote in from the taker, the base out of the market
+        place.inner_instructions.push(transfer(0, 10, 7, 37_838_000));
+        place.inner_instructions.push(transfer(1, 6, 9, 25_000_000));
+        place.program_data_logs = vec![
+            taker_signature_log(7),
+            fill_log(7),
+            taker_signature_log(8),
+        ];
+
+        let event =
+            OpenBookV2InstructionParser::decode_place_take_order_event(&place, &account_keys)
+                .unwrap();
+        assert_eq!(event.market, MARKET);
+        assert_eq!(event.owner, TAKER);
+        assert_eq!(event.side, OpenBookV2Side::Bid);
+        assert_eq!(event.price_lots, 1_520);
+        assert_eq!(event.max_base_lots, 50);
+        assert_eq!(event.max_quote_lots_including_fees, 80_000);
+        assert_eq!(event.order_type, 3);
+        assert_eq!(event.fills.len(), 1);
+        assert_eq!(event.fills[0].seq_num, 7);
+        assert_eq!(event.pending_fills, [8]);
+        assert_eq!(event.base_amount, Some(25_000_000));
+        assert_eq!(event.quote_amount, Some(37_838_000));
+    }
+}
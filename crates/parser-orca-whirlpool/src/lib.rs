@@ -0,0 +1,349 @@
+use instruction_parser::InstructionParser;
+use types::{
+    DecodeError, DecodeErrorKind, DecodeReport, DecodedEvent, DecodedInstruction,
+    DecodedOrcaWhirlpoolCreatePoolEvent, DecodedOrcaWhirlpoolEvent,
+    DecodedOrcaWhirlpoolLiquidityEvent, DecodedOrcaWhirlpoolSwapEvent,
+    DecodedOrcaWhirlpoolTwoHopSwapEvent, StructuredInstruction, TokenProgramTransfer,
+};
+use utils::{
+    find_token_transfer, get_account, get_instruction_token_balance, get_token_transfers,
+    read_bool, read_u16, read_u128,
+};
+use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
+
+#[derive(Clone, Debug, Default)]
+pub struct OrcaWhirlpoolInstructionParser {}
+
+impl InstructionParser for OrcaWhirlpoolInstructionParser {
+    fn get_program_id(&self) -> &str {
+        Self::PROGRAM_ID
+    }
+
+    fn decode_instructions(
+        &self,
+        instructions: Vec<StructuredInstruction>,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> DecodeReport {
+        let mut report = DecodeReport::default();
+        for instruction in instructions.iter() {
+            match self.decode_instruction(instruction, account_keys, transaction) {
+                Ok(Some(decoded_instruction)) => report.events.push(DecodedInstruction::new(
+                    Self::PROGRAM_ID,
+                    instruction,
+                    DecodedEvent::OrcaWhirlpool(decoded_instruction),
+                )),
+                Ok(None) => {}
+                Err(kind) => report
+                    .errors
+                    .push(DecodeError::new(Self::PROGRAM_ID, instruction, 8, kind)),
+            }
+        }
+        report
+    }
+}
+
+/// Where one hop of a swap finds its pool and vaults in the instruction accounts.
+struct SwapLegAccounts {
+    pool: usize,
+    vault_in: usize,
+    vault_out: usize,
+    a_to_b: bool,
+}
+
+impl OrcaWhirlpoolInstructionParser {
+    const PROGRAM_ID: &'static str = "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc";
+    const SWAP_DISCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+    const SWAP_V2_DISCRIMINATOR: [u8; 8] = [43, 4, 237, 11, 26, 201, 30, 98];
+    const TWO_HOP_SWAP_DISCRIMINATOR: [u8; 8] = [195, 96, 237, 108, 68, 162, 219, 230];
+    const TWO_HOP_SWAP_V2_DISCRIMINATOR: [u8; 8] = [186, 143, 209, 29, 254, 2, 194, 117];
+    const INCREASE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [46, 156, 243, 118, 13, 205, 251, 178];
+    const INCREASE_LIQUIDITY_V2_DISCRIMINATOR: [u8; 8] = [133, 29, 89, 223, 69, 238, 176, 10];
+    const DECREASE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [160, 38, 208, 111, 104, 91, 44, 1];
+    const DECREASE_LIQUIDITY_V2_DISCRIMINATOR: [u8; 8] = [58, 127, 188, 62, 79, 82, 196, 96];
+    const INITIALIZE_POOL_DISCRIMINATOR: [u8; 8] = [95, 180, 10, 172, 84, 174, 232, 40];
+    const INITIALIZE_POOL_V2_DISCRIMINATOR: [u8; 8] = [207, 45, 87, 242, 27, 63, 204, 67];
+
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn decode_instruction(
+        &self,
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> Result<Option<DecodedOrcaWhirlpoolEvent>, DecodeErrorKind> {
+        let discriminator = match instruction.data.get(..8) {
+            Some(discriminator) => discriminator,
+            None => return Ok(None),
+        };
+        if discriminator == Self::SWAP_DISCRIMINATOR {
+            return Ok(Some(DecodedOrcaWhirlpoolEvent::Swap(Self::decode_swap_event(
+                instruction,
+                account_keys,
+                transaction,
+                false,
+            )?)));
+        } else if discriminator == Self::SWAP_V2_DISCRIMINATOR {
+            return Ok(Some(DecodedOrcaWhirlpoolEvent::Swap(Self::decode_swap_event(
+                instruction,
+                account_keys,
+                transaction,
+                true,
+            )?)));
+        } else if discriminator == Self::TWO_HOP_SWAP_DISCRIMINATOR {
+            return Ok(Some(DecodedOrcaWhirlpoolEvent::TwoHopSwap(
+                Self::decode_two_hop_swap_event(instruction, account_keys, transaction, false)?,
+            )));
+        } else if discriminator == Self::TWO_HOP_SWAP_V2_DISCRIMINATOR {
+            return Ok(Some(DecodedOrcaWhirlpoolEvent::TwoHopSwap(
+                Self::decode_two_hop_swap_event(instruction, account_keys, transaction, true)?,
+            )));
+        } else if discriminator == Self::INCREASE_LIQUIDITY_DISCRIMINATOR {
+            return Ok(Some(DecodedOrcaWhirlpoolEvent::IncreaseLiquidity(
+                Self::decode_liquidity_event(instruction, account_keys, transaction, true, false)?,
+            )));
+        } else if discriminator == Self::INCREASE_LIQUIDITY_V2_DISCRIMINATOR {
+            return Ok(Some(DecodedOrcaWhirlpoolEvent::IncreaseLiquidity(
+                Self::decode_liquidity_event(instruction, account_keys, transaction, true, true)?,
+            )));
+        } else if discriminator == Self::DECREASE_LIQUIDITY_DISCRIMINATOR {
+            return Ok(Some(DecodedOrcaWhirlpoolEvent::DecreaseLiquidity(
+                Self::decode_liquidity_event(instruction, account_keys, transaction, false, false)?,
+            )));
+        } else if discriminator == Self::DECREASE_LIQUIDITY_V2_DISCRIMINATOR {
+            return Ok(Some(DecodedOrcaWhirlpoolEvent::DecreaseLiquidity(
+                Self::decode_liquidity_event(instruction, account_keys, transaction, false, true)?,
+            )));
+        } else if discriminator == Self::INITIALIZE_POOL_DISCRIMINATOR {
+            return Ok(Some(DecodedOrcaWhirlpoolEvent::CreatePool(
+                Self::decode_pool_creation_event(instruction, account_keys, false)?,
+            )));
+        } else if discriminator == Self::INITIALIZE_POOL_V2_DISCRIMINATOR {
+            return Ok(Some(DecodedOrcaWhirlpoolEvent::CreatePool(
+                Self::decode_pool_creation_event(instruction, account_keys, true)?,
+            )));
+        }
+        Ok(None)
+    }
+
+    fn decode_swap_leg(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+        transfers: &[TokenProgramTransfer],
+        leg: SwapLegAccounts,
+        user: &str,
+        amount_specified_is_input: bool,
+    ) -> Result<DecodedOrcaWhirlpoolSwapEvent, DecodeErrorKind> {
+        let pool = get_account(instruction, account_keys, leg.pool)?;
+        let vault_in = get_account(instruction, account_keys, leg.vault_in)?;
+        let vault_out = get_account(instruction, account_keys, leg.vault_out)?;
+        let (mint_in, mint_in_reserve, in_decimals) =
+            get_instruction_token_balance(instruction, transaction, leg.vault_in)?;
+        let (mint_out, mint_out_reserve, out_decimals) =
+            get_instruction_token_balance(instruction, transaction, leg.vault_out)?;
+        let amount_in = find_token_transfer(transfers, &vault_in, true)?.amount;
+        let amount_out = find_token_transfer(transfers, &vault_out, false)?.amount;
+
+        Ok(DecodedOrcaWhirlpoolSwapEvent {
+            pool,
+            user: user.to_string(),
+            mint_in,
+            mint_out,
+            in_decimals,
+            out_decimals,
+            mint_in_reserve,
+            mint_out_reserve,
+            amount_in,
+            amount_out,
+            a_to_b: leg.a_to_b,
+            amount_specified_is_input,
+        })
+    }
+
+    /// `swap` accounts: [token_program, token_authority, whirlpool, token_owner_account_a,
+    /// token_vault_a, token_owner_account_b, token_vault_b, ...]
+    ///
+    /// `swap_v2` accounts: [token_program_a, token_program_b, memo_program, token_authority,
+    /// whirlpool, token_mint_a, token_mint_b, token_owner_account_a, token_vault_a,
+    /// token_owner_account_b, token_vault_b, ...]
+    pub fn decode_swap_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+        v2: bool,
+    ) -> Result<DecodedOrcaWhirlpoolSwapEvent, DecodeErrorKind> {
+        // args: amount, other_amount_threshold, sqrt_price_limit, amount_specified_is_input, a_to_b
+        let amount_specified_is_input = read_bool(&instruction.data, 40)?;
+        let a_to_b = read_bool(&instruction.data, 41)?;
+        let (user_position, pool, vault_a, vault_b) = if v2 { (3, 4, 8, 10) } else { (1, 2, 4, 6) };
+        let (vault_in, vault_out) = if a_to_b { (vault_a, vault_b) } else { (vault_b, vault_a) };
+        let user = get_account(instruction, account_keys, user_position)?;
+        let transfers = get_token_transfers(instruction, account_keys)?;
+
+        Self::decode_swap_leg(
+            instruction,
+            account_keys,
+            transaction,
+            &transfers,
+            SwapLegAccounts {
+                pool,
+                vault_in,
+                vault_out,
+                a_to_b,
+            },
+            &user,
+            amount_specified_is_input,
+        )
+    }
+
+    /// `two_hop_swap` accounts: [token_program, token_authority, whirlpool_one, whirlpool_two,
+    /// token_owner_account_one_a, token_vault_one_a, token_owner_account_one_b,
+    /// token_vault_one_b, token_owner_account_two_a, token_vault_two_a,
+    /// token_owner_account_two_b, token_vault_two_b, ...]
+    ///
+    /// `two_hop_swap_v2` accounts: [whirlpool_one, whirlpool_two, token_mint_input,
+    /// token_mint_intermediate, token_mint_output, token_program_input,
+    /// token_program_intermediate, token_program_output, token_owner_account_input,
+    /// token_vault_one_input, token_vault_one_intermediate, token_vault_two_intermediate,
+    /// token_vault_two_output, token_owner_account_output, token_authority, ...]
+    pub fn decode_two_hop_swap_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+        v2: bool,
+    ) -> Result<DecodedOrcaWhirlpoolTwoHopSwapEvent, DecodeErrorKind> {
+        // args: amount, other_amount_threshold, amount_specified_is_input, a_to_b_one, a_to_b_two, ...
+        let amount_specified_is_input = read_bool(&instruction.data, 24)?;
+        let a_to_b_one = read_bool(&instruction.data, 25)?;
+        let a_to_b_two = read_bool(&instruction.data, 26)?;
+        let (user_position, first, second) = if v2 {
+            (
+                14,
+                SwapLegAccounts { pool: 0, vault_in: 9, vault_out: 10, a_to_b: a_to_b_one },
+                SwapLegAccounts { pool: 1, vault_in: 11, vault_out: 12, a_to_b: a_to_b_two },
+            )
+        } else {
+            let (one_in, one_out) = if a_to_b_one { (5, 7) } else { (7, 5) };
+            let (two_in, two_out) = if a_to_b_two { (9, 11) } else { (11, 9) };
+            (
+                1,
+                SwapLegAccounts { pool: 2, vault_in: one_in, vault_out: one_out, a_to_b: a_to_b_one },
+                SwapLegAccounts { pool: 3, vault_in: two_in, vault_out: two_out, a_to_b: a_to_b_two },
+            )
+        };
+        let user = get_account(instruction, account_keys, user_position)?;
+        let transfers = get_token_transfers(instruction, account_keys)?;
+
+        Ok(DecodedOrcaWhirlpoolTwoHopSwapEvent {
+            first: Self::decode_swap_leg(
+                instruction,
+                account_keys,
+                transaction,
+                &transfers,
+                first,
+                &user,
+                amount_specified_is_input,
+            )?,
+            second: Self::decode_swap_leg(
+                instruction,
+                account_keys,
+                transaction,
+                &transfers,
+                second,
+                &user,
+                amount_specified_is_input,
+            )?,
+        })
+    }
+
+    /// Increase and decrease liquidity share their accounts:
+    /// [whirlpool, token_program, position_authority, position, position_token_account,
+    /// token_owner_account_a, token_owner_account_b, token_vault_a, token_vault_b, ...]
+    ///
+    /// and so do their v2: [whirlpool, token_program_a, token_program_b, memo_program,
+    /// position_authority, position, position_token_account, token_mint_a, token_mint_b,
+    /// token_owner_account_a, token_owner_account_b, token_vault_a, token_vault_b, ...]
+    pub fn decode_liquidity_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+        increase: bool,
+        v2: bool,
+    ) -> Result<DecodedOrcaWhirlpoolLiquidityEvent, DecodeErrorKind> {
+        let (user_position, position_position, vault_a_position, vault_b_position) =
+            if v2 { (4, 5, 11, 12) } else { (2, 3, 7, 8) };
+        let pool = get_account(instruction, account_keys, 0)?;
+        let user = get_account(instruction, account_keys, user_position)?;
+        let position = get_account(instruction, account_keys, position_position)?;
+        let vault_a = get_account(instruction, account_keys, vault_a_position)?;
+        let vault_b = get_account(instruction, account_keys, vault_b_position)?;
+        let (token_a_mint, _, _) =
+            get_instruction_token_balance(instruction, transaction, vault_a_position)?;
+        let (token_b_mint, _, _) =
+            get_instruction_token_balance(instruction, transaction, vault_b_position)?;
+        // args: liquidity_amount, then the token maximums or minimums
+        let liquidity = read_u128(&instruction.data, 8)?;
+
+        // the program skips transfers of zero amounts
+        let transfers = get_token_transfers(instruction, account_keys)?;
+        let amount_a = find_token_transfer(&transfers, &vault_a, increase)
+            .map(|transfer| transfer.amount)
+            .unwrap_or(0);
+        let amount_b = find_token_transfer(&transfers, &vault_b, increase)
+            .map(|transfer| transfer.amount)
+            .unwrap_or(0);
+
+        Ok(DecodedOrcaWhirlpoolLiquidityEvent {
+            pool,
+            user,
+            position,
+            token_a_mint,
+            token_b_mint,
+            liquidity,
+            amount_a,
+            amount_b,
+        })
+    }
+
+    /// `initialize_pool` accounts: [whirlpools_config, token_mint_a, token_mint_b, funder,
+    /// whirlpool, token_vault_a, token_vault_b, fee_tier, ...]
+    ///
+    /// `initialize_pool_v2` accounts: [whirlpools_config, token_mint_a, token_mint_b,
+    /// token_badge_a, token_badge_b, funder, whirlpool, token_vault_a, token_vault_b, fee_tier,
+    /// ...]
+    pub fn decode_pool_creation_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        v2: bool,
+    ) -> Result<DecodedOrcaWhirlpoolCreatePoolEvent, DecodeErrorKind> {
+        let (creator_position, pool_position, vault_a_position, vault_b_position) =
+            if v2 { (5, 6, 7, 8) } else { (3, 4, 5, 6) };
+        let config = get_account(instruction, account_keys, 0)?;
+        let token_a_mint = get_account(instruction, account_keys, 1)?;
+        let token_b_mint = get_account(instruction, account_keys, 2)?;
+        let creator = get_account(instruction, account_keys, creator_position)?;
+        let pool = get_account(instruction, account_keys, pool_position)?;
+        let token_a_vault = get_account(instruction, account_keys, vault_a_position)?;
+        let token_b_vault = get_account(instruction, account_keys, vault_b_position)?;
+
+        // args: bumps (whirlpool_bump), tick_spacing, initial_sqrt_price; v2 has no bumps
+        let args_offset = if v2 { 8 } else { 9 };
+        let tick_spacing = read_u16(&instruction.data, args_offset)?;
+        let initial_sqrt_price = read_u128(&instruction.data, args_offset + 2)?;
+
+        Ok(DecodedOrcaWhirlpoolCreatePoolEvent {
+            pool,
+            creator,
+            config,
+            token_a_mint,
+            token_b_mint,
+            token_a_vault,
+            token_b_vault,
+            tick_spacing,
+            initial_sqrt_price,
+        })
+    }
+}
@@ -0,0 +1,593 @@
+use instruction_parser::InstructionParser;
+use types::{
+    DecodeError, DecodeErrorKind, DecodeReport, DecodedEvent, DecodedInstruction,
+    DecodedPhoenixEvent, DecodedPhoenixFillEvent, DecodedPhoenixFillSummaryEvent,
+    DecodedPhoenixOrderEvent, PhoenixSide, StructuredInstruction,
+};
+use utils::{
+    find_token_transfer, get_account, get_instruction_token_balance, get_token_transfers,
+    read_pubkey, read_u8, read_u64, read_u128,
+};
+use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
+
+#[derive(Clone, Debug, Default)]
+pub struct PhoenixInstructionParser {}
+
+impl InstructionParser for PhoenixInstructionParser {
+    fn get_program_id(&self) -> &str {
+        Self::PROGRAM_ID
+    }
+
+    fn decode_instructions(
+        &self,
+        instructions: Vec<StructuredInstruction>,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> DecodeReport {
+        let mut report = DecodeReport::default();
+        for instruction in instructions.iter() {
+            match self.decode_instruction(instruction, account_keys, transaction) {
+                Ok(decoded_instructions) => {
+                    for decoded_instruction in decoded_instructions {
+                        report.events.push(DecodedInstruction::new(
+                            Self::PROGRAM_ID,
+                            instruction,
+                            DecodedEvent::Phoenix(decoded_instruction),
+                        ));
+                    }
+                }
+                Err(kind) => report
+                    .errors
+                    .push(DecodeError::new(Self::PROGRAM_ID, instruction, 1, kind)),
+            }
+        }
+        report
+    }
+}
+
+impl PhoenixInstructionParser {
+    const PROGRAM_ID: &'static str = "PhoeNiXZ8ByJGLkxNfZRnkUfjvmuYqLR89jjFHGqdXY";
+    const SWAP_TAG: u8 = 0;
+    const LOG_TAG: u8 = 15;
+    const HEADER_EVENT_TAG: u8 = 1;
+    const FILL_EVENT_TAG: u8 = 2;
+    const PLACE_EVENT_TAG: u8 = 3;
+    const REDUCE_EVENT_TAG: u8 = 4;
+    const EVICT_EVENT_TAG: u8 = 5;
+    const FILL_SUMMARY_EVENT_TAG: u8 = 6;
+    const FEE_EVENT_TAG: u8 = 7;
+    const TIME_IN_FORCE_EVENT_TAG: u8 = 8;
+    const EXPIRED_ORDER_EVENT_TAG: u8 = 9;
+
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Phoenix is not an Anchor program: instructions start with a one byte tag, and the
+    /// market events are logged in batches through self-CPIs of the `Log` instruction. The
+    /// batches are decoded together with the instruction that emitted them, so one
+    /// instruction can yield several events.
+    pub fn decode_instruction(
+        &self,
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> Result<Vec<DecodedPhoenixEvent>, DecodeErrorKind> {
+        let tag = match instruction.data.first() {
+            Some(tag) => *tag,
+            None => return Ok(Vec::new()),
+        };
+        if tag == Self::LOG_TAG {
+            return Ok(Vec::new());
+        }
+        let mut events = Vec::new();
+        for inner in instruction.inner_instructions.iter() {
+            let is_log = inner.data.first() == Some(&Self::LOG_TAG)
+                && account_keys.get(inner.program_id_index as usize).map(String::as_str)
+                    == Some(Self::PROGRAM_ID);
+            if is_log {
+                events.append(&mut Self::decode_log(&inner.data)?);
+            }
+        }
+        if tag == Self::SWAP_TAG {
+            Self::add_swap_amounts(instruction, account_keys, transaction, &mut events)?;
+        }
+        Ok(events)
+    }
+
+    /// `Log` data: the instruction tag followed by `PhoenixMarketEvent`s, each a one byte
+    /// tag and its fields. Every batch starts with a `Header` naming the market and signer.
+    pub fn decode_log(data: &[u8]) -> Result<Vec<DecodedPhoenixEvent>, DecodeErrorKind> {
+        let mut events = Vec::new();
+        let mut header: Option<(String, String)> = None;
+        let mut maker_side: Option<PhoenixSide> = None;
+        let mut offset: usize = 1;
+        while offset < data.len() {
+            let tag = read_u8(data, offset)?;
+            offset += 1;
+            if tag == Self::HEADER_EVENT_TAG {
+                // instruction, sequence_number, timestamp, slot
+                let market = read_pubkey(data, offset + 25)?;
+                let signer = read_pubkey(data, offset + 57)?;
+                header = Some((market, signer));
+                // market, signer, total_events
+                offset += 91;
+                continue;
+            }
+            let (market, signer) = header.as_ref().ok_or(DecodeErrorKind::MissingEventLog)?;
+            // every event starts with its u16 index in the batch
+            match tag {
+                Self::FILL_EVENT_TAG => {
+                    let fill = Self::decode_fill_log(data, offset + 2, market, signer)?;
+                    maker_side = Some(fill.maker_side);
+                    events.push(DecodedPhoenixEvent::Fill(fill));
+                    offset += 66;
+                }
+                Self::PLACE_EVENT_TAG => {
+                    events.push(DecodedPhoenixEvent::Place(Self::decode_place_log(
+                        data,
+                        offset + 2,
+                        market,
+                        signer,
+                    )?));
+                    offset += 42;
+                }
+                Self::REDUCE_EVENT_TAG => {
+                    events.push(DecodedPhoenixEvent::Reduce(Self::decode_reduce_log(
+                        data,
+                        offset + 2,
+                        market,
+                        signer,
+                    )?));
+                    offset += 34;
+                }
+                Self::EVICT_EVENT_TAG => {
+                    events.push(DecodedPhoenixEvent::Evict(Self::decode_evict_log(
+                        data,
+                        offset + 2,
+                        market,
+                    )?));
+                    offset += 58;
+                }
+                Self::FILL_SUMMARY_EVENT_TAG => {
+                    // the taker crossed the side of the makers it just filled
+                    let taker_side = maker_side.take().map(|side| match side {
+                        PhoenixSide::Bid => PhoenixSide::Ask,
+                        PhoenixSide::Ask => PhoenixSide::Bid,
+                    });
+                    events.push(DecodedPhoenixEvent::FillSummary(Self::decode_fill_summary_log(
+                        data,
+                        offset + 2,
+                        market,
+                        signer,
+                        taker_side,
+                    )?));
+                    offset += 42;
+                }
+                Self::FEE_EVENT_TAG => offset += 10,
+                Self::TIME_IN_FORCE_EVENT_TAG => offset += 26,
+                Self::EXPIRED_ORDER_EVENT_TAG => offset += 58,
+                _ => {
+                    return Err(DecodeErrorKind::UnknownEventTag {
+                        offset: offset - 1,
+                        tag,
+                    });
+                }
+            }
+        }
+        Ok(events)
+    }
+
+    /// `FillEvent`: maker_id, order_sequence_number, price_in_ticks, base_lots_filled,
+    /// base_lots_remaining
+    fn decode_fill_log(
+        data: &[u8],
+        mut offset: usize,
+        market: &str,
+        taker: &str,
+    ) -> Result<DecodedPhoenixFillEvent, DecodeErrorKind> {
+        let maker = read_pubkey(data, offset)?;
+        offset += 32;
+        let order_sequence_number = read_u64(data, offset)?;
+        offset += 8;
+        let price_in_ticks = read_u64(data, offset)?;
+        offset += 8;
+        let base_lots_filled = read_u64(data, offset)?;
+        offset += 8;
+        let base_lots_remaining = read_u64(data, offset)?;
+        Ok(DecodedPhoenixFillEvent {
+            market: market.to_string(),
+            maker,
+            taker: taker.to_string(),
+            maker_side: Self::order_side(order_sequence_number),
+            order_sequence_number,
+            price_in_ticks,
+            base_lots_filled,
+            base_lots_remaining,
+            base_amount: None,
+            quote_amount: None,
+            price: None,
+        })
+    }
+
+    /// `PlaceEvent`: order_sequence_number, client_order_id, price_in_ticks, base_lots_placed
+    fn decode_place_log(
+        data: &[u8],
+        mut offset: usize,
+        market: &str,
+        trader: &str,
+    ) -> Result<DecodedPhoenixOrderEvent, DecodeErrorKind> {
+        let order_sequence_number = read_u64(data, offset)?;
+        offset += 8;
+        let client_order_id = read_u128(data, offset)?;
+        offset += 16;
+        let price_in_ticks = read_u64(data, offset)?;
+        offset += 8;
+        let base_lots = read_u64(data, offset)?;
+        Ok(DecodedPhoenixOrderEvent {
+            market: market.to_string(),
+            trader: trader.to_string(),
+            side: Self::order_side(order_sequence_number),
+            order_sequence_number,
+            price_in_ticks,
+            base_lots,
+            client_order_id: Some(client_order_id),
+        })
+    }
+
+    /// `ReduceEvent`: order_sequence_number, price_in_ticks, base_lots_removed,
+    /// base_lots_remaining
+    fn decode_reduce_log(
+        data: &[u8],
+        mut offset: usize,
+        market: &str,
+        trader: &str,
+    ) -> Result<DecodedPhoenixOrderEvent, DecodeErrorKind> {
+        let order_sequence_number = read_u64(data, offset)?;
+        offset += 8;
+        let price_in_ticks = read_u64(data, offset)?;
+        offset += 8;
+        let base_lots = read_u64(data, offset)?;
+        Ok(DecodedPhoenixOrderEvent {
+            market: market.to_string(),
+            trader: trader.to_string(),
+            side: Self::order_side(order_sequence_number),
+            order_sequence_number,
+            price_in_ticks,
+            base_lots,
+            client_order_id: None,
+        })
+    }
+
+    /// `EvictEvent`: maker_id, order_sequence_number, price_in_ticks, base_lots_evicted
+    fn decode_evict_log(
+        data: &[u8],
+        mut offset: usize,
+        market: &str,
+    ) -> Result<DecodedPhoenixOrderEvent, DecodeErrorKind> {
+        let trader = read_pubkey(data, offset)?;
+        offset += 32;
+        let order_sequence_number = read_u64(data, offset)?;
+        offset += 8;
+        let price_in_ticks = read_u64(data, offset)?;
+        offset += 8;
+        let base_lots = read_u64(data, offset)?;
+        Ok(DecodedPhoenixOrderEvent {
+            market: market.to_string(),
+            trader,
+            side: Self::order_side(order_sequence_number),
+            order_sequence_number,
+            price_in_ticks,
+            base_lots,
+            client_order_id: None,
+        })
+    }
+
+    /// `FillSummaryEvent`: client_order_id, total_base_lots_filled, total_quote_lots_filled,
+    /// total_fee_in_quote_lots
+    fn decode_fill_summary_log(
+        data: &[u8],
+        mut offset: usize,
+        market: &str,
+        taker: &str,
+        taker_side: Option<PhoenixSide>,
+    ) -> Result<DecodedPhoenixFillSummaryEvent, DecodeErrorKind> {
+        let client_order_id = read_u128(data, offset)?;
+        offset += 16;
+        let total_base_lots_filled = read_u64(data, offset)?;
+        offset += 8;
+        let total_quote_lots_filled = read_u64(data, offset)?;
+        offset += 8;
+        let total_fee_in_quote_lots = read_u64(data, offset)?;
+        Ok(DecodedPhoenixFillSummaryEvent {
+            market: market.to_string(),
+            taker: taker.to_string(),
+            taker_side,
+            client_order_id,
+            total_base_lots_filled,
+            total_quote_lots_filled,
+            total_fee_in_quote_lots,
+            base_mint: None,
+            quote_mint: None,
+            base_amount: None,
+            quote_amount: None,
+        })
+    }
+
+    /// Bids are keyed by the bitwise negation of their sequence number, so only they have the
+    /// top bit set.
+    fn order_side(order_sequence_number: u64) -> PhoenixSide {
+        if order_sequence_number >> 63 == 1 {
+            PhoenixSide::Bid
+        } else {
+            PhoenixSide::Ask
+        }
+    }
+
+    /// Accounts: [phoenix_program, log_authority, market, trader, base_account, quote_account,
+    /// base_vault, quote_vault, token_program]
+    ///
+    /// Lot and tick sizes live in the market account, so they are recovered from the vault
+    /// transfers: the base transfer is a whole number of base lots, and the quote transfer is
+    /// the filled quote lots plus (buy) or minus (sell) the fee. Each fill gets the share of
+    /// the quote amount matching its price in ticks times its base lots.
+    fn add_swap_amounts(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+        events: &mut [DecodedPhoenixEvent],
+    ) -> Result<(), DecodeErrorKind> {
+        let summary = events.iter().find_map(|event| match event {
+            DecodedPhoenixEvent::FillSummary(summary) => Some(summary),
+            _ => None,
+        });
+        let (taker_side, total_base_lots, total_quote_lots, fee_lots) = match summary {
+            Some(summary) if summary.total_base_lots_filled > 0 => match summary.taker_side {
+                Some(taker_side) => (
+                    taker_side,
+                    summary.total_base_lots_filled,
+                    summary.total_quote_lots_filled,
+                    summary.total_fee_in_quote_lots,
+                ),
+                None => return Ok(()),
+            },
+            _ => return Ok(()),
+        };
+        let buy = taker_side == PhoenixSide::Bid;
+        let quote_lots_transferred = if buy {
+            total_quote_lots.checked_add(fee_lots).ok_or(DecodeErrorKind::AmountOverflow)?
+        } else {
+            total_quote_lots.saturating_sub(fee_lots)
+        };
+        if quote_lots_transferred == 0 {
+            return Ok(());
+        }
+
+        let base_vault = get_account(instruction, account_keys, 6)?;
+        let quote_vault = get_account(instruction, account_keys, 7)?;
+        let (base_mint, _, base_decimals) = get_instruction_token_balance(instruction, transaction, 6)?;
+        let (quote_mint, _, quote_decimals) =
+            get_instruction_token_balance(instruction, transaction, 7)?;
+        let transfers = get_token_transfers(instruction, account_keys)?;
+        let base_amount = find_token_transfer(&transfers, &base_vault, !buy)?.amount;
+        let quote_amount = find_token_transfer(&transfers, &quote_vault, buy)?.amount;
+
+        let base_lot_size = base_amount / total_base_lots;
+        let quote_lot_size = quote_amount / quote_lots_transferred;
+        let filled_quote_amount = total_quote_lots as u128 * quote_lot_size as u128;
+        let total_weight: u128 = events
+            .iter()
+            .filter_map(|event| match event {
+                DecodedPhoenixEvent::Fill(fill) => {
+                    Some(fill.price_in_ticks as u128 * fill.base_lots_filled as u128)
+                }
+                _ => None,
+            })
+            .sum();
+
+        for event in events.iter_mut() {
+            match event {
+                DecodedPhoenixEvent::Fill(fill) if total_weight > 0 => {
+                    let weight = fill.price_in_ticks as u128 * fill.base_lots_filled as u128;
+                    let fill_base_amount = fill
+                        .base_lots_filled
+                        .checked_mul(base_lot_size)
+                        .ok_or(DecodeErrorKind::AmountOverflow)?;
+                    let fill_quote_amount = filled_quote_amount
+                        .checked_mul(weight)
+                        .and_then(|amount| u64::try_from(amount / total_weight).ok())
+                        .ok_or(DecodeErrorKind::AmountOverflow)?;
+                    fill.base_amount = Some(fill_base_amount);
+                    fill.quote_amount = Some(fill_quote_amount);
+                    fill.price = Some(
+                        (fill_quote_amount as f64 / 10f64.powi(quote_decimals as i32))
+                            / (fill_base_amount as f64 / 10f64.powi(base_decimals as i32)),
+                    );
+                }
+                DecodedPhoenixEvent::FillSummary(summary) => {
+                    summary.base_mint = Some(base_mint.clone());
+                    summary.quote_mint = Some(quote_mint.clone());
+                    summary.base_amount = Some(base_amount);
+                    summary.quote_amount = Some(quote_amount);
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::testing::pubkey;
+
+    const MARKET: &str = "4DoNfFBfF7UokCC2FQzriy7yHK6DY6NVdYpuekQ5pRgg";
+    const TAKER: &str = "7u7cD7NxcZEuzRCBaYo8uVpotRdqZwez47vvuwzCov43";
+    const MAKER: &str = "5Y3ZhsLXDvk4xJSMKzPCWHs2xTA3KtVVr6Q8mG5jJ4Kn";
+
+    /// The `Log` instruction tag and the `Header` of a batch of `total_events`.
+    fn batch(total_events: u16) -> Vec<u8> {
+        let mut data = vec![
+            PhoenixInstructionParser::LOG_TAG,
+            PhoenixInstructionParser::HEADER_EVENT_TAG,
+        ];
+        data.push(0); // instruction
+        data.extend(81_234_567u64.to_le_bytes()); // sequence_number
+        data.extend(1_750_012_345i64.to_le_bytes()); // timestamp
+        data.extend(345_678_901u64.to_le_bytes()); // slot
+        data.extend(pubkey(MARKET));
+        data.extend(pubkey(TAKER));
+        data.extend(total_events.to_le_bytes());
+        data
+    }
+
+    fn push_event(data: &mut Vec<u8>, tag: u8, index: u16) {
+        data.push(tag);
+        data.extend(index.to_le_bytes());
+    }
+
+    #[test]
+    fn decodes_a_swap_batch() {
+        let mut data = batch(4);
+        push_event(&mut data, PhoenixInstructionParser::FILL_EVENT_TAG, 0);
+        data.extend(pubkey(MAKER));
+        data.extend((!1_234_567u64).to_le_bytes()); // a bid
+        for value in [151_230u64, 2_000, 500] {
+            // price_in_ticks, base_lots_filled, base_lots_remaining
+            data.extend(value.to_le_bytes());
+        }
+        push_event(&mut data, PhoenixInstructionParser::FILL_SUMMARY_EVENT_TAG, 1);
+        data.extend(0u128.to_le_bytes()); // client_order_id
+        for value in [2_000u64, 302_460_000, 30_246] {
+            // total_base_lots_filled, total_quote_lots_filled, total_fee_in_quote_lots
+            data.extend(value.to_le_bytes());
+        }
+        push_event(&mut data, PhoenixInstructionParser::FEE_EVENT_TAG, 2);
+        data.extend(30_246u64.to_le_bytes());
+
+        let events = PhoenixInstructionParser::decode_log(&data).unwrap();
+        assert_eq!(events.len(), 2);
+        let DecodedPhoenixEvent::Fill(fill) = &events[0] else {
+            panic!("expected a fill, got {:?}", events[0]);
+        };
+        assert_eq!(fill.market, MARKET);
+        assert_eq!(fill.maker, MAKER);
+        assert_eq!(fill.taker, TAKER);
+        assert_eq!(fill.maker_side, PhoenixSide::Bid);
+        assert_eq!(fill.price_in_ticks, 151_230);
+        assert_eq!(fill.base_lots_filled, 2_000);
+        assert_eq!(fill.base_lots_remaining, 500);
+        let DecodedPhoenixEvent::FillSummary(summary) = &events[1] else {
+            panic!("expected a fill summary, got {:?}", events[1]);
+        };
+        assert_eq!(summary.taker_side, Some(PhoenixSide::Ask));
+        assert_eq!(summary.total_base_lots_filled, 2_000);
+        assert_eq!(summary.total_quote_lots_filled, 302_460_000);
+        assert_eq!(summary.total_fee_in_quote_lots, 30_246);
+    }
+
+    #[test]
+    fn decodes_an_order_batch() {
+        let mut data = batch(5);
+        push_event(&mut data, PhoenixInstructionParser::EXPIRED_ORDER_EVENT_TAG, 0);
+        data.extend(pubkey(MAKER));
+        for value in [77u64, 152_000, 100] {
+            // order_sequence_number, price_in_ticks, base_lots_removed
+            data.extend(value.to_le_bytes());
+        }
+        push_event(&mut data, PhoenixInstructionParser::EVICT_EVENT_TAG, 1);
+        data.extend(pubkey(MAKER));
+        for value in [78u64, 152_100, 40] {
+            // order_sequence_number, price_in_ticks, base_lots_evicted
+            data.extend(value.to_le_bytes());
+        }
+        push_event(&mut data, PhoenixInstructionParser::REDUCE_EVENT_TAG, 2);
+        for value in [79u64, 152_200, 10, 90] {
+            // order_sequence_number, price_in_ticks, base_lots_removed, base_lots_remaining
+            data.extend(value.to_le_bytes());
+        }
+        push_event(&mut data, PhoenixInstructionParser::PLACE_EVENT_TAG, 3);
+        data.extend(80u64.to_le_bytes()); // order_sequence_number
+        data.extend(42u128.to_le_bytes()); // client_order_id
+        data.extend(152_300u64.to_le_bytes()); // price_in_ticks
+        data.extend(1_000u64.to_le_bytes()); // base_lots_placed
+        push_event(&mut data, PhoenixInstructionParser::TIME_IN_FORCE_EVENT_TAG, 4);
+        for value in [80u64, 345_679_000, 1_750_012_400] {
+            // order_sequence_number, last_valid_slot, last_valid_unix_timestamp_in_seconds
+            data.extend(value.to_le_bytes());
+        }
+
+        let events = PhoenixInstructionParser::decode_log(&data).unwrap();
+        assert_eq!(events.len(), 3);
+        let DecodedPhoenixEvent::Evict(evict) = &events[0] else {
+            panic!("expected an eviction, got {:?}", events[0]);
+        };
+        assert_eq!(evict.trader, MAKER);
+        assert_eq!(evict.order_sequence_number, 78);
+        assert_eq!(evict.base_lots, 40);
+        let DecodedPhoenixEvent::Reduce(reduce) = &events[1] else {
+            panic!("expected a reduction, got {:?}", events[1]);
+        };
+        assert_eq!(reduce.trader, TAKER);
+        assert_eq!(reduce.price_in_ticks, 152_200);
+        assert_eq!(reduce.base_lots, 10);
+        let DecodedPhoenixEvent::Place(place) = &events[2] else {
+            panic!("expected a place, got {:?}", events[2]);
+        };
+        assert_eq!(place.side, PhoenixSide::Ask);
+        assert_eq!(place.price_in_ticks, 152_300);
+        assert_eq!(place.base_lots, 1_000);
+        assert_eq!(place.client_order_id, Some(42));
+    }
+
+    #[test]
+    fn rejects_swap_lots_that_overflow_a_token_amount() {
+        let mut data = batch(2);
+        push_event(&mut data, PhoenixInstructionParser::FILL_EVENT_TAG, 0);
+        data.extend(pubkey(MAKER));
+        data.extend(1_234_567u64.to_le_bytes()); // an ask
+        for value in [151_230u64, 2_000, 0] {
+            // price_in_ticks, base_lots_filled, base_lots_remaining
+            data.extend(value.to_le_bytes());
+        }
+        push_event(&mut data, PhoenixInstructionParser::FILL_SUMMARY_EVENT_TAG, 1);
+        data.extend(0u128.to_le_bytes()); // client_order_id
+        for value in [2_000u64, u64::MAX, 1] {
+            // total_base_lots_filled, total_quote_lots_filled, total_fee_in_quote_lots
+            data.extend(value.to_le_bytes());
+        }
+
+        let mut events = PhoenixInstructionParser::decode_log(&data).unwrap();
+        let instruction = StructuredInstruction {
+            account_key_indexes: Vec::new(),
+            program_id_index: 0,
+            data: Vec::new(),
+            inner_instructions: Vec::new(),
+            stack_height: 1,
+            path: vec![0],
+            program_data_logs: Vec::new(),
+        };
+        assert_eq!(
+            PhoenixInstructionParser::add_swap_amounts(
+                &instruction,
+                &[],
+                &SubscribeUpdateTransaction::default(),
+                &mut events,
+            )
+            .unwrap_err(),
+            DecodeErrorKind::AmountOverflow
+        );
+    }
+
+    #[test]
+    fn rejects_an_unknown_event_tag() {
+        let mut data = batch(1);
+        let offset = data.len();
+        push_event(&mut data, 10, 0);
+        assert_eq!(
+            PhoenixInstructionParser::decode_log(&data).unwrap_err(),
+            DecodeErrorKind::UnknownEventTag { offset, tag: 10 }
+        );
+    }
+}
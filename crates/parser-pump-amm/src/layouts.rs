@@ -0,0 +1,426 @@
+//! Borsh layouts of the Pump AMM instruction args and events, decoded by `#[derive(Layout)]`.
+//! Event discriminators are the ones following the self-CPI tag.
+
+use layout_derive::Layout;
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [242, 35, 198, 137, 82, 225, 242, 182])]
+pub struct DepositArgs {
+    pub lp_token_amount_out: u64,
+    pub max_base_amount_in: u64,
+    pub max_quote_amount_in: u64,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [183, 18, 70, 156, 148, 109, 161, 34])]
+pub struct WithdrawArgs {
+    pub lp_token_amount_in: u64,
+    pub min_base_amount_out: u64,
+    pub min_quote_amount_out: u64,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [103, 244, 82, 31, 44, 245, 119, 119])]
+pub struct BuyEvent {
+    pub timestamp: i64,
+    pub base_amount_out: u64,
+    pub max_quote_amount_in: u64,
+    pub user_base_token_reserves: u64,
+    pub user_quote_token_reserves: u64,
+    pub pool_base_token_reserves: u64,
+    pub pool_quote_token_reserves: u64,
+    pub quote_amount_in: u64,
+    pub lp_fee_basis_points: u64,
+    pub lp_fee: u64,
+    pub protocol_fee_basis_points: u64,
+    pub protocol_fee: u64,
+    pub quote_amount_in_with_lp_fee: u64,
+    pub user_quote_amount_in: u64,
+    #[layout(pubkey)]
+    pub pool: String,
+    #[layout(pubkey)]
+    pub user: String,
+    #[layout(pubkey)]
+    pub user_base_token_account: String,
+    #[layout(pubkey)]
+    pub user_quote_token_account: String,
+    #[layout(pubkey)]
+    pub protocol_fee_recipient: String,
+    #[layout(pubkey)]
+    pub protocol_fee_recipient_token_account: String,
+    #[layout(pubkey)]
+    pub coin_creator: String,
+    pub coin_creator_fee_basis_points: u64,
+    pub coin_creator_fee: u64,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [62, 47, 55, 10, 165, 3, 220, 42])]
+pub struct SellEvent {
+    pub timestamp: i64,
+    pub base_amount_in: u64,
+    pub min_quote_amount_out: u64,
+    pub user_base_token_reserves: u64,
+    pub user_quote_token_reserves: u64,
+    pub pool_base_token_reserves: u64,
+    pub pool_quote_token_reserves: u64,
+    pub quote_amount_out: u64,
+    pub lp_fee_basis_points: u64,
+    pub lp_fee: u64,
+    pub protocol_fee_basis_points: u64,
+    pub protocol_fee: u64,
+    pub quote_amount_out_without_lp_fee: u64,
+    pub user_quote_amount_out: u64,
+    #[layout(pubkey)]
+    pub pool: String,
+    #[layout(pubkey)]
+    pub user: String,
+    #[layout(pubkey)]
+    pub user_base_token_account: String,
+    #[layout(pubkey)]
+    pub user_quote_token_account: String,
+    #[layout(pubkey)]
+    pub protocol_fee_recipient: String,
+    #[layout(pubkey)]
+    pub protocol_fee_recipient_token_account: String,
+    #[layout(pubkey)]
+    pub coin_creator: String,
+    pub coin_creator_fee_basis_points: u64,
+    pub coin_creator_fee: u64,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [177, 49, 12, 210, 160, 118, 167, 116])]
+pub struct CreatePoolEvent {
+    pub timestamp: i64,
+    pub index: u16,
+    #[layout(pubkey)]
+    pub creator: String,
+    #[layout(pubkey)]
+    pub base_mint: String,
+    #[layout(pubkey)]
+    pub quote_mint: String,
+    pub base_mint_decimals: u8,
+    pub quote_mint_decimals: u8,
+    pub base_amount_in: u64,
+    pub quote_amount_in: u64,
+    pub pool_base_amount: u64,
+    pub pool_quote_amount: u64,
+    pub minimum_liquidity: u64,
+    pub initial_liquidity: u64,
+    pub lp_token_amount_out: u64,
+    pub pool_bump: u8,
+    #[layout(pubkey)]
+    pub pool: String,
+    #[layout(pubkey)]
+    pub lp_mint: String,
+    #[layout(pubkey)]
+    pub user_base_token_account: String,
+    #[layout(pubkey)]
+    pub user_quote_token_account: String,
+    /// Only logged since coin creator fees were introduced.
+    #[layout(pubkey, trailing)]
+    pub coin_creator: Option<String>,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [120, 248, 61, 83, 31, 142, 107, 144])]
+pub struct DepositEvent {
+    pub timestamp: i64,
+    pub lp_token_amount_out: u64,
+    pub max_base_amount_in: u64,
+    pub max_quote_amount_in: u64,
+    pub user_base_token_reserves: u64,
+    pub user_quote_token_reserves: u64,
+    pub pool_base_token_reserves: u64,
+    pub pool_quote_token_reserves: u64,
+    pub base_amount_in: u64,
+    pub quote_amount_in: u64,
+    pub lp_mint_supply: u64,
+    #[layout(pubkey)]
+    pub pool: String,
+    #[layout(pubkey)]
+    pub user: String,
+    #[layout(pubkey)]
+    pub user_base_token_account: String,
+    #[layout(pubkey)]
+    pub user_quote_token_account: String,
+    #[layout(pubkey)]
+    pub user_pool_token_account: String,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [22, 9, 133, 26, 160, 44, 71, 192])]
+pub struct WithdrawEvent {
+    pub timestamp: i64,
+    pub lp_token_amount_in: u64,
+    pub min_base_amount_out: u64,
+    pub min_quote_amount_out: u64,
+    pub user_base_token_reserves: u64,
+    pub user_quote_token_reserves: u64,
+    pub pool_base_token_reserves: u64,
+    pub pool_quote_token_reserves: u64,
+    pub base_amount_out: u64,
+    pub quote_amount_out: u64,
+    pub lp_mint_supply: u64,
+    #[layout(pubkey)]
+    pub pool: String,
+    #[layout(pubkey)]
+    pub user: String,
+    #[layout(pubkey)]
+    pub user_base_token_account: String,
+    #[layout(pubkey)]
+    pub user_quote_token_account: String,
+    #[layout(pubkey)]
+    pub user_pool_token_account: String,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [232, 245, 194, 238, 234, 218, 58, 89])]
+pub struct CollectCoinCreatorFeeEvent {
+    pub timestamp: i64,
+    #[layout(pubkey)]
+    pub coin_creator: String,
+    pub coin_creator_fee: u64,
+    #[layout(pubkey)]
+    pub coin_creator_vault_ata: String,
+    #[layout(pubkey)]
+    pub coin_creator_token_account: String,
+}
+
+/// Shared by `SetBondingCurveCoinCreatorEvent` and `SetMetaplexCoinCreatorEvent`, which only
+/// differ in where the coin creator was read from.
+#[derive(Layout, Debug, Clone)]
+pub struct SetCoinCreatorEvent {
+    pub timestamp: i64,
+    #[layout(pubkey)]
+    pub base_mint: String,
+    #[layout(pubkey)]
+    pub pool: String,
+    /// The bonding curve or the Metaplex metadata account.
+    #[layout(pubkey)]
+    pub coin_creator_source: String,
+    #[layout(pubkey)]
+    pub coin_creator: String,
+}
+
+/// Shared by `CreateConfigEvent` and `UpdateFeeConfigEvent`.
+#[derive(Layout, Debug, Clone)]
+pub struct ConfigEvent {
+    pub timestamp: i64,
+    #[layout(pubkey)]
+    pub admin: String,
+    pub lp_fee_basis_points: u64,
+    pub protocol_fee_basis_points: u64,
+    #[layout(pubkey)]
+    pub protocol_fee_recipients: [String; 8],
+    /// Only logged since coin creator fees were introduced.
+    #[layout(trailing)]
+    pub coin_creator_fee_basis_points: Option<u64>,
+    #[layout(pubkey, trailing)]
+    pub admin_set_coin_creator_authority: Option<String>,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [225, 152, 171, 87, 246, 63, 66, 234])]
+pub struct UpdateAdminEvent {
+    pub timestamp: i64,
+    #[layout(pubkey)]
+    pub admin: String,
+    #[layout(pubkey)]
+    pub new_admin: String,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [107, 253, 193, 76, 228, 202, 27, 104])]
+pub struct DisableEvent {
+    pub timestamp: i64,
+    #[layout(pubkey)]
+    pub admin: String,
+    pub disable_create_pool: bool,
+    pub disable_deposit: bool,
+    pub disable_withdraw: bool,
+    pub disable_buy: bool,
+    pub disable_sell: bool,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [134, 36, 13, 72, 232, 101, 130, 216])]
+pub struct InitUserVolumeAccumulatorEvent {
+    #[layout(pubkey)]
+    pub payer: String,
+    #[layout(pubkey)]
+    pub user: String,
+    pub timestamp: i64,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [197, 122, 167, 124, 116, 81, 91, 255])]
+pub struct SyncUserVolumeAccumulatorEvent {
+    #[layout(pubkey)]
+    pub user: String,
+    pub total_claimed_tokens_before: u64,
+    pub total_claimed_tokens_after: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [146, 159, 189, 172, 146, 88, 56, 244])]
+pub struct CloseUserVolumeAccumulatorEvent {
+    #[layout(pubkey)]
+    pub user: String,
+    pub timestamp: i64,
+    pub total_unclaimed_tokens: u64,
+    pub total_claimed_tokens: u64,
+    pub current_sol_volume: u64,
+    pub last_update_timestamp: i64,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [79, 172, 246, 49, 205, 91, 206, 232])]
+pub struct ClaimTokenIncentivesEvent {
+    #[layout(pubkey)]
+    pub user: String,
+    #[layout(pubkey)]
+    pub mint: String,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub total_claimed_tokens: u64,
+    pub current_sol_volume: u64,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [147, 250, 108, 120, 247, 29, 67, 222])]
+pub struct AdminUpdateTokenIncentivesEvent {
+    pub start_time: i64,
+    pub end_time: i64,
+    pub day_number: u64,
+    pub token_supply_per_day: u64,
+    #[layout(pubkey)]
+    pub mint: String,
+    pub seconds_in_a_day: i64,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::DecodeErrorKind;
+    use utils::testing::{USER, WSOL_MINT, pubkey};
+
+    const POOL: &str = "Gf7sXMoP8iRw4iiXmJ1nq4vxcRycbGXy5RL8a8LnTd3v";
+    const BASE_MINT: &str = "4k3Dyjzvzp8eMZWUXbBCjEvwSkkk59S5iCNLY3QrkX6R";
+    const PROTOCOL_FEE_RECIPIENT: &str = "62qc2CNXwrYqQScmEdiZFFAnJR262PxWEuNQtxfafNgV";
+    const COIN_CREATOR: &str = "5Y3ZhsLXDvk4xJSMKzPCWHs2xTA3KtVVr6Q8mG5jJ4Kn";
+
+    /// The `BuyEvent` of a 1 SOL buy on a graduated coin, as it follows its discriminator.
+    fn buy_event_payload() -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend(1_750_012_345i64.to_le_bytes());
+        for amount in [
+            3_512_345_678_901u64, // base_amount_out
+            1_010_000_000,        // max_quote_amount_in
+            0,                    // user_base_token_reserves
+            5_000_000_000,        // user_quote_token_reserves
+            180_000_000_000_000,  // pool_base_token_reserves
+            90_000_000_000,       // pool_quote_token_reserves
+            1_000_000_000,        // quote_amount_in
+            20,                   // lp_fee_basis_points
+            2_000_000,            // lp_fee
+            5,                    // protocol_fee_basis_points
+            500_000,              // protocol_fee
+            1_002_000_000,        // quote_amount_in_with_lp_fee
+            1_003_000_000,        // user_quote_amount_in
+        ] {
+            data.extend(amount.to_le_bytes());
+        }
+        for address in [POOL, USER, USER, USER, PROTOCOL_FEE_RECIPIENT, PROTOCOL_FEE_RECIPIENT] {
+            data.extend(pubkey(address));
+        }
+        data.extend(pubkey(COIN_CREATOR));
+        data.extend(5u64.to_le_bytes());
+        data.extend(500_000u64.to_le_bytes());
+        data
+    }
+
+    fn create_pool_event_payload(coin_creator: Option<&str>) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend(1_750_000_000i64.to_le_bytes());
+        data.extend(0u16.to_le_bytes());
+        for address in [USER, BASE_MINT, WSOL_MINT] {
+            data.extend(pubkey(address));
+        }
+        data.extend([6u8, 9]);
+        for amount in [
+            206_900_000_000_000u64,
+            84_990_359_346,
+            206_900_000_000_000,
+            84_990_359_346,
+            100,
+            4_193_388_646_201,
+            4_193_388_646_101,
+        ] {
+            data.extend(amount.to_le_bytes());
+        }
+        data.push(255);
+        for address in [POOL, POOL, USER, USER] {
+            data.extend(pubkey(address));
+        }
+        if let Some(coin_creator) = coin_creator {
+            data.extend(pubkey(coin_creator));
+        }
+        data
+    }
+
+    #[test]
+    fn decodes_a_buy_event() {
+        let mut data = BuyEvent::DISCRIMINATOR.to_vec();
+        data.extend(buy_event_payload());
+        let event = BuyEvent::decode_with_discriminator(&data).unwrap().unwrap();
+        assert_eq!(event.timestamp, 1_750_012_345);
+        assert_eq!(event.base_amount_out, 3_512_345_678_901);
+        assert_eq!(event.pool_quote_token_reserves, 90_000_000_000);
+        assert_eq!(event.quote_amount_in, 1_000_000_000);
+        assert_eq!(event.user_quote_amount_in, 1_003_000_000);
+        assert_eq!(event.pool, POOL);
+        assert_eq!(event.user, USER);
+        assert_eq!(event.protocol_fee_recipient, PROTOCOL_FEE_RECIPIENT);
+        assert_eq!(event.coin_creator, COIN_CREATOR);
+        assert_eq!(event.coin_creator_fee_basis_points, 5);
+        assert_eq!(event.coin_creator_fee, 500_000);
+        assert_eq!(BuyEvent::MIN_LEN, buy_event_payload().len());
+    }
+
+    #[test]
+    fn skips_another_discriminator() {
+        let mut data = SellEvent::DISCRIMINATOR.to_vec();
+        data.extend(buy_event_payload());
+        assert!(BuyEvent::decode_with_discriminator(&data).unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_a_truncated_event() {
+        let data = buy_event_payload();
+        let truncated = &data[..data.len() - 1];
+        assert_eq!(
+            BuyEvent::decode(truncated).unwrap_err(),
+            DecodeErrorKind::DataTooShort {
+                offset: 0,
+                needed: data.len(),
+                len: truncated.len(),
+            }
+        );
+    }
+
+    #[test]
+    fn decodes_a_trailing_field_only_when_logged() {
+        let data = create_pool_event_payload(Some(COIN_CREATOR));
+        let event = CreatePoolEvent::decode(&data).unwrap();
+        assert_eq!(event.coin_creator.as_deref(), Some(COIN_CREATOR));
+        assert_eq!(event.lp_token_amount_out, 4_193_388_646_101);
+
+        let event = CreatePoolEvent::decode(&create_pool_event_payload(None)).unwrap();
+        assert_eq!(event.coin_creator, None);
+        assert_eq!(event.pool, POOL);
+    }
+}
@@ -1,15 +1,32 @@
-use bs58;
+pub mod layouts;
+
 use instruction_parser::InstructionParser;
-use types::{DecodedEvent, DecodedPumpAmmBuyLog, DecodedPumpAmmCreatePoolEvent, DecodedPumpAmmEvent, DecodedPumpAmmSellLog, DecodedPumpAmmSwapEvent, StructuredInstruction, SwapEventAccounts, TransactionType, DecodedPumpAmmDepositEvent, DecodedPumpAmmWithdrawEvent};
+use solana_pubkey::{Pubkey, pubkey};
+use std::str::FromStr;
+use layouts::{
+    AdminUpdateTokenIncentivesEvent, BuyEvent, ClaimTokenIncentivesEvent,
+    CloseUserVolumeAccumulatorEvent, CollectCoinCreatorFeeEvent, ConfigEvent, CreatePoolEvent,
+    DepositArgs, DepositEvent, DisableEvent, InitUserVolumeAccumulatorEvent, SellEvent,
+    SetCoinCreatorEvent, SyncUserVolumeAccumulatorEvent, UpdateAdminEvent, WithdrawArgs,
+    WithdrawEvent,
+};
+use types::{
+    DecodeError, DecodeErrorKind, DecodeReport, DecodedEvent, DecodedInstruction,
+    DecodedPumpAmmAdminUpdateTokenIncentivesEvent, DecodedPumpAmmBuyLog,
+    DecodedPumpAmmClaimTokenIncentivesEvent, DecodedPumpAmmCloseUserVolumeAccumulatorEvent,
+    DecodedPumpAmmCollectCoinCreatorFeeEvent, DecodedPumpAmmConfigEvent,
+    DecodedPumpAmmCreatePoolEvent, DecodedPumpAmmDepositEvent, DecodedPumpAmmDisableEvent,
+    DecodedPumpAmmEvent, DecodedPumpAmmInitUserVolumeAccumulatorEvent, DecodedPumpAmmSellLog,
+    DecodedPumpAmmSetCoinCreatorEvent, DecodedPumpAmmSwapEvent,
+    DecodedPumpAmmSyncUserVolumeAccumulatorEvent, DecodedPumpAmmUpdateAdminEvent,
+    DecodedPumpAmmWithdrawEvent, StructuredInstruction, SwapEventAccounts, TransactionType,
+};
+use utils::{find_event, get_account};
 use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct PumpAmmInstructionParser {}
 
 impl InstructionParser for PumpAmmInstructionParser {
-    fn new() -> Self {
-        Self {}
-    }
-
     fn get_program_id(&self) -> &str {
         Self::PROGRAM_ID
     }
@@ -17,22 +34,24 @@ impl InstructionParser for PumpAmmInstructionParser {
     fn decode_instructions(
         &self,
         instructions: Vec<StructuredInstruction>,
-        account_keys: &Vec<String>,
-        transaction: &SubscribeUpdateTransaction
-    ) -> Vec<DecodedEvent> {
-        let ixs: Vec<DecodedEvent> = instructions
-            .iter()
-            .filter_map(
-                |instruction| match self.decode_instruction(instruction, &account_keys) {
-                    Some(decoded_instruction) => Some(DecodedEvent::PumpAmm(decoded_instruction)),
-                    None => None,
-                },
-            )
-            .collect();
-        if ixs.is_empty() {
-            println!("empty instructions: {:?}", instructions);
+        account_keys: &[String],
+        _transaction: &SubscribeUpdateTransaction
+    ) -> DecodeReport {
+        let mut report = DecodeReport::default();
+        for instruction in instructions.iter() {
+            match self.decode_instruction(instruction, account_keys) {
+                Ok(Some(decoded_instruction)) => report.events.push(DecodedInstruction::new(
+                    Self::PROGRAM_ID,
+                    instruction,
+                    DecodedEvent::PumpAmm(decoded_instruction),
+                )),
+                Ok(None) => {}
+                Err(kind) => report
+                    .errors
+                    .push(DecodeError::new(Self::PROGRAM_ID, instruction, 8, kind)),
+            }
         }
-        ixs
+        report
     }
 }
 
@@ -41,51 +60,130 @@ impl PumpAmmInstructionParser {
     const POOL_CREATION_DISCRIMINATOR: [u8; 8] = [233, 146, 209, 142, 207, 104, 64, 188];
     const BUY_DISCRIMINATOR: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
     const SELL_DISCRIMINATOR: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
-    const DEPOSIT_DISCRIMINATOR: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
-    const WITHDRAW_DISCRIMINATOR: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+    const COLLECT_COIN_CREATOR_FEE_DISCRIMINATOR: [u8; 8] = [160, 57, 89, 42, 181, 139, 43, 66];
+    const SET_COIN_CREATOR_DISCRIMINATOR: [u8; 8] = [210, 149, 128, 45, 188, 58, 78, 175];
+    const CREATE_CONFIG_DISCRIMINATOR: [u8; 8] = [201, 207, 243, 114, 75, 111, 47, 189];
+    const UPDATE_FEE_CONFIG_DISCRIMINATOR: [u8; 8] = [104, 184, 103, 242, 88, 151, 107, 20];
+    const UPDATE_ADMIN_DISCRIMINATOR: [u8; 8] = [161, 176, 40, 213, 60, 184, 179, 228];
+    const DISABLE_DISCRIMINATOR: [u8; 8] = [185, 173, 187, 90, 216, 15, 238, 233];
+    const INIT_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR: [u8; 8] = [94, 6, 202, 115, 255, 96, 232, 183];
+    const SYNC_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR: [u8; 8] = [86, 31, 192, 87, 163, 87, 79, 238];
+    const CLOSE_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR: [u8; 8] =
+        [249, 69, 164, 218, 150, 103, 84, 138];
+    const CLAIM_TOKEN_INCENTIVES_DISCRIMINATOR: [u8; 8] = [16, 4, 71, 28, 204, 1, 40, 27];
+    const ADMIN_UPDATE_TOKEN_INCENTIVES_DISCRIMINATOR: [u8; 8] =
+        [209, 11, 115, 87, 213, 23, 124, 204];
+    const SET_BONDING_CURVE_COIN_CREATOR_EVENT_DISCRIMINATOR: [u8; 8] =
+        [242, 231, 235, 102, 65, 99, 189, 211];
+    const SET_METAPLEX_COIN_CREATOR_EVENT_DISCRIMINATOR: [u8; 8] =
+        [150, 107, 199, 123, 124, 207, 102, 228];
+    const CREATE_CONFIG_EVENT_DISCRIMINATOR: [u8; 8] = [107, 52, 89, 129, 55, 226, 81, 22];
+    const UPDATE_FEE_CONFIG_EVENT_DISCRIMINATOR: [u8; 8] = [90, 23, 65, 35, 62, 244, 188, 208];
+    const PUMP_FUN_PROGRAM_ID: Pubkey = pubkey!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
+
+    pub fn new() -> Self {
+        Self {}
+    }
 
     pub fn decode_instruction(
         &self,
         instruction: &StructuredInstruction,
-        account_keys: &Vec<String>,
-    ) -> Option<DecodedPumpAmmEvent> {
-        let discriminator = &instruction.data[..8];
+        account_keys: &[String],
+    ) -> Result<Option<DecodedPumpAmmEvent>, DecodeErrorKind> {
+        let discriminator = match instruction.data.get(..8) {
+            Some(discriminator) => discriminator,
+            None => return Ok(None),
+        };
         if discriminator == Self::BUY_DISCRIMINATOR {
-            return Some(DecodedPumpAmmEvent::Swap(Self::decode_buy_event(
+            return Ok(Some(DecodedPumpAmmEvent::Swap(Self::decode_buy_event(
                 instruction,
                 account_keys,
-            )));
+            )?)));
         } else if discriminator == Self::SELL_DISCRIMINATOR {
-            return Some(DecodedPumpAmmEvent::Swap(Self::decode_sell_event(
+            return Ok(Some(DecodedPumpAmmEvent::Swap(Self::decode_sell_event(
                 instruction,
                 account_keys,
-            )));
+            )?)));
         } else if discriminator == Self::POOL_CREATION_DISCRIMINATOR {
-            return Some(DecodedPumpAmmEvent::CreatePool(
-                Self::decode_pool_creation_event(instruction, account_keys),
-            ));
-        } else if discriminator == Self::WITHDRAW_DISCRIMINATOR {
-        return Some(DecodedPumpAmmEvent::Withdraw(Self::decode_withdraw_event(instruction)));
-        } else if discriminator == Self::DEPOSIT_DISCRIMINATOR {
-        return Some(DecodedPumpAmmEvent::Deposit(Self::decode_deposit_event(instruction)));
+            return Ok(Some(DecodedPumpAmmEvent::CreatePool(
+                Self::decode_pool_creation_event(instruction, account_keys)?,
+            )));
+        } else if let Some(args) = WithdrawArgs::decode_with_discriminator(&instruction.data)? {
+            return Ok(Some(DecodedPumpAmmEvent::Withdraw(Self::decode_withdraw_event(
+                instruction,
+                account_keys,
+                args,
+            )?)));
+        } else if let Some(args) = DepositArgs::decode_with_discriminator(&instruction.data)? {
+            return Ok(Some(DecodedPumpAmmEvent::Deposit(Self::decode_deposit_event(
+                instruction,
+                account_keys,
+                args,
+            )?)));
+        } else if discriminator == Self::COLLECT_COIN_CREATOR_FEE_DISCRIMINATOR {
+            return Ok(Some(DecodedPumpAmmEvent::CollectCoinCreatorFee(
+                Self::decode_collect_coin_creator_fee_event(instruction)?,
+            )));
+        } else if discriminator == Self::SET_COIN_CREATOR_DISCRIMINATOR {
+            return Ok(Some(DecodedPumpAmmEvent::SetCoinCreator(
+                Self::decode_set_coin_creator_event(instruction)?,
+            )));
+        } else if discriminator == Self::CREATE_CONFIG_DISCRIMINATOR {
+            return Ok(Some(DecodedPumpAmmEvent::CreateConfig(Self::decode_config_event(
+                instruction,
+                &Self::CREATE_CONFIG_EVENT_DISCRIMINATOR,
+            )?)));
+        } else if discriminator == Self::UPDATE_FEE_CONFIG_DISCRIMINATOR {
+            return Ok(Some(DecodedPumpAmmEvent::UpdateFeeConfig(Self::decode_config_event(
+                instruction,
+                &Self::UPDATE_FEE_CONFIG_EVENT_DISCRIMINATOR,
+            )?)));
+        } else if discriminator == Self::UPDATE_ADMIN_DISCRIMINATOR {
+            return Ok(Some(DecodedPumpAmmEvent::UpdateAdmin(Self::decode_update_admin_event(
+                instruction,
+            )?)));
+        } else if discriminator == Self::DISABLE_DISCRIMINATOR {
+            return Ok(Some(DecodedPumpAmmEvent::Disable(Self::decode_disable_event(instruction)?)));
+        } else if discriminator == Self::INIT_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR {
+            return Ok(Some(DecodedPumpAmmEvent::InitUserVolumeAccumulator(
+                Self::decode_init_user_volume_accumulator_event(instruction)?,
+            )));
+        } else if discriminator == Self::SYNC_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR {
+            return Ok(Some(DecodedPumpAmmEvent::SyncUserVolumeAccumulator(
+                Self::decode_sync_user_volume_accumulator_event(instruction)?,
+            )));
+        } else if discriminator == Self::CLOSE_USER_VOLUME_ACCUMULATOR_DISCRIMINATOR {
+            return Ok(Some(DecodedPumpAmmEvent::CloseUserVolumeAccumulator(
+                Self::decode_close_user_volume_accumulator_event(instruction)?,
+            )));
+        } else if discriminator == Self::CLAIM_TOKEN_INCENTIVES_DISCRIMINATOR {
+            return Ok(Some(DecodedPumpAmmEvent::ClaimTokenIncentives(
+                Self::decode_claim_token_incentives_event(instruction)?,
+            )));
+        } else if discriminator == Self::ADMIN_UPDATE_TOKEN_INCENTIVES_DISCRIMINATOR {
+            return Ok(Some(DecodedPumpAmmEvent::AdminUpdateTokenIncentives(
+                Self::decode_admin_update_token_incentives_event(instruction)?,
+            )));
         }
-        None
+        Ok(None)
     }
+
+
     pub fn decode_buy_event(
         instruction: &StructuredInstruction,
         account_keys: &[String],
-    ) -> DecodedPumpAmmSwapEvent {
-        let account_key_indexes = &instruction.account_key_indexes;
-        let pool = account_keys[account_key_indexes[0] as usize].clone();
-        let user = account_keys[account_key_indexes[1] as usize].clone();
-        let base_mint = account_keys[account_key_indexes[3] as usize].clone();
-        let quote_mint = account_keys[account_key_indexes[4] as usize].clone();
-        let buy_log = instruction.inner_instructions.last().unwrap();
-        let decoded_buy_log = Self::decode_buy_log(&buy_log.data).unwrap();
-        let mint_in_reserve = decoded_buy_log.pool_base_token_reserves.clone();
-        let mint_out_reserve = decoded_buy_log.pool_quote_token_reserves.clone();
-
-        DecodedPumpAmmSwapEvent {
+    ) -> Result<DecodedPumpAmmSwapEvent, DecodeErrorKind> {
+        let pool = get_account(instruction, account_keys, 0)?;
+        let user = get_account(instruction, account_keys, 1)?;
+        let base_mint = get_account(instruction, account_keys, 3)?;
+        let quote_mint = get_account(instruction, account_keys, 4)?;
+        let buy_log = find_event(instruction, &BuyEvent::DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)?;
+        let decoded_buy_log = Self::decode_buy_log(buy_log)?;
+        let mint_in_reserve = decoded_buy_log.pool_base_token_reserves;
+        let mint_out_reserve = decoded_buy_log.pool_quote_token_reserves;
+
+        Ok(DecodedPumpAmmSwapEvent {
             accounts: SwapEventAccounts {
                 pool,
                 user,
@@ -98,51 +196,77 @@ impl PumpAmmInstructionParser {
             amount_out: decoded_buy_log.base_amount_out,
             mint_in_reserve,
             mint_out_reserve,
+            timestamp: decoded_buy_log.timestamp,
+            max_quote_amount_in: Some(decoded_buy_log.max_quote_amount_in),
+            min_quote_amount_out: None,
+            quote_amount: decoded_buy_log.quote_amount_in,
+            user_quote_amount: decoded_buy_log.user_quote_amount_in,
+            quote_amount_in_with_lp_fee: Some(decoded_buy_log.quote_amount_in_with_lp_fee),
+            quote_amount_out_without_lp_fee: None,
+            user_base_token_reserves: decoded_buy_log.user_base_token_reserves,
+            user_quote_token_reserves: decoded_buy_log.user_quote_token_reserves,
+            lp_fee_basis_points: decoded_buy_log.lp_fee_basis_points,
+            lp_fee: decoded_buy_log.lp_fee,
+            protocol_fee_basis_points: decoded_buy_log.protocol_fee_basis_points,
+            protocol_fee: decoded_buy_log.protocol_fee,
+            coin_creator: decoded_buy_log.coin_creator,
+            coin_creator_fee_basis_points: decoded_buy_log.coin_creator_fee_basis_points,
+            coin_creator_fee: decoded_buy_log.coin_creator_fee,
+            user_base_token_account: decoded_buy_log.user_base_token_account,
+            user_quote_token_account: decoded_buy_log.user_quote_token_account,
+            protocol_fee_recipient: decoded_buy_log.protocol_fee_recipient,
+            protocol_fee_recipient_token_account: decoded_buy_log
+                .protocol_fee_recipient_token_account,
             event_type: TransactionType::Buy,
-        }
+        })
     }
 
-    pub fn decode_buy_log(data: &[u8]) -> Option<DecodedPumpAmmBuyLog> {
-        if data.len() < 352 {
-            return None;
-        }
-        let mut offset: usize = 24;
-        let base_amount_out: u64 = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 32;
-        let pool_base_token_reserves: u64 =
-            u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 8;
-        let pool_quote_token_reserves: u64 =
-            u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 8;
-        let quote_amount_in: u64 = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 248;
-        let coin_creator: String = bs58::encode(data[offset..offset + 32].to_vec()).into_string();
-        Some(DecodedPumpAmmBuyLog {
-            quote_amount_in,
-            base_amount_out,
-            pool_base_token_reserves,
-            pool_quote_token_reserves,
-            coin_creator,
+    /// Decodes a `BuyEvent` from the data following its discriminator.
+    pub fn decode_buy_log(data: &[u8]) -> Result<DecodedPumpAmmBuyLog, DecodeErrorKind> {
+        let event = BuyEvent::decode(data)?;
+        Ok(DecodedPumpAmmBuyLog {
+            timestamp: event.timestamp,
+            base_amount_out: event.base_amount_out,
+            max_quote_amount_in: event.max_quote_amount_in,
+            user_base_token_reserves: event.user_base_token_reserves,
+            user_quote_token_reserves: event.user_quote_token_reserves,
+            pool_base_token_reserves: event.pool_base_token_reserves,
+            pool_quote_token_reserves: event.pool_quote_token_reserves,
+            quote_amount_in: event.quote_amount_in,
+            lp_fee_basis_points: event.lp_fee_basis_points,
+            lp_fee: event.lp_fee,
+            protocol_fee_basis_points: event.protocol_fee_basis_points,
+            protocol_fee: event.protocol_fee,
+            quote_amount_in_with_lp_fee: event.quote_amount_in_with_lp_fee,
+            user_quote_amount_in: event.user_quote_amount_in,
+            pool: event.pool,
+            user: event.user,
+            user_base_token_account: event.user_base_token_account,
+            user_quote_token_account: event.user_quote_token_account,
+            protocol_fee_recipient: event.protocol_fee_recipient,
+            protocol_fee_recipient_token_account: event.protocol_fee_recipient_token_account,
+            coin_creator: event.coin_creator,
+            coin_creator_fee_basis_points: event.coin_creator_fee_basis_points,
+            coin_creator_fee: event.coin_creator_fee,
             transaction_type: TransactionType::Buy,
         })
     }
 
     pub fn decode_sell_event(
         instruction: &StructuredInstruction,
-        account_keys: &Vec<String>,
-    ) -> DecodedPumpAmmSwapEvent {
-        let account_key_indexes = &instruction.account_key_indexes;
-        let pool = account_keys[account_key_indexes[0] as usize].clone();
-        let user = account_keys[account_key_indexes[1] as usize].clone();
-        let base_mint = account_keys[account_key_indexes[3] as usize].clone();
-        let quote_mint = account_keys[account_key_indexes[4] as usize].clone();
-        let sell_log = instruction.inner_instructions.last().unwrap();
-        let decoded_sell_log = Self::decode_sell_log(&sell_log.data).unwrap();
-        let mint_in_reserve = decoded_sell_log.pool_base_token_reserves.clone();
-        let mint_out_reserve = decoded_sell_log.pool_quote_token_reserves.clone();
-
-        DecodedPumpAmmSwapEvent {
+        account_keys: &[String],
+    ) -> Result<DecodedPumpAmmSwapEvent, DecodeErrorKind> {
+        let pool = get_account(instruction, account_keys, 0)?;
+        let user = get_account(instruction, account_keys, 1)?;
+        let base_mint = get_account(instruction, account_keys, 3)?;
+        let quote_mint = get_account(instruction, account_keys, 4)?;
+        let sell_log = find_event(instruction, &SellEvent::DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)?;
+        let decoded_sell_log = Self::decode_sell_log(sell_log)?;
+        let mint_in_reserve = decoded_sell_log.pool_base_token_reserves;
+        let mint_out_reserve = decoded_sell_log.pool_quote_token_reserves;
+
+        Ok(DecodedPumpAmmSwapEvent {
             accounts: SwapEventAccounts {
                 pool,
                 user,
@@ -155,111 +279,344 @@ impl PumpAmmInstructionParser {
             amount_out: decoded_sell_log.quote_amount_out,
             mint_in_reserve,
             mint_out_reserve,
+            timestamp: decoded_sell_log.timestamp,
+            max_quote_amount_in: None,
+            min_quote_amount_out: Some(decoded_sell_log.min_quote_amount_out),
+            quote_amount: decoded_sell_log.quote_amount_out,
+            user_quote_amount: decoded_sell_log.user_quote_amount_out,
+            quote_amount_in_with_lp_fee: None,
+            quote_amount_out_without_lp_fee: Some(decoded_sell_log.quote_amount_out_without_lp_fee),
+            user_base_token_reserves: decoded_sell_log.user_base_token_reserves,
+            user_quote_token_reserves: decoded_sell_log.user_quote_token_reserves,
+            lp_fee_basis_points: decoded_sell_log.lp_fee_basis_points,
+            lp_fee: decoded_sell_log.lp_fee,
+            protocol_fee_basis_points: decoded_sell_log.protocol_fee_basis_points,
+            protocol_fee: decoded_sell_log.protocol_fee,
+            coin_creator: decoded_sell_log.coin_creator,
+            coin_creator_fee_basis_points: decoded_sell_log.coin_creator_fee_basis_points,
+            coin_creator_fee: decoded_sell_log.coin_creator_fee,
+            user_base_token_account: decoded_sell_log.user_base_token_account,
+            user_quote_token_account: decoded_sell_log.user_quote_token_account,
+            protocol_fee_recipient: decoded_sell_log.protocol_fee_recipient,
+            protocol_fee_recipient_token_account: decoded_sell_log
+                .protocol_fee_recipient_token_account,
             event_type: TransactionType::Sell,
-        }
+        })
     }
 
-    fn decode_sell_log(data: &[u8]) -> Option<DecodedPumpAmmSellLog> {
-        let mut offset: usize = 24;
-        let base_amount_in: u64 = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 32;
-        let pool_base_token_reserves: u64 =
-            u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 8;
-        let pool_quote_token_reserves: u64 =
-            u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 56;
-        let quote_amount_out: u64 =
-            u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 200;
-        let coin_creator: String = bs58::encode(data[offset..offset + 32].to_vec()).into_string();
-        return Some(DecodedPumpAmmSellLog {
-            base_amount_in,
-            pool_base_token_reserves,
-            pool_quote_token_reserves,
-            quote_amount_out,
-            coin_creator,
+    /// Decodes a `SellEvent` from the data following its discriminator.
+    pub fn decode_sell_log(data: &[u8]) -> Result<DecodedPumpAmmSellLog, DecodeErrorKind> {
+        let event = SellEvent::decode(data)?;
+        Ok(DecodedPumpAmmSellLog {
+            timestamp: event.timestamp,
+            base_amount_in: event.base_amount_in,
+            min_quote_amount_out: event.min_quote_amount_out,
+            user_base_token_reserves: event.user_base_token_reserves,
+            user_quote_token_reserves: event.user_quote_token_reserves,
+            pool_base_token_reserves: event.pool_base_token_reserves,
+            pool_quote_token_reserves: event.pool_quote_token_reserves,
+            quote_amount_out: event.quote_amount_out,
+            lp_fee_basis_points: event.lp_fee_basis_points,
+            lp_fee: event.lp_fee,
+            protocol_fee_basis_points: event.protocol_fee_basis_points,
+            protocol_fee: event.protocol_fee,
+            quote_amount_out_without_lp_fee: event.quote_amount_out_without_lp_fee,
+            user_quote_amount_out: event.user_quote_amount_out,
+            pool: event.pool,
+            user: event.user,
+            user_base_token_account: event.user_base_token_account,
+            user_quote_token_account: event.user_quote_token_account,
+            protocol_fee_recipient: event.protocol_fee_recipient,
+            protocol_fee_recipient_token_account: event.protocol_fee_recipient_token_account,
+            coin_creator: event.coin_creator,
+            coin_creator_fee_basis_points: event.coin_creator_fee_basis_points,
+            coin_creator_fee: event.coin_creator_fee,
             transaction_type: TransactionType::Sell,
-        });
+        })
     }
 
+    /// Accounts: [pool, global_config, creator, base_mint, quote_mint, lp_mint,
+    /// user_base_token_account, user_quote_token_account, user_pool_token_account,
+    /// pool_base_token_account, pool_quote_token_account, ...]
     pub fn decode_pool_creation_event(
         instruction: &StructuredInstruction,
-        account_keys: &Vec<String>,
-    ) -> DecodedPumpAmmCreatePoolEvent {
-        let account_key_indexes: &Vec<u8> = &instruction.account_key_indexes;
-        let data: &Vec<u8> = &instruction.data;
-        let pool: String = account_keys[account_key_indexes[0] as usize].clone();
-        let base_mint: String = account_keys[account_key_indexes[3] as usize].clone();
-        let quote_mint: String = account_keys[account_key_indexes[4] as usize].clone();
-        let pool_base_token_account: String = account_keys[account_key_indexes[9] as usize].clone();
-        let pool_quote_token_account: String =
-            account_keys[account_key_indexes[10] as usize].clone();
-
-        let mut offset: usize = 8;
-        let index: u16 = u16::from_le_bytes(data[offset..offset + 2].try_into().unwrap());
-        offset += 2;
-        let base_amount_in: u64 = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 8;
-        let quote_amount_in: u64 = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 8;
-        let coin_creator: String = bs58::encode(data[offset..offset + 32].to_vec()).into_string();
-
-        DecodedPumpAmmCreatePoolEvent {
-            pool,
-            creator: coin_creator,
-            base_mint,
-            quote_mint,
-            pool_base_token_reserve: base_amount_in,
-            pool_quote_token_reserve: quote_amount_in,
+        account_keys: &[String],
+    ) -> Result<DecodedPumpAmmCreatePoolEvent, DecodeErrorKind> {
+        let pool_base_token_account: String = get_account(instruction, account_keys, 9)?;
+        let pool_quote_token_account: String = get_account(instruction, account_keys, 10)?;
+
+        let event = find_event(instruction, &CreatePoolEvent::DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)
+            .and_then(CreatePoolEvent::decode)?;
+
+        let is_canonical = event.index == 0
+            && Self::pump_fun_pool_authority(&event.base_mint)
+                .is_some_and(|pool_authority| event.creator == pool_authority);
+
+        Ok(DecodedPumpAmmCreatePoolEvent {
+            pool: event.pool,
+            creator: event.creator,
+            coin_creator: event.coin_creator,
+            base_mint: event.base_mint,
+            quote_mint: event.quote_mint,
+            base_decimals: event.base_mint_decimals,
+            quote_decimals: event.quote_mint_decimals,
+            pool_base_token_reserve: event.pool_base_amount,
+            pool_quote_token_reserve: event.pool_quote_amount,
             pool_base_token_account,
             pool_quote_token_account,
-            index,
+            lp_mint: event.lp_mint,
+            initial_liquidity: event.initial_liquidity,
+            minimum_liquidity: event.minimum_liquidity,
+            lp_token_amount_out: event.lp_token_amount_out,
+            index: event.index,
+            is_canonical,
+            timestamp: event.timestamp,
             event_type: TransactionType::CreatePool,
-        }
+        })
     }
 
+    /// pump.fun migrates a curve by creating the pool with its per-mint `pool-authority` PDA as
+    /// creator.
+    fn pump_fun_pool_authority(base_mint: &str) -> Option<String> {
+        let base_mint = Pubkey::from_str(base_mint).ok()?;
+        let (pool_authority, _) = Pubkey::find_program_address(
+            &[b"pool-authority", base_mint.as_ref()],
+            &Self::PUMP_FUN_PROGRAM_ID,
+        );
+        Some(pool_authority.to_string())
+    }
+
+    /// `deposit` and `withdraw` share their accounts: [pool, global_config, user, base_mint,
+    /// quote_mint, lp_mint, user_base_token_account, user_quote_token_account,
+    /// user_pool_token_account, pool_base_token_account, pool_quote_token_account, ...]
     pub fn decode_withdraw_event(
         instruction: &StructuredInstruction,
-    ) -> DecodedPumpAmmWithdrawEvent {
-        let correct_instruction = instruction.inner_instructions.last().unwrap().clone();
-        let data = correct_instruction.data;
-        let mut offset: usize = 64;
-        let pool_base_token_reserves = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 8;
-        let pool_quote_token_reserves = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 8;
-        let base_amount_out = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 8;
-        let quote_amount_out = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        
-        DecodedPumpAmmWithdrawEvent {
-            pool_base_token_reserves,
-            pool_quote_token_reserves,
-            base_amount_out,
-            quote_amount_out,
-        }
+        account_keys: &[String],
+        args: WithdrawArgs,
+    ) -> Result<DecodedPumpAmmWithdrawEvent, DecodeErrorKind> {
+        let pool = get_account(instruction, account_keys, 0)?;
+        let user = get_account(instruction, account_keys, 2)?;
+        let base_mint = get_account(instruction, account_keys, 3)?;
+        let quote_mint = get_account(instruction, account_keys, 4)?;
+        let lp_mint = get_account(instruction, account_keys, 5)?;
+        let user_pool_token_account = get_account(instruction, account_keys, 8)?;
+
+        let event = find_event(instruction, &WithdrawEvent::DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)
+            .and_then(WithdrawEvent::decode)?;
+
+        Ok(DecodedPumpAmmWithdrawEvent {
+            pool,
+            user,
+            base_mint,
+            quote_mint,
+            lp_mint,
+            user_pool_token_account,
+            lp_token_amount_in: args.lp_token_amount_in,
+            min_base_amount_out: args.min_base_amount_out,
+            min_quote_amount_out: args.min_quote_amount_out,
+            pool_base_token_reserves: event.pool_base_token_reserves,
+            pool_quote_token_reserves: event.pool_quote_token_reserves,
+            base_amount_out: event.base_amount_out,
+            quote_amount_out: event.quote_amount_out,
+            lp_mint_supply: event.lp_mint_supply,
+        })
     }
 
     pub fn decode_deposit_event(
         instruction: &StructuredInstruction,
-    ) -> DecodedPumpAmmDepositEvent {
-        let correct_instruction = instruction.inner_instructions.last().unwrap().clone();
-        let data = correct_instruction.data;
-        let mut offset: usize = 64;
-        let pool_base_token_reserves = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 8;
-        let pool_quote_token_reserves = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 8;
-        let base_amount_in = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 8;
-        let quote_amount_in = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-
-        DecodedPumpAmmDepositEvent {
-            pool_base_token_reserves,
-            pool_quote_token_reserves,
-            base_amount_in,
-            quote_amount_in,
-        }
+        account_keys: &[String],
+        args: DepositArgs,
+    ) -> Result<DecodedPumpAmmDepositEvent, DecodeErrorKind> {
+        let pool = get_account(instruction, account_keys, 0)?;
+        let user = get_account(instruction, account_keys, 2)?;
+        let base_mint = get_account(instruction, account_keys, 3)?;
+        let quote_mint = get_account(instruction, account_keys, 4)?;
+        let lp_mint = get_account(instruction, account_keys, 5)?;
+        let user_pool_token_account = get_account(instruction, account_keys, 8)?;
+
+        let event = find_event(instruction, &DepositEvent::DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)
+            .and_then(DepositEvent::decode)?;
+
+        Ok(DecodedPumpAmmDepositEvent {
+            pool,
+            user,
+            base_mint,
+            quote_mint,
+            lp_mint,
+            user_pool_token_account,
+            lp_token_amount_out: args.lp_token_amount_out,
+            max_base_amount_in: args.max_base_amount_in,
+            max_quote_amount_in: args.max_quote_amount_in,
+            pool_base_token_reserves: event.pool_base_token_reserves,
+            pool_quote_token_reserves: event.pool_quote_token_reserves,
+            base_amount_in: event.base_amount_in,
+            quote_amount_in: event.quote_amount_in,
+            lp_mint_supply: event.lp_mint_supply,
+        })
+    }
+
+    pub fn decode_collect_coin_creator_fee_event(
+        instruction: &StructuredInstruction,
+    ) -> Result<DecodedPumpAmmCollectCoinCreatorFeeEvent, DecodeErrorKind> {
+        let event = find_event(instruction, &CollectCoinCreatorFeeEvent::DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)
+            .and_then(CollectCoinCreatorFeeEvent::decode)?;
+        Ok(DecodedPumpAmmCollectCoinCreatorFeeEvent {
+            coin_creator: event.coin_creator,
+            coin_creator_fee: event.coin_creator_fee,
+            coin_creator_vault_ata: event.coin_creator_vault_ata,
+            coin_creator_token_account: event.coin_creator_token_account,
+            timestamp: event.timestamp,
+        })
+    }
+
+    pub fn decode_set_coin_creator_event(
+        instruction: &StructuredInstruction,
+    ) -> Result<DecodedPumpAmmSetCoinCreatorEvent, DecodeErrorKind> {
+        let (data, from_metadata) =
+            match find_event(instruction, &Self::SET_BONDING_CURVE_COIN_CREATOR_EVENT_DISCRIMINATOR) {
+                Some(data) => (data, false),
+                None => (
+                    find_event(instruction, &Self::SET_METAPLEX_COIN_CREATOR_EVENT_DISCRIMINATOR)
+                        .ok_or(DecodeErrorKind::MissingEventLog)?,
+                    true,
+                ),
+            };
+        let event = SetCoinCreatorEvent::decode(data)?;
+        Ok(DecodedPumpAmmSetCoinCreatorEvent {
+            base_mint: event.base_mint,
+            pool: event.pool,
+            coin_creator_source: event.coin_creator_source,
+            from_metadata,
+            coin_creator: event.coin_creator,
+            timestamp: event.timestamp,
+        })
+    }
+
+    pub fn decode_config_event(
+        instruction: &StructuredInstruction,
+        event_discriminator: &[u8; 8],
+    ) -> Result<DecodedPumpAmmConfigEvent, DecodeErrorKind> {
+        let event = find_event(instruction, event_discriminator)
+            .ok_or(DecodeErrorKind::MissingEventLog)
+            .and_then(ConfigEvent::decode)?;
+        Ok(DecodedPumpAmmConfigEvent {
+            admin: event.admin,
+            lp_fee_basis_points: event.lp_fee_basis_points,
+            protocol_fee_basis_points: event.protocol_fee_basis_points,
+            protocol_fee_recipients: event.protocol_fee_recipients.to_vec(),
+            coin_creator_fee_basis_points: event.coin_creator_fee_basis_points,
+            admin_set_coin_creator_authority: event.admin_set_coin_creator_authority,
+            timestamp: event.timestamp,
+        })
+    }
+
+    pub fn decode_update_admin_event(
+        instruction: &StructuredInstruction,
+    ) -> Result<DecodedPumpAmmUpdateAdminEvent, DecodeErrorKind> {
+        let event = find_event(instruction, &UpdateAdminEvent::DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)
+            .and_then(UpdateAdminEvent::decode)?;
+        Ok(DecodedPumpAmmUpdateAdminEvent {
+            admin: event.admin,
+            new_admin: event.new_admin,
+            timestamp: event.timestamp,
+        })
+    }
+
+    pub fn decode_disable_event(
+        instruction: &StructuredInstruction,
+    ) -> Result<DecodedPumpAmmDisableEvent, DecodeErrorKind> {
+        let event = find_event(instruction, &DisableEvent::DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)
+            .and_then(DisableEvent::decode)?;
+        Ok(DecodedPumpAmmDisableEvent {
+            admin: event.admin,
+            disable_create_pool: event.disable_create_pool,
+            disable_deposit: event.disable_deposit,
+            disable_withdraw: event.disable_withdraw,
+            disable_buy: event.disable_buy,
+            disable_sell: event.disable_sell,
+            timestamp: event.timestamp,
+        })
+    }
+
+    pub fn decode_init_user_volume_accumulator_event(
+        instruction: &StructuredInstruction,
+    ) -> Result<DecodedPumpAmmInitUserVolumeAccumulatorEvent, DecodeErrorKind> {
+        let event = find_event(instruction, &InitUserVolumeAccumulatorEvent::DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)
+            .and_then(InitUserVolumeAccumulatorEvent::decode)?;
+        Ok(DecodedPumpAmmInitUserVolumeAccumulatorEvent {
+            payer: event.payer,
+            user: event.user,
+            timestamp: event.timestamp,
+        })
+    }
+
+    pub fn decode_sync_user_volume_accumulator_event(
+        instruction: &StructuredInstruction,
+    ) -> Result<DecodedPumpAmmSyncUserVolumeAccumulatorEvent, DecodeErrorKind> {
+        let event = find_event(instruction, &SyncUserVolumeAccumulatorEvent::DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)
+            .and_then(SyncUserVolumeAccumulatorEvent::decode)?;
+        Ok(DecodedPumpAmmSyncUserVolumeAccumulatorEvent {
+            user: event.user,
+            total_claimed_tokens_before: event.total_claimed_tokens_before,
+            total_claimed_tokens_after: event.total_claimed_tokens_after,
+            timestamp: event.timestamp,
+        })
+    }
+
+    pub fn decode_close_user_volume_accumulator_event(
+        instruction: &StructuredInstruction,
+    ) -> Result<DecodedPumpAmmCloseUserVolumeAccumulatorEvent, DecodeErrorKind> {
+        let event = find_event(instruction, &CloseUserVolumeAccumulatorEvent::DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)
+            .and_then(CloseUserVolumeAccumulatorEvent::decode)?;
+        Ok(DecodedPumpAmmCloseUserVolumeAccumulatorEvent {
+            user: event.user,
+            total_unclaimed_tokens: event.total_unclaimed_tokens,
+            total_claimed_tokens: event.total_claimed_tokens,
+            current_sol_volume: event.current_sol_volume,
+            last_update_timestamp: event.last_update_timestamp,
+            timestamp: event.timestamp,
+        })
+    }
+
+    pub fn decode_claim_token_incentives_event(
+        instruction: &StructuredInstruction,
+    ) -> Result<DecodedPumpAmmClaimTokenIncentivesEvent, DecodeErrorKind> {
+        let event = find_event(instruction, &ClaimTokenIncentivesEvent::DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)
+            .and_then(ClaimTokenIncentivesEvent::decode)?;
+        Ok(DecodedPumpAmmClaimTokenIncentivesEvent {
+            user: event.user,
+            mint: event.mint,
+            amount: event.amount,
+            total_claimed_tokens: event.total_claimed_tokens,
+            current_sol_volume: event.current_sol_volume,
+            timestamp: event.timestamp,
+        })
+    }
+
+    pub fn decode_admin_update_token_incentives_event(
+        instruction: &StructuredInstruction,
+    ) -> Result<DecodedPumpAmmAdminUpdateTokenIncentivesEvent, DecodeErrorKind> {
+        let event = find_event(instruction, &AdminUpdateTokenIncentivesEvent::DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)
+            .and_then(AdminUpdateTokenIncentivesEvent::decode)?;
+        Ok(DecodedPumpAmmAdminUpdateTokenIncentivesEvent {
+            mint: event.mint,
+            start_time: event.start_time,
+            end_time: event.end_time,
+            day_number: event.day_number,
+            token_supply_per_day: event.token_supply_per_day,
+            seconds_in_a_day: event.seconds_in_a_day,
+            timestamp: event.timestamp,
+        })
     }
 }
@@ -0,0 +1,256 @@
+//! Borsh layouts of the pump.fun instruction args and events, decoded by `#[derive(Layout)]`.
+//! Event discriminators are the ones following the self-CPI tag.
+
+use layout_derive::Layout;
+
+/// Leading args of both `create` and `create_v2`.
+#[derive(Layout, Debug, Clone)]
+pub struct CreateArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    #[layout(pubkey)]
+    pub creator: String,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [189, 219, 127, 211, 78, 230, 97, 238])]
+pub struct TradeEvent {
+    #[layout(pubkey)]
+    pub mint: String,
+    pub sol_amount: u64,
+    pub token_amount: u64,
+    pub is_buy: bool,
+    #[layout(pubkey)]
+    pub user: String,
+    pub timestamp: i64,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    /// The fee fields (up to `creator_fee`) are missing from older events.
+    #[layout(pubkey, trailing)]
+    pub fee_recipient: Option<String>,
+    #[layout(trailing)]
+    pub fee_basis_points: Option<u64>,
+    #[layout(trailing)]
+    pub fee: Option<u64>,
+    #[layout(pubkey, trailing)]
+    pub creator: Option<String>,
+    #[layout(trailing)]
+    pub creator_fee_basis_points: Option<u64>,
+    #[layout(trailing)]
+    pub creator_fee: Option<u64>,
+    /// So are the volume fields (up to `last_update_timestamp`).
+    #[layout(trailing)]
+    pub track_volume: Option<bool>,
+    #[layout(trailing)]
+    pub total_unclaimed_tokens: Option<u64>,
+    #[layout(trailing)]
+    pub total_claimed_tokens: Option<u64>,
+    #[layout(trailing)]
+    pub current_sol_volume: Option<u64>,
+    #[layout(trailing)]
+    pub last_update_timestamp: Option<i64>,
+    /// Only logged by recent program versions.
+    #[layout(trailing)]
+    pub ix_name: Option<String>,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [95, 114, 97, 156, 212, 46, 152, 8])]
+pub struct CompleteEvent {
+    #[layout(pubkey)]
+    pub user: String,
+    #[layout(pubkey)]
+    pub mint: String,
+    #[layout(pubkey)]
+    pub bonding_curve: String,
+    pub timestamp: i64,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [189, 233, 93, 185, 92, 148, 234, 148])]
+pub struct CompletePumpAmmMigrationEvent {
+    #[layout(pubkey)]
+    pub user: String,
+    #[layout(pubkey)]
+    pub mint: String,
+    pub mint_amount: u64,
+    pub sol_amount: u64,
+    pub pool_migration_fee: u64,
+    #[layout(pubkey)]
+    pub bonding_curve: String,
+    pub timestamp: i64,
+    #[layout(pubkey)]
+    pub pool: String,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [97, 97, 215, 144, 93, 146, 22, 124])]
+pub struct ExtendAccountEvent {
+    #[layout(pubkey)]
+    pub account: String,
+    #[layout(pubkey)]
+    pub user: String,
+    pub current_size: u64,
+    pub new_size: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [237, 52, 123, 37, 245, 251, 72, 210])]
+pub struct SetCreatorEvent {
+    pub timestamp: i64,
+    #[layout(pubkey)]
+    pub mint: String,
+    #[layout(pubkey)]
+    pub bonding_curve: String,
+    #[layout(pubkey)]
+    pub creator: String,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [79, 172, 246, 49, 205, 91, 206, 232])]
+pub struct ClaimTokenIncentivesEvent {
+    #[layout(pubkey)]
+    pub user: String,
+    #[layout(pubkey)]
+    pub mint: String,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub total_claimed_tokens: u64,
+    pub current_sol_volume: u64,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [122, 2, 127, 1, 14, 191, 12, 175])]
+pub struct CollectCreatorFeeEvent {
+    pub timestamp: i64,
+    #[layout(pubkey)]
+    pub creator: String,
+    pub creator_fee: u64,
+}
+
+/// The leading fields only; the fee recipients and authorities that follow are not decoded.
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [223, 195, 159, 246, 62, 48, 143, 131])]
+pub struct SetParamsEvent {
+    pub initial_virtual_token_reserves: u64,
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_real_token_reserves: u64,
+    pub final_real_sol_reserves: u64,
+    pub token_total_supply: u64,
+    pub fee_basis_points: u64,
+    #[layout(pubkey)]
+    pub withdraw_authority: String,
+    pub enable_migrate: bool,
+    pub pool_migration_fee: u64,
+    pub creator_fee_basis_points: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::testing::{USER, pubkey};
+
+    const MINT: &str = "4k3Dyjzvzp8eMZWUXbBCjEvwSkkk59S5iCNLY3QrkX6R";
+    const FEE_RECIPIENT: &str = "62qc2CNXwrYqQScmEdiZFFAnJR262PxWEuNQtxfafNgV";
+    const CREATOR: &str = "5Y3ZhsLXDvk4xJSMKzPCWHs2xTA3KtVVr6Q8mG5jJ4Kn";
+
+    /// The `TradeEvent` of a 0.1 SOL buy as logged before the fee fields were added.
+    fn trade_event_payload() -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend(pubkey(MINT));
+        data.extend(100_000_000u64.to_le_bytes()); // sol_amount
+        data.extend(3_456_789_012_345u64.to_le_bytes()); // token_amount
+        data.push(1); // is_buy
+        data.extend(pubkey(USER));
+        data.extend(1_750_012_345i64.to_le_bytes());
+        for amount in [
+            31_100_000_000u64,     // virtual_sol_reserves
+            1_035_012_345_678_901, // virtual_token_reserves
+            1_100_000_000,         // real_sol_reserves
+            755_112_345_678_901,   // real_token_reserves
+        ] {
+            data.extend(amount.to_le_bytes());
+        }
+        data
+    }
+
+    fn push_fee_fields(data: &mut Vec<u8>) {
+        data.extend(pubkey(FEE_RECIPIENT));
+        data.extend(95u64.to_le_bytes()); // fee_basis_points
+        data.extend(950_000u64.to_le_bytes()); // fee
+        data.extend(pubkey(CREATOR));
+        data.extend(5u64.to_le_bytes()); // creator_fee_basis_points
+        data.extend(50_000u64.to_le_bytes()); // creator_fee
+    }
+
+    fn push_volume_fields(data: &mut Vec<u8>) {
+        data.push(1); // track_volume
+        data.extend(12_345_678u64.to_le_bytes()); // total_unclaimed_tokens
+        data.extend(0u64.to_le_bytes()); // total_claimed_tokens
+        data.extend(2_100_000_000u64.to_le_bytes()); // current_sol_volume
+        data.extend(1_750_000_000i64.to_le_bytes()); // last_update_timestamp
+    }
+
+    #[test]
+    fn decodes_a_trade_event_from_before_the_fees() {
+        let data = trade_event_payload();
+        assert_eq!(data.len(), 121);
+        assert_eq!(TradeEvent::MIN_LEN, 121);
+
+        let event = TradeEvent::decode(&data).unwrap();
+        assert_eq!(event.mint, MINT);
+        assert_eq!(event.sol_amount, 100_000_000);
+        assert_eq!(event.token_amount, 3_456_789_012_345);
+        assert!(event.is_buy);
+        assert_eq!(event.user, USER);
+        assert_eq!(event.timestamp, 1_750_012_345);
+        assert_eq!(event.virtual_sol_reserves, 31_100_000_000);
+        assert_eq!(event.real_token_reserves, 755_112_345_678_901);
+        assert_eq!(event.fee_recipient, None);
+        assert_eq!(event.creator_fee, None);
+        assert_eq!(event.track_volume, None);
+        assert_eq!(event.ix_name, None);
+    }
+
+    #[test]
+    fn decodes_a_trade_event_from_before_the_volume_tracking() {
+        let mut data = trade_event_payload();
+        push_fee_fields(&mut data);
+        assert_eq!(data.len(), 217);
+
+        let event = TradeEvent::decode(&data).unwrap();
+        assert_eq!(event.real_token_reserves, 755_112_345_678_901);
+        assert_eq!(event.fee_recipient.as_deref(), Some(FEE_RECIPIENT));
+        assert_eq!(event.fee_basis_points, Some(95));
+        assert_eq!(event.fee, Some(950_000));
+        assert_eq!(event.creator.as_deref(), Some(CREATOR));
+        assert_eq!(event.creator_fee_basis_points, Some(5));
+        assert_eq!(event.creator_fee, Some(50_000));
+        assert_eq!(event.track_volume, None);
+        assert_eq!(event.last_update_timestamp, None);
+        assert_eq!(event.ix_name, None);
+    }
+
+    #[test]
+    fn decodes_a_current_trade_event() {
+        let mut data = TradeEvent::DISCRIMINATOR.to_vec();
+        data.extend(trade_event_payload());
+        push_fee_fields(&mut data);
+        push_volume_fields(&mut data);
+        data.extend(3u32.to_le_bytes());
+        data.extend(b"buy");
+
+        let event = TradeEvent::decode_with_discriminator(&data).unwrap().unwrap();
+        assert_eq!(event.creator_fee, Some(50_000));
+        assert_eq!(event.track_volume, Some(true));
+        assert_eq!(event.total_unclaimed_tokens, Some(12_345_678));
+        assert_eq!(event.total_claimed_tokens, Some(0));
+        assert_eq!(event.current_sol_volume, Some(2_100_000_000));
+        assert_eq!(event.last_update_timestamp, Some(1_750_000_000));
+        assert_eq!(event.ix_name.as_deref(), Some("buy"));
+    }
+}
@@ -1,18 +1,29 @@
-use bs58;
+pub mod layouts;
+
 use instruction_parser::InstructionParser;
+use layouts::{
+    ClaimTokenIncentivesEvent, CollectCreatorFeeEvent, CompleteEvent,
+    CompletePumpAmmMigrationEvent, CreateArgs, ExtendAccountEvent, SetCreatorEvent,
+    SetParamsEvent, TradeEvent,
+};
 use types::{
-    DecodedEvent, DecodedPumpFunCreatePoolEvent, DecodedPumpFunEvent, DecodedPumpFunSwapEvent,
-    DecodedPumpFunSwapLog, StructuredInstruction, SwapEventAccounts, TransactionType,
+    DecodeError, DecodeErrorKind, DecodeReport, DecodedEvent, DecodedInstruction,
+    DecodedPumpAmmEvent, DecodedPumpFunClaimTokenIncentivesEvent,
+    DecodedPumpFunCollectCreatorFeeEvent, DecodedPumpFunCompleteEvent,
+    DecodedPumpFunCreatePoolEvent, DecodedPumpFunEvent, DecodedPumpFunExtendAccountEvent,
+    DecodedPumpFunMigrateEvent, DecodedPumpFunSetCreatorEvent, DecodedPumpFunSetParamsEvent,
+    DecodedPumpFunSwapEvent, DecodedPumpFunSwapLog, DecodedPumpFunWithdrawEvent,
+    StructuredInstruction, SwapEventAccounts, TransactionType,
+};
+use utils::{
+    find_event, find_token_transfer, get_account, get_account_lamports,
+    get_account_pre_lamports, get_token_transfers,
 };
 use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct PumpFunInstructionParser {}
 
 impl InstructionParser for PumpFunInstructionParser {
-    fn new() -> Self {
-        Self {}
-    }
-
     fn get_program_id(&self) -> &str {
         Self::PROGRAM_ID
     }
@@ -20,18 +31,58 @@ impl InstructionParser for PumpFunInstructionParser {
     fn decode_instructions(
         &self,
         instructions: Vec<StructuredInstruction>,
-        account_keys: &Vec<String>,
+        account_keys: &[String],
         transaction: &SubscribeUpdateTransaction,
-    ) -> Vec<DecodedEvent> {
-        instructions
-            .iter()
-            .filter_map(
-                |instruction| match self.decode_instruction(instruction, &account_keys) {
-                    Some(decoded_instruction) => Some(DecodedEvent::PumpFun(decoded_instruction)),
-                    None => None,
-                },
-            )
-            .collect()
+    ) -> DecodeReport {
+        let mut report = DecodeReport::default();
+        for instruction in instructions.iter() {
+            match self.decode_instruction(instruction, account_keys, transaction) {
+                Ok(Some(decoded_instruction)) => report.events.push(DecodedInstruction::new(
+                    Self::PROGRAM_ID,
+                    instruction,
+                    DecodedEvent::PumpFun(decoded_instruction),
+                )),
+                Ok(None) => {}
+                Err(kind) => report
+                    .errors
+                    .push(DecodeError::new(Self::PROGRAM_ID, instruction, 8, kind)),
+            }
+            // the buy that fills the curve reports it next to its trade
+            match Self::decode_complete_event(instruction) {
+                Ok(Some(decoded_complete)) => report.events.push(DecodedInstruction::new(
+                    Self::PROGRAM_ID,
+                    instruction,
+                    DecodedEvent::PumpFun(DecodedPumpFunEvent::Complete(decoded_complete)),
+                )),
+                Ok(None) => {}
+                Err(kind) => report
+                    .errors
+                    .push(DecodeError::new(Self::PROGRAM_ID, instruction, 8, kind)),
+            }
+        }
+        report
+    }
+
+    fn link_events(&self, report: &mut DecodeReport) {
+        for index in 0..report.events.len() {
+            if report.events[index].program_id != Self::PROGRAM_ID {
+                continue;
+            }
+            let pool_creation = report
+                .nested_event_indexes(&report.events[index].instruction_path)
+                .into_iter()
+                .find(|nested| {
+                    matches!(
+                        report.events[*nested].event,
+                        DecodedEvent::PumpAmm(DecodedPumpAmmEvent::CreatePool(_))
+                    )
+                });
+            if let DecodedEvent::PumpFun(DecodedPumpFunEvent::Migrate(migrate)) =
+                &mut report.events[index].event
+            {
+                migrate.pool_creation = pool_creation;
+            }
+        }
     }
 }
 
@@ -41,181 +92,347 @@ impl PumpFunInstructionParser {
     const POOL_CREATION_DISCRIMINATOR: [u8; 8] = [24, 30, 200, 40, 5, 28, 7, 119];
     const BUY_DISCRIMINATOR: [u8; 8] = [102, 6, 61, 18, 1, 218, 235, 234];
     const SELL_DISCRIMINATOR: [u8; 8] = [51, 230, 133, 164, 1, 127, 131, 173];
+    const CREATE_V2_DISCRIMINATOR: [u8; 8] = [214, 144, 76, 236, 95, 139, 49, 180];
+    const BUY_EXACT_SOL_IN_DISCRIMINATOR: [u8; 8] = [56, 252, 116, 8, 158, 223, 205, 95];
+    const EXTEND_ACCOUNT_DISCRIMINATOR: [u8; 8] = [234, 102, 194, 203, 150, 72, 62, 229];
+    const SET_CREATOR_DISCRIMINATOR: [u8; 8] = [254, 148, 255, 112, 207, 142, 170, 165];
+    const CLAIM_TOKEN_INCENTIVES_DISCRIMINATOR: [u8; 8] = [16, 4, 71, 28, 204, 1, 40, 27];
+    const COLLECT_CREATOR_FEE_DISCRIMINATOR: [u8; 8] = [20, 22, 86, 123, 198, 28, 219, 132];
+    const SET_PARAMS_DISCRIMINATOR: [u8; 8] = [27, 234, 178, 52, 147, 2, 187, 141];
+    const MIGRATE_DISCRIMINATOR: [u8; 8] = [155, 234, 231, 146, 236, 158, 162, 30];
+    const WITHDRAW_DISCRIMINATOR: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+
+    pub fn new() -> Self {
+        Self {}
+    }
 
     pub fn decode_instruction(
         &self,
         instruction: &StructuredInstruction,
-        account_keys: &Vec<String>,
-    ) -> Option<DecodedPumpFunEvent> {
-        let discriminator = &instruction.data[..8];
-        if discriminator == Self::BUY_DISCRIMINATOR {
-            return Some(DecodedPumpFunEvent::Swap(Self::decode_buy_event(
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> Result<Option<DecodedPumpFunEvent>, DecodeErrorKind> {
+        let discriminator = match instruction.data.get(..8) {
+            Some(discriminator) => discriminator,
+            None => return Ok(None),
+        };
+        if discriminator == Self::BUY_DISCRIMINATOR
+            || discriminator == Self::BUY_EXACT_SOL_IN_DISCRIMINATOR
+            || discriminator == Self::SELL_DISCRIMINATOR
+        {
+            return Ok(Some(DecodedPumpFunEvent::Swap(Self::decode_swap_event(instruction)?)));
+        } else if discriminator == Self::POOL_CREATION_DISCRIMINATOR {
+            return Ok(Some(DecodedPumpFunEvent::CreatePool(
+                Self::decode_pool_creation_event(instruction, account_keys)?,
+            )));
+        } else if discriminator == Self::CREATE_V2_DISCRIMINATOR {
+            return Ok(Some(DecodedPumpFunEvent::CreatePoolV2(
+                Self::decode_pool_creation_event(instruction, account_keys)?,
+            )));
+        } else if discriminator == Self::MIGRATE_DISCRIMINATOR {
+            return Ok(Some(DecodedPumpFunEvent::Migrate(Self::decode_migrate_event(
+                instruction,
+            )?)));
+        } else if discriminator == Self::WITHDRAW_DISCRIMINATOR {
+            return Ok(Some(DecodedPumpFunEvent::Withdraw(Self::decode_withdraw_event(
                 instruction,
+                account_keys,
+                transaction,
+            )?)));
+        } else if discriminator == Self::EXTEND_ACCOUNT_DISCRIMINATOR {
+            return Ok(Some(DecodedPumpFunEvent::ExtendAccount(
+                Self::decode_extend_account_event(instruction)?,
             )));
-        } else if discriminator == Self::SELL_DISCRIMINATOR {
-            return Some(DecodedPumpFunEvent::Swap(Self::decode_sell_event(
+        } else if discriminator == Self::SET_CREATOR_DISCRIMINATOR {
+            return Ok(Some(DecodedPumpFunEvent::SetCreator(Self::decode_set_creator_event(
                 instruction,
+            )?)));
+        } else if discriminator == Self::CLAIM_TOKEN_INCENTIVES_DISCRIMINATOR {
+            return Ok(Some(DecodedPumpFunEvent::ClaimTokenIncentives(
+                Self::decode_claim_token_incentives_event(instruction)?,
             )));
-        } else if discriminator == Self::POOL_CREATION_DISCRIMINATOR {
-            return Some(DecodedPumpFunEvent::CreatePool(
-                Self::decode_pool_creation_event(instruction, account_keys),
-            ));
-        }
-        None
-    }
-
-    pub fn decode_buy_event(instruction: &StructuredInstruction) -> DecodedPumpFunSwapEvent {
-        let last_ix = instruction.inner_instructions.last().unwrap();
-        let buy_log = if last_ix.data.len() < 233 {
-            instruction
-                .inner_instructions
-                .get(instruction.inner_instructions.len().wrapping_sub(2))
-                .unwrap()
-        } else {
-            last_ix
-        };
-        let decoded_buy_log = Self::decode_buy_log(&buy_log.data);
-
-        DecodedPumpFunSwapEvent {
-            accounts: SwapEventAccounts {
-                pool: decoded_buy_log.mint.clone(),
-                user: decoded_buy_log.user.clone(),
-                base_mint: decoded_buy_log.mint.clone(),
-                quote_mint: Self::WSOL_ADDRESS.to_string(),
-            },
-            mint_in: Self::WSOL_ADDRESS.to_string(),
-            mint_out: decoded_buy_log.mint.clone(),
-            amount_in: decoded_buy_log.sol_amount,
-            amount_out: decoded_buy_log.token_amount,
-            mint_in_reserve: decoded_buy_log.virtual_sol_reserves,
-            mint_out_reserve: decoded_buy_log.virtual_token_reserves,
-            event_type: TransactionType::Buy,
+        } else if discriminator == Self::COLLECT_CREATOR_FEE_DISCRIMINATOR {
+            return Ok(Some(DecodedPumpFunEvent::CollectCreatorFee(
+                Self::decode_collect_creator_fee_event(instruction)?,
+            )));
+        } else if discriminator == Self::SET_PARAMS_DISCRIMINATOR {
+            return Ok(Some(DecodedPumpFunEvent::SetParams(Self::decode_set_params_event(
+                instruction,
+            )?)));
         }
+        Ok(None)
     }
 
-    pub fn decode_buy_log(data: &[u8]) -> DecodedPumpFunSwapLog {
-        let mut offset: usize = 16;
-        let mint: String = bs58::encode(data[offset..offset + 32].to_vec()).into_string();
-        offset += 32;
-        let sol_amount = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 8;
-        let token_amount = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 9;
-        let user = bs58::encode(data[offset..offset + 32].to_vec()).into_string();
-        offset += 40;
-        let virtual_sol_reserves = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 8;
-        let virtual_token_reserves =
-            u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-
-        DecodedPumpFunSwapLog {
-            mint,
-            sol_amount,
-            token_amount,
-            user,
-            virtual_sol_reserves,
-            virtual_token_reserves,
-        }
-    }
+    /// Buys and sells both log a `TradeEvent` and are told apart by its `is_buy`.
+    pub fn decode_swap_event(
+        instruction: &StructuredInstruction,
+    ) -> Result<DecodedPumpFunSwapEvent, DecodeErrorKind> {
+        let data = find_event(instruction, &TradeEvent::DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)?;
+        let trade_log = Self::decode_trade_log(data)?;
 
-    pub fn decode_sell_event(instruction: &StructuredInstruction) -> DecodedPumpFunSwapEvent {
-        let inner_instructions = &instruction.inner_instructions;
-        let last_ix = &inner_instructions.last().unwrap();
-        let sell_log = if last_ix.data.len() < 233 {
-            inner_instructions
-                .get(inner_instructions.len().wrapping_sub(2))
-                .unwrap()
+        let wsol = Self::WSOL_ADDRESS.to_string();
+        let (
+            mint_in,
+            mint_out,
+            amount_in,
+            amount_out,
+            mint_in_reserve,
+            mint_out_reserve,
+            event_type,
+        ) = if trade_log.is_buy {
+            (
+                wsol.clone(),
+                trade_log.mint.clone(),
+                trade_log.sol_amount,
+                trade_log.token_amount,
+                trade_log.virtual_sol_reserves,
+                trade_log.virtual_token_reserves,
+                TransactionType::Buy,
+            )
         } else {
-            last_ix
+            (
+                trade_log.mint.clone(),
+                wsol.clone(),
+                trade_log.token_amount,
+                trade_log.sol_amount,
+                trade_log.virtual_token_reserves,
+                trade_log.virtual_sol_reserves,
+                TransactionType::Sell,
+            )
         };
-        let decoded_sell_log = Self::decode_sell_log(&sell_log.data);
 
-        DecodedPumpFunSwapEvent {
+        Ok(DecodedPumpFunSwapEvent {
             accounts: SwapEventAccounts {
-                pool: decoded_sell_log.mint.clone(),
-                user: decoded_sell_log.user.clone(),
-                base_mint: decoded_sell_log.mint.clone(),
-                quote_mint: Self::WSOL_ADDRESS.to_string(),
+                pool: trade_log.mint.clone(),
+                user: trade_log.user,
+                base_mint: trade_log.mint,
+                quote_mint: wsol,
             },
-            mint_in: decoded_sell_log.mint.clone(),
-            mint_out: Self::WSOL_ADDRESS.to_string(),
-            amount_in: decoded_sell_log.token_amount,
-            amount_out: decoded_sell_log.sol_amount,
-            mint_in_reserve: decoded_sell_log.virtual_token_reserves,
-            mint_out_reserve: decoded_sell_log.virtual_sol_reserves,
-            event_type: TransactionType::Sell,
-        }
+            mint_in,
+            mint_out,
+            amount_in,
+            amount_out,
+            mint_in_reserve,
+            mint_out_reserve,
+            is_buy: trade_log.is_buy,
+            timestamp: trade_log.timestamp,
+            real_sol_reserves: trade_log.real_sol_reserves,
+            real_token_reserves: trade_log.real_token_reserves,
+            fee_recipient: trade_log.fee_recipient,
+            fee_basis_points: trade_log.fee_basis_points,
+            fee: trade_log.fee,
+            creator: trade_log.creator,
+            creator_fee_basis_points: trade_log.creator_fee_basis_points,
+            creator_fee: trade_log.creator_fee,
+            track_volume: trade_log.track_volume,
+            total_unclaimed_tokens: trade_log.total_unclaimed_tokens,
+            total_claimed_tokens: trade_log.total_claimed_tokens,
+            current_sol_volume: trade_log.current_sol_volume,
+            last_update_timestamp: trade_log.last_update_timestamp,
+            ix_name: trade_log.ix_name,
+            event_type,
+        })
     }
 
-    fn decode_sell_log(data: &[u8]) -> DecodedPumpFunSwapLog {
-        let mut offset: usize = 16;
-        let mint: String = bs58::encode(data[offset..offset + 32].to_vec()).into_string();
-        offset += 32;
-        let sol_amount = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 8;
-        let token_amount = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 9;
-        let user = bs58::encode(data[offset..offset + 32].to_vec()).into_string();
-        offset += 40;
-        let virtual_sol_reserves = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-        offset += 8;
-        let virtual_token_reserves =
-            u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
-
-        DecodedPumpFunSwapLog {
-            mint,
-            sol_amount,
-            token_amount,
-            user,
-            virtual_sol_reserves,
-            virtual_token_reserves,
-        }
+    /// Decodes a `TradeEvent` from the data following its discriminator.
+    pub fn decode_trade_log(data: &[u8]) -> Result<DecodedPumpFunSwapLog, DecodeErrorKind> {
+        let event = TradeEvent::decode(data)?;
+        Ok(DecodedPumpFunSwapLog {
+            mint: event.mint,
+            sol_amount: event.sol_amount,
+            token_amount: event.token_amount,
+            is_buy: event.is_buy,
+            user: event.user,
+            timestamp: event.timestamp,
+            virtual_sol_reserves: event.virtual_sol_reserves,
+            virtual_token_reserves: event.virtual_token_reserves,
+            real_sol_reserves: event.real_sol_reserves,
+            real_token_reserves: event.real_token_reserves,
+            fee_recipient: event.fee_recipient,
+            fee_basis_points: event.fee_basis_points,
+            fee: event.fee,
+            creator: event.creator,
+            creator_fee_basis_points: event.creator_fee_basis_points,
+            creator_fee: event.creator_fee,
+            track_volume: event.track_volume,
+            total_unclaimed_tokens: event.total_unclaimed_tokens,
+            total_claimed_tokens: event.total_claimed_tokens,
+            current_sol_volume: event.current_sol_volume,
+            last_update_timestamp: event.last_update_timestamp,
+            ix_name: event.ix_name,
+        })
     }
 
+    /// `create` and `create_v2` share the leading args and accounts: [mint, mint_authority,
+    /// bonding_curve, associated_bonding_curve, ...]
     pub fn decode_pool_creation_event(
         instruction: &StructuredInstruction,
         account_keys: &[String],
-    ) -> DecodedPumpFunCreatePoolEvent {
-        let account_key_indexes: &Vec<u8> = &instruction.account_key_indexes;
-        let data: &Vec<u8> = &instruction.data;
-        if data.len() < 8 {
-            println!("instruction: {:?}", instruction)
-        }
-        let mut offset = 8;
-        let name_length = u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
-        if name_length > 100 {
-            println!("{:?}", data);
-        }
-        offset += 4;
-        let name = String::from_utf8(data[offset..offset + name_length as usize].to_vec()).unwrap();
-        offset += name_length as usize;
-        let symbol_length = u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
-        offset += 4;
-        let symbol = String::from_utf8(data[offset..offset + symbol_length as usize].to_vec()).unwrap();
-        offset += symbol_length as usize;
-        let uri_length = u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
-        offset += 4;
-        let uri = String::from_utf8(data[offset..offset + uri_length as usize].to_vec()).unwrap();
-        offset += uri_length as usize;
-        let coin_creator = bs58::encode(data[offset..offset + 32].to_vec()).into_string();
-
-        let mint =
-            bs58::encode(account_keys[account_key_indexes[0] as usize].clone()).into_string();
-        let bonding_curve =
-            bs58::encode(account_keys[account_key_indexes[2] as usize].clone()).into_string();
-        let associated_bonding_curve =
-            bs58::encode(account_keys[account_key_indexes[3] as usize].clone()).into_string();
-
-        DecodedPumpFunCreatePoolEvent {
-            name,
-            symbol,
-            uri,
-            creator: coin_creator,
+    ) -> Result<DecodedPumpFunCreatePoolEvent, DecodeErrorKind> {
+        let args = CreateArgs::decode(instruction.data.get(8..).unwrap_or_default())?;
+
+        let mint = get_account(instruction, account_keys, 0)?;
+        let bonding_curve = get_account(instruction, account_keys, 2)?;
+        let associated_bonding_curve = get_account(instruction, account_keys, 3)?;
+
+        Ok(DecodedPumpFunCreatePoolEvent {
+            name: args.name,
+            symbol: args.symbol,
+            uri: args.uri,
+            creator: args.creator,
             base_mint: mint,
             quote_mint: Self::WSOL_ADDRESS.to_string(),
             bonding_curve,
             associated_bonding_curve,
             event_type: TransactionType::CreatePool,
-        }
+        })
+    }
+
+    pub fn decode_complete_event(
+        instruction: &StructuredInstruction,
+    ) -> Result<Option<DecodedPumpFunCompleteEvent>, DecodeErrorKind> {
+        let data = match find_event(instruction, &CompleteEvent::DISCRIMINATOR) {
+            Some(data) => data,
+            None => return Ok(None),
+        };
+        let event = CompleteEvent::decode(data)?;
+        Ok(Some(DecodedPumpFunCompleteEvent {
+            user: event.user,
+            mint: event.mint,
+            bonding_curve: event.bonding_curve,
+            timestamp: event.timestamp,
+        }))
+    }
+
+    /// `migrate` creates the Pump AMM pool through a CPI into its `create_pool`.
+    pub fn decode_migrate_event(
+        instruction: &StructuredInstruction,
+    ) -> Result<DecodedPumpFunMigrateEvent, DecodeErrorKind> {
+        let event = find_event(instruction, &CompletePumpAmmMigrationEvent::DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)
+            .and_then(CompletePumpAmmMigrationEvent::decode)?;
+        Ok(DecodedPumpFunMigrateEvent {
+            user: event.user,
+            mint: event.mint,
+            bonding_curve: event.bonding_curve,
+            pool: event.pool,
+            mint_amount: event.mint_amount,
+            sol_amount: event.sol_amount,
+            pool_migration_fee: event.pool_migration_fee,
+            timestamp: event.timestamp,
+            pool_creation: None,
+            event_type: TransactionType::Migrate,
+        })
+    }
+
+    /// Accounts: [global, last_withdraw, mint, bonding_curve, associated_bonding_curve,
+    /// associated_user, user, system_program, token_program, rent, event_authority, program]
+    ///
+    /// The SOL leaves the curve by a direct lamport change, not a transfer.
+    pub fn decode_withdraw_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> Result<DecodedPumpFunWithdrawEvent, DecodeErrorKind> {
+        let mint = get_account(instruction, account_keys, 2)?;
+        let bonding_curve = get_account(instruction, account_keys, 3)?;
+        let associated_bonding_curve = get_account(instruction, account_keys, 4)?;
+        let user = get_account(instruction, account_keys, 6)?;
+        let transfers = get_token_transfers(instruction, account_keys)?;
+        let token_amount =
+            find_token_transfer(&transfers, &associated_bonding_curve, false)?.amount;
+        let bonding_curve_index = *instruction
+            .account_key_indexes
+            .get(3)
+            .ok_or(DecodeErrorKind::MissingAccount { position: 3 })?;
+        let sol_amount = get_account_pre_lamports(transaction, bonding_curve_index as u32)?
+            .saturating_sub(get_account_lamports(transaction, bonding_curve_index as u32)?);
+        Ok(DecodedPumpFunWithdrawEvent {
+            user,
+            mint,
+            bonding_curve,
+            token_amount,
+            sol_amount,
+            event_type: TransactionType::Withdraw,
+        })
+    }
+
+    pub fn decode_extend_account_event(
+        instruction: &StructuredInstruction,
+    ) -> Result<DecodedPumpFunExtendAccountEvent, DecodeErrorKind> {
+        let event = find_event(instruction, &ExtendAccountEvent::DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)
+            .and_then(ExtendAccountEvent::decode)?;
+        Ok(DecodedPumpFunExtendAccountEvent {
+            account: event.account,
+            user: event.user,
+            current_size: event.current_size,
+            new_size: event.new_size,
+            timestamp: event.timestamp,
+        })
+    }
+
+    pub fn decode_set_creator_event(
+        instruction: &StructuredInstruction,
+    ) -> Result<DecodedPumpFunSetCreatorEvent, DecodeErrorKind> {
+        let event = find_event(instruction, &SetCreatorEvent::DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)
+            .and_then(SetCreatorEvent::decode)?;
+        Ok(DecodedPumpFunSetCreatorEvent {
+            mint: event.mint,
+            bonding_curve: event.bonding_curve,
+            creator: event.creator,
+            timestamp: event.timestamp,
+        })
+    }
+
+    pub fn decode_claim_token_incentives_event(
+        instruction: &StructuredInstruction,
+    ) -> Result<DecodedPumpFunClaimTokenIncentivesEvent, DecodeErrorKind> {
+        let event = find_event(instruction, &ClaimTokenIncentivesEvent::DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)
+            .and_then(ClaimTokenIncentivesEvent::decode)?;
+        Ok(DecodedPumpFunClaimTokenIncentivesEvent {
+            user: event.user,
+            mint: event.mint,
+            amount: event.amount,
+            total_claimed_tokens: event.total_claimed_tokens,
+            current_sol_volume: event.current_sol_volume,
+            timestamp: event.timestamp,
+        })
+    }
+
+    pub fn decode_collect_creator_fee_event(
+        instruction: &StructuredInstruction,
+    ) -> Result<DecodedPumpFunCollectCreatorFeeEvent, DecodeErrorKind> {
+        let event = find_event(instruction, &CollectCreatorFeeEvent::DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)
+            .and_then(CollectCreatorFeeEvent::decode)?;
+        Ok(DecodedPumpFunCollectCreatorFeeEvent {
+            creator: event.creator,
+            creator_fee: event.creator_fee,
+            timestamp: event.timestamp,
+        })
+    }
+
+    pub fn decode_set_params_event(
+        instruction: &StructuredInstruction,
+    ) -> Result<DecodedPumpFunSetParamsEvent, DecodeErrorKind> {
+        let event = find_event(instruction, &SetParamsEvent::DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)
+            .and_then(SetParamsEvent::decode)?;
+        Ok(DecodedPumpFunSetParamsEvent {
+            initial_virtual_token_reserves: event.initial_virtual_token_reserves,
+            initial_virtual_sol_reserves: event.initial_virtual_sol_reserves,
+            initial_real_token_reserves: event.initial_real_token_reserves,
+            final_real_sol_reserves: event.final_real_sol_reserves,
+            token_total_supply: event.token_total_supply,
+            fee_basis_points: event.fee_basis_points,
+            withdraw_authority: event.withdraw_authority,
+            enable_migrate: event.enable_migrate,
+            pool_migration_fee: event.pool_migration_fee,
+            creator_fee_basis_points: event.creator_fee_basis_points,
+        })
     }
 }
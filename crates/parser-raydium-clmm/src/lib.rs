@@ -0,0 +1,544 @@
+use instruction_parser::InstructionParser;
+use types::{
+    DecodeError, DecodeErrorKind, DecodeReport, DecodedEvent, DecodedInstruction,
+    DecodedRaydiumClmmCreatePoolEvent, DecodedRaydiumClmmEvent,
+    DecodedRaydiumClmmLiquidityEvent, DecodedRaydiumClmmSwapEvent, DecodedRaydiumClmmSwapLog,
+    RaydiumSwapType, StructuredInstruction, TokenProgramTransfer,
+};
+use utils::{
+    find_event, find_token_transfer, get_account, get_instruction_token_balance,
+    get_token_transfers, read_bool, read_i32, read_pubkey, read_u64, read_u128,
+};
+use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
+
+#[derive(Clone, Debug, Default)]
+pub struct RaydiumClmmInstructionParser {}
+
+impl InstructionParser for RaydiumClmmInstructionParser {
+    fn get_program_id(&self) -> &str {
+        Self::PROGRAM_ID
+    }
+
+    fn decode_instructions(
+        &self,
+        instructions: Vec<StructuredInstruction>,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> DecodeReport {
+        let mut report = DecodeReport::default();
+        for instruction in instructions.iter() {
+            match self.decode_instruction(instruction, account_keys, transaction) {
+                Ok(Some(decoded_instruction)) => report.events.push(DecodedInstruction::new(
+                    Self::PROGRAM_ID,
+                    instruction,
+                    DecodedEvent::RaydiumClmm(decoded_instruction),
+                )),
+                Ok(None) => {}
+                Err(kind) => report
+                    .errors
+                    .push(DecodeError::new(Self::PROGRAM_ID, instruction, 8, kind)),
+            }
+        }
+        report
+    }
+}
+
+/// Where an open position instruction keeps the accounts we report.
+struct OpenPositionAccounts {
+    pool: usize,
+    personal_position: usize,
+    token_vault_0: usize,
+    token_vault_1: usize,
+}
+
+impl RaydiumClmmInstructionParser {
+    const PROGRAM_ID: &'static str = "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK";
+    const CREATE_POOL_DISCRIMINATOR: [u8; 8] = [233, 146, 209, 142, 207, 104, 64, 188];
+    const SWAP_DISCRIMINATOR: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+    const SWAP_V2_DISCRIMINATOR: [u8; 8] = [43, 4, 237, 11, 26, 201, 30, 98];
+    const OPEN_POSITION_DISCRIMINATOR: [u8; 8] = [135, 128, 47, 77, 15, 152, 240, 49];
+    const OPEN_POSITION_V2_DISCRIMINATOR: [u8; 8] = [77, 184, 74, 214, 112, 86, 241, 199];
+    const OPEN_POSITION_WITH_TOKEN22_NFT_DISCRIMINATOR: [u8; 8] =
+        [77, 255, 174, 82, 125, 29, 201, 46];
+    const INCREASE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [46, 156, 243, 118, 13, 205, 251, 178];
+    const INCREASE_LIQUIDITY_V2_DISCRIMINATOR: [u8; 8] = [133, 29, 89, 223, 69, 238, 176, 10];
+    const DECREASE_LIQUIDITY_DISCRIMINATOR: [u8; 8] = [160, 38, 208, 111, 104, 91, 44, 1];
+    const DECREASE_LIQUIDITY_V2_DISCRIMINATOR: [u8; 8] = [58, 127, 188, 62, 79, 82, 196, 96];
+    const SWAP_EVENT_DISCRIMINATOR: [u8; 8] = [64, 198, 205, 232, 38, 8, 113, 226];
+
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn decode_instruction(
+        &self,
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> Result<Option<DecodedRaydiumClmmEvent>, DecodeErrorKind> {
+        let discriminator = match instruction.data.get(..8) {
+            Some(discriminator) => discriminator,
+            None => return Ok(None),
+        };
+        if discriminator == Self::SWAP_DISCRIMINATOR {
+            return Ok(Some(DecodedRaydiumClmmEvent::Swap(Self::decode_swap_event(
+                instruction,
+                account_keys,
+                transaction,
+                false,
+            )?)));
+        } else if discriminator == Self::SWAP_V2_DISCRIMINATOR {
+            return Ok(Some(DecodedRaydiumClmmEvent::Swap(Self::decode_swap_event(
+                instruction,
+                account_keys,
+                transaction,
+                true,
+            )?)));
+        } else if discriminator == Self::CREATE_POOL_DISCRIMINATOR {
+            return Ok(Some(DecodedRaydiumClmmEvent::CreatePool(
+                Self::decode_pool_creation_event(instruction, account_keys)?,
+            )));
+        } else if discriminator == Self::OPEN_POSITION_DISCRIMINATOR
+            || discriminator == Self::OPEN_POSITION_V2_DISCRIMINATOR
+        {
+            // [payer, position_nft_owner, position_nft_mint, position_nft_account,
+            // metadata_account, pool_state, protocol_position, tick_array_lower,
+            // tick_array_upper, personal_position, token_account_0, token_account_1,
+            // token_vault_0, token_vault_1, ...]
+            return Ok(Some(DecodedRaydiumClmmEvent::OpenPosition(
+                Self::decode_open_position_event(
+                    instruction,
+                    account_keys,
+                    transaction,
+                    OpenPositionAccounts {
+                        pool: 5,
+                        personal_position: 9,
+                        token_vault_0: 12,
+                        token_vault_1: 13,
+                    },
+                )?,
+            )));
+        } else if discriminator == Self::OPEN_POSITION_WITH_TOKEN22_NFT_DISCRIMINATOR {
+            // the same accounts without metadata_account, the NFT carries its own metadata
+            return Ok(Some(DecodedRaydiumClmmEvent::OpenPosition(
+                Self::decode_open_position_event(
+                    instruction,
+                    account_keys,
+                    transaction,
+                    OpenPositionAccounts {
+                        pool: 4,
+                        personal_position: 8,
+                        token_vault_0: 11,
+                        token_vault_1: 12,
+                    },
+                )?,
+            )));
+        } else if discriminator == Self::INCREASE_LIQUIDITY_DISCRIMINATOR
+            || discriminator == Self::INCREASE_LIQUIDITY_V2_DISCRIMINATOR
+        {
+            return Ok(Some(DecodedRaydiumClmmEvent::IncreaseLiquidity(
+                Self::decode_increase_liquidity_event(instruction, account_keys, transaction)?,
+            )));
+        } else if discriminator == Self::DECREASE_LIQUIDITY_DISCRIMINATOR
+            || discriminator == Self::DECREASE_LIQUIDITY_V2_DISCRIMINATOR
+        {
+            return Ok(Some(DecodedRaydiumClmmEvent::DecreaseLiquidity(
+                Self::decode_decrease_liquidity_event(instruction, account_keys, transaction)?,
+            )));
+        }
+        Ok(None)
+    }
+
+    /// `swap` and `swap_v2` share their leading accounts:
+    /// [payer, amm_config, pool_state, input_token_account, output_token_account,
+    /// input_vault, output_vault, observation_state, ...]
+    pub fn decode_swap_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+        v2: bool,
+    ) -> Result<DecodedRaydiumClmmSwapEvent, DecodeErrorKind> {
+        let user = get_account(instruction, account_keys, 0)?;
+        let pool = get_account(instruction, account_keys, 2)?;
+        let (mint_in, mint_in_reserve, in_decimals) =
+            get_instruction_token_balance(instruction, transaction, 5)?;
+        let (mint_out, mint_out_reserve, out_decimals) =
+            get_instruction_token_balance(instruction, transaction, 6)?;
+
+        // args: amount, other_amount_threshold, sqrt_price_limit_x64, is_base_input
+        let is_base_input = read_bool(&instruction.data, 40)?;
+        let swap_type = match (is_base_input, v2) {
+            (true, false) => RaydiumSwapType::BaseIn,
+            (false, false) => RaydiumSwapType::BaseOut,
+            (true, true) => RaydiumSwapType::BaseInV2,
+            (false, true) => RaydiumSwapType::BaseOutV2,
+        };
+
+        // emitted with `emit!`, so it is found in the instruction's `Program data:` logs
+        let swap_log = find_event(instruction, &Self::SWAP_EVENT_DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)?;
+        let decoded_swap_log = Self::decode_swap_log(swap_log)?;
+        let (amount_in, amount_out) = if decoded_swap_log.zero_for_one {
+            (decoded_swap_log.amount_0, decoded_swap_log.amount_1)
+        } else {
+            (decoded_swap_log.amount_1, decoded_swap_log.amount_0)
+        };
+
+        Ok(DecodedRaydiumClmmSwapEvent {
+            pool,
+            user,
+            mint_in,
+            mint_out,
+            in_decimals,
+            out_decimals,
+            mint_in_reserve,
+            mint_out_reserve,
+            amount_in,
+            amount_out,
+            zero_for_one: decoded_swap_log.zero_for_one,
+            sqrt_price_x64: decoded_swap_log.sqrt_price_x64,
+            liquidity: decoded_swap_log.liquidity,
+            tick: decoded_swap_log.tick,
+            swap_type,
+        })
+    }
+
+    pub fn decode_swap_log(data: &[u8]) -> Result<DecodedRaydiumClmmSwapLog, DecodeErrorKind> {
+        let mut offset: usize = 0;
+        let pool = read_pubkey(data, offset)?;
+        offset += 32;
+        let sender = read_pubkey(data, offset)?;
+        // token_account_0, token_account_1
+        offset += 96;
+        let amount_0 = read_u64(data, offset)?;
+        offset += 8;
+        let transfer_fee_0 = read_u64(data, offset)?;
+        offset += 8;
+        let amount_1 = read_u64(data, offset)?;
+        offset += 8;
+        let transfer_fee_1 = read_u64(data, offset)?;
+        offset += 8;
+        let zero_for_one = read_bool(data, offset)?;
+        offset += 1;
+        let sqrt_price_x64 = read_u128(data, offset)?;
+        offset += 16;
+        let liquidity = read_u128(data, offset)?;
+        offset += 16;
+        let tick = read_i32(data, offset)?;
+
+        Ok(DecodedRaydiumClmmSwapLog {
+            pool,
+            sender,
+            amount_0,
+            transfer_fee_0,
+            amount_1,
+            transfer_fee_1,
+            zero_for_one,
+            sqrt_price_x64,
+            liquidity,
+            tick,
+        })
+    }
+
+    pub fn decode_pool_creation_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Result<DecodedRaydiumClmmCreatePoolEvent, DecodeErrorKind> {
+        let creator = get_account(instruction, account_keys, 0)?;
+        let pool = get_account(instruction, account_keys, 2)?;
+        let token_0_mint = get_account(instruction, account_keys, 3)?;
+        let token_1_mint = get_account(instruction, account_keys, 4)?;
+        let token_0_vault = get_account(instruction, account_keys, 5)?;
+        let token_1_vault = get_account(instruction, account_keys, 6)?;
+
+        let sqrt_price_x64 = read_u128(&instruction.data, 8)?;
+        let open_time = read_u64(&instruction.data, 24)?;
+
+        Ok(DecodedRaydiumClmmCreatePoolEvent {
+            pool,
+            creator,
+            token_0_mint,
+            token_1_mint,
+            token_0_vault,
+            token_1_vault,
+            sqrt_price_x64,
+            open_time,
+        })
+    }
+
+    /// Amount moved into (`to_vault`) or out of a vault; the program skips zero transfers.
+    fn get_vault_amount(transfers: &[TokenProgramTransfer], vault: &str, to_vault: bool) -> u64 {
+        find_token_transfer(transfers, vault, to_vault)
+            .map(|transfer| transfer.amount)
+            .unwrap_or(0)
+    }
+
+    /// `open_position`, `open_position_v2` and `open_position_with_token22_nft` share their
+    /// leading args; the v2 ones append with_metadata and base_flag.
+    fn decode_open_position_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+        accounts: OpenPositionAccounts,
+    ) -> Result<DecodedRaydiumClmmLiquidityEvent, DecodeErrorKind> {
+        let user = get_account(instruction, account_keys, 0)?;
+        let pool = get_account(instruction, account_keys, accounts.pool)?;
+        let position = get_account(instruction, account_keys, accounts.personal_position)?;
+        let token_0_vault = get_account(instruction, account_keys, accounts.token_vault_0)?;
+        let token_1_vault = get_account(instruction, account_keys, accounts.token_vault_1)?;
+        let (token_0_mint, _, _) =
+            get_instruction_token_balance(instruction, transaction, accounts.token_vault_0)?;
+        let (token_1_mint, _, _) =
+            get_instruction_token_balance(instruction, transaction, accounts.token_vault_1)?;
+
+        // args: tick_lower_index, tick_upper_index, tick_array_lower_start_index,
+        // tick_array_upper_start_index, liquidity, amount_0_max, amount_1_max
+        let data = &instruction.data;
+        let tick_lower_index = read_i32(data, 8)?;
+        let tick_upper_index = read_i32(data, 12)?;
+        let liquidity = read_u128(data, 24)?;
+
+        let transfers = get_token_transfers(instruction, account_keys)?;
+
+        Ok(DecodedRaydiumClmmLiquidityEvent {
+            pool,
+            user,
+            position,
+            token_0_mint,
+            token_1_mint,
+            liquidity,
+            amount_0: Self::get_vault_amount(&transfers, &token_0_vault, true),
+            amount_1: Self::get_vault_amount(&transfers, &token_1_vault, true),
+            tick_lower_index: Some(tick_lower_index),
+            tick_upper_index: Some(tick_upper_index),
+        })
+    }
+
+    /// `increase_liquidity` and `increase_liquidity_v2` share their leading accounts:
+    /// [nft_owner, nft_account, pool_state, protocol_position, personal_position,
+    /// tick_array_lower, tick_array_upper, token_account_0, token_account_1, token_vault_0,
+    /// token_vault_1, ...]
+    pub fn decode_increase_liquidity_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> Result<DecodedRaydiumClmmLiquidityEvent, DecodeErrorKind> {
+        let user = get_account(instruction, account_keys, 0)?;
+        let pool = get_account(instruction, account_keys, 2)?;
+        let position = get_account(instruction, account_keys, 4)?;
+        let token_0_vault = get_account(instruction, account_keys, 9)?;
+        let token_1_vault = get_account(instruction, account_keys, 10)?;
+        let (token_0_mint, _, _) = get_instruction_token_balance(instruction, transaction, 9)?;
+        let (token_1_mint, _, _) = get_instruction_token_balance(instruction, transaction, 10)?;
+        let liquidity = read_u128(&instruction.data, 8)?;
+
+        let transfers = get_token_transfers(instruction, account_keys)?;
+
+        Ok(DecodedRaydiumClmmLiquidityEvent {
+            pool,
+            user,
+            position,
+            token_0_mint,
+            token_1_mint,
+            liquidity,
+            amount_0: Self::get_vault_amount(&transfers, &token_0_vault, true),
+            amount_1: Self::get_vault_amount(&transfers, &token_1_vault, true),
+            tick_lower_index: None,
+            tick_upper_index: None,
+        })
+    }
+
+    /// `decrease_liquidity` and `decrease_liquidity_v2` share their leading accounts:
+    /// [nft_owner, nft_account, personal_position, pool_state, protocol_position,
+    /// token_vault_0, token_vault_1, tick_array_lower, tick_array_upper,
+    /// recipient_token_account_0, recipient_token_account_1, ...]
+    pub fn decode_decrease_liquidity_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> Result<DecodedRaydiumClmmLiquidityEvent, DecodeErrorKind> {
+        let user = get_account(instruction, account_keys, 0)?;
+        let position = get_account(instruction, account_keys, 2)?;
+        let pool = get_account(instruction, account_keys, 3)?;
+        let token_0_vault = get_account(instruction, account_keys, 5)?;
+        let token_1_vault = get_account(instruction, account_keys, 6)?;
+        let (token_0_mint, _, _) = get_instruction_token_balance(instruction, transaction, 5)?;
+        let (token_1_mint, _, _) = get_instruction_token_balance(instruction, transaction, 6)?;
+        let liquidity = read_u128(&instruction.data, 8)?;
+
+        let transfers = get_token_transfers(instruction, account_keys)?;
+
+        Ok(DecodedRaydiumClmmLiquidityEvent {
+            pool,
+            user,
+            position,
+            token_0_mint,
+            token_1_mint,
+            liquidity,
+            amount_0: Self::get_vault_amount(&transfers, &token_0_vault, false),
+            amount_1: Self::get_vault_amount(&transfers, &token_1_vault, false),
+            tick_lower_index: None,
+            tick_upper_index: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::TOKEN_PROGRAM_ID;
+    use utils::testing::{
+        USDC_MINT, USER, WSOL_MINT, account_keys, pubkey, token_balances_transaction,
+    };
+
+    const POOL: &str = "3ucNos4NbumPLZNWztqGHNFFgkHeRMBQAVemeeomsUxv";
+    const POSITION: &str = "3Kzk1Fp3JH7ndXnBV2fDTf8e1qAr1mCdZTqXsYEr8Y2Q";
+
+    /// The `SwapEvent` of 1 SOL sold for 151.23 USDC.
+    fn swap_log() -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend(pubkey(POOL));
+        data.extend(pubkey(USER));
+        data.extend([0; 64]); // token_account_0, token_account_1
+        data.extend(1_000_000_000u64.to_le_bytes()); // amount_0
+        data.extend(0u64.to_le_bytes()); // transfer_fee_0
+        data.extend(151_234_567u64.to_le_bytes()); // amount_1
+        data.extend(0u64.to_le_bytes()); // transfer_fee_1
+        data.push(1); // zero_for_one
+        data.extend(7_171_234_567_890_123_456u128.to_le_bytes()); // sqrt_price_x64
+        data.extend(98_765_432_109_876u128.to_le_bytes()); // liquidity
+        data.extend((-18_870i32).to_le_bytes()); // tick
+        data
+    }
+
+    #[test]
+    fn decodes_a_swap_log() {
+        let log = RaydiumClmmInstructionParser::decode_swap_log(&swap_log()).unwrap();
+        assert_eq!(log.pool, POOL);
+        assert_eq!(log.sender, USER);
+        assert_eq!(log.amount_0, 1_000_000_000);
+        assert_eq!(log.transfer_fee_0, 0);
+        assert_eq!(log.amount_1, 151_234_567);
+        assert_eq!(log.transfer_fee_1, 0);
+        assert!(log.zero_for_one);
+        assert_eq!(log.sqrt_price_x64, 7_171_234_567_890_123_456);
+        assert_eq!(log.liquidity, 98_765_432_109_876);
+        assert_eq!(log.tick, -18_870);
+    }
+
+    #[test]
+    fn decodes_a_swap_from_its_logged_event() {
+        let mut account_keys = account_keys(10);
+        account_keys[0] = USER.to_string();
+        account_keys[2] = POOL.to_string();
+        account_keys.push(RaydiumClmmInstructionParser::PROGRAM_ID.to_string()); // 10
+        let transaction = token_balances_transaction(&[
+            (5, WSOL_MINT, 48_000_000_000_000, 9),
+            (6, USDC_MINT, 7_200_000_000_000, 6),
+        ]);
+        let mut data = RaydiumClmmInstructionParser::SWAP_DISCRIMINATOR.to_vec();
+        data.extend(1_000_000_000u64.to_le_bytes()); // amount
+        data.extend(150_000_000u64.to_le_bytes()); // other_amount_threshold
+        data.extend(0u128.to_le_bytes()); // sqrt_price_limit_x64
+        data.push(1); // is_base_input
+        let mut event = RaydiumClmmInstructionParser::SWAP_EVENT_DISCRIMINATOR.to_vec();
+        event.extend(swap_log());
+        let swap = StructuredInstruction {
+            account_key_indexes: (0..10).collect(),
+            program_id_index: 10,
+            data,
+            inner_instructions: Vec::new(),
+            stack_height: 1,
+            path: vec![0],
+            program_data_logs: vec![event],
+        };
+
+        let Some(DecodedRaydiumClmmEvent::Swap(event)) = RaydiumClmmInstructionParser::new()
+            .decode_instruction(&swap, &account_keys, &transaction)
+            .unwrap()
+        else {
+            panic!("expected a swap");
+        };
+        assert_eq!(event.pool, POOL);
+        assert_eq!(event.user, USER);
+        assert_eq!(event.mint_in, WSOL_MINT);
+        assert_eq!(event.mint_out, USDC_MINT);
+        assert_eq!(event.amount_in, 1_000_000_000);
+        assert_eq!(event.amount_out, 151_234_567);
+        assert_eq!(event.mint_in_reserve, 48_000_000_000_000);
+        assert_eq!(event.mint_out_reserve, 7_200_000_000_000);
+        assert_eq!(event.tick, -18_870);
+        assert!(matches!(event.swap_type, RaydiumSwapType::BaseIn));
+    }
+
+    #[test]
+    fn decodes_an_open_position_with_token22_nft() {
+        let mut account_keys = account_keys(20);
+        account_keys[0] = USER.to_string();
+        account_keys[4] = POOL.to_string();
+        account_keys[8] = POSITION.to_string();
+        account_keys.push(TOKEN_PROGRAM_ID.to_string()); // 20
+        account_keys.push(RaydiumClmmInstructionParser::PROGRAM_ID.to_string()); // 21
+        let transaction = token_balances_transaction(&[
+            (11, WSOL_MINT, 48_000_000_000_000, 9),
+            (12, USDC_MINT, 7_200_000_000_000, 6),
+        ]);
+
+        let mut data = RaydiumClmmInstructionParser::OPEN_POSITION_WITH_TOKEN22_NFT_DISCRIMINATOR
+            .to_vec();
+        for tick in [-19_200i32, -18_600, -21_600, -18_000] {
+            // tick_lower_index, tick_upper_index, tick_array_lower_start_index,
+            // tick_array_upper_start_index
+            data.extend(tick.to_le_bytes());
+        }
+        data.extend(12_345_678_901u128.to_le_bytes()); // liquidity
+        data.extend(2_000_000_000u64.to_le_bytes()); // amount_0_max
+        data.extend(300_000_000u64.to_le_bytes()); // amount_1_max
+        data.extend([1, 0]); // with_metadata, base_flag
+        // transfer_checked: [source, mint, destination, authority]
+        let transfer = |index, source, mint, destination, amount: u64, decimals| {
+            let mut data = vec![12];
+            data.extend(amount.to_le_bytes());
+            data.push(decimals);
+            StructuredInstruction {
+                account_key_indexes: vec![source, mint, destination, 0],
+                program_id_index: 20,
+                data,
+                inner_instructions: Vec::new(),
+                stack_height: 2,
+                path: vec![0, index],
+                program_data_logs: Vec::new(),
+            }
+        };
+        let open = StructuredInstruction {
+            account_key_indexes: (0..20).collect(),
+            program_id_index: 21,
+            data,
+            inner_instructions: vec![
+                transfer(0, 9, 13, 11, 1_000_000_000, 9),
+                transfer(1, 10, 14, 12, 148_765_432, 6),
+            ],
+            stack_height: 1,
+            path: vec![0],
+            program_data_logs: Vec::new(),
+        };
+
+        let Some(DecodedRaydiumClmmEvent::OpenPosition(event)) =
+            RaydiumClmmInstructionParser::new()
+                .decode_instruction(&open, &account_keys, &transaction)
+                .unwrap()
+        else {
+            panic!("expected an opened position");
+        };
+        assert_eq!(event.pool, POOL);
+        assert_eq!(event.user, USER);
+        assert_eq!(event.position, POSITION);
+        assert_eq!(event.token_0_mint, WSOL_MINT);
+        assert_eq!(event.token_1_mint, USDC_MINT);
+        assert_eq!(event.liquidity, 12_345_678_901);
+        assert_eq!(event.amount_0, 1_000_000_000);
+        assert_eq!(event.amount_1, 148_765_432);
+        assert_eq!(event.tick_lower_index, Some(-19_200));
+        assert_eq!(event.tick_upper_index, Some(-18_600));
+    }
+}
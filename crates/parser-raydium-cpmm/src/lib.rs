@@ -0,0 +1,250 @@
+use instruction_parser::InstructionParser;
+use types::{
+    DecodeError, DecodeErrorKind, DecodeReport, DecodedEvent, DecodedInstruction,
+    DecodedRaydiumCpmmCreatePoolEvent, DecodedRaydiumCpmmDepositEvent, DecodedRaydiumCpmmEvent,
+    DecodedRaydiumCpmmWithdrawEvent, RaydiumCpmmSwap, StructuredInstruction,
+};
+use utils::{
+    find_token_transfer, get_account, get_instruction_token_balance,
+    get_token_mint_or_burn_amount, get_token_transfers, read_u64,
+};
+use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
+
+#[derive(Clone, Debug, Default)]
+pub struct RaydiumCpmmInstructionParser {}
+
+impl InstructionParser for RaydiumCpmmInstructionParser {
+    fn get_program_id(&self) -> &str {
+        Self::PROGRAM_ID
+    }
+
+    fn decode_instructions(
+        &self,
+        instructions: Vec<StructuredInstruction>,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> DecodeReport {
+        let mut report = DecodeReport::default();
+        for instruction in instructions.iter() {
+            match self.decode_instruction(instruction, account_keys, transaction) {
+                Ok(Some(decoded_instruction)) => report.events.push(DecodedInstruction::new(
+                    Self::PROGRAM_ID,
+                    instruction,
+                    DecodedEvent::RaydiumCpmm(decoded_instruction),
+                )),
+                Ok(None) => {}
+                Err(kind) => report
+                    .errors
+                    .push(DecodeError::new(Self::PROGRAM_ID, instruction, 8, kind)),
+            }
+        }
+        report
+    }
+}
+
+impl RaydiumCpmmInstructionParser {
+    const PROGRAM_ID: &'static str = "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C";
+    const PLATFORM: &'static str = "raydium-cpmm";
+    const INITIALIZE_DISCRIMINATOR: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
+    const DEPOSIT_DISCRIMINATOR: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+    const WITHDRAW_DISCRIMINATOR: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+    const SWAP_BASE_INPUT_DISCRIMINATOR: [u8; 8] = [143, 190, 90, 218, 196, 30, 51, 222];
+    const SWAP_BASE_OUTPUT_DISCRIMINATOR: [u8; 8] = [55, 217, 98, 86, 163, 74, 180, 173];
+
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn decode_instruction(
+        &self,
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> Result<Option<DecodedRaydiumCpmmEvent>, DecodeErrorKind> {
+        let discriminator = match instruction.data.get(..8) {
+            Some(discriminator) => discriminator,
+            None => return Ok(None),
+        };
+        if discriminator == Self::SWAP_BASE_INPUT_DISCRIMINATOR {
+            return Ok(Some(DecodedRaydiumCpmmEvent::Swap(Self::decode_swap_event(
+                instruction,
+                account_keys,
+                transaction,
+                "swap_base_input",
+            )?)));
+        } else if discriminator == Self::SWAP_BASE_OUTPUT_DISCRIMINATOR {
+            return Ok(Some(DecodedRaydiumCpmmEvent::Swap(Self::decode_swap_event(
+                instruction,
+                account_keys,
+                transaction,
+                "swap_base_output",
+            )?)));
+        } else if discriminator == Self::INITIALIZE_DISCRIMINATOR {
+            return Ok(Some(DecodedRaydiumCpmmEvent::CreatePool(
+                Self::decode_pool_creation_event(instruction, account_keys)?,
+            )));
+        } else if discriminator == Self::DEPOSIT_DISCRIMINATOR {
+            return Ok(Some(DecodedRaydiumCpmmEvent::Deposit(Self::decode_deposit_event(
+                instruction,
+                account_keys,
+                transaction,
+            )?)));
+        } else if discriminator == Self::WITHDRAW_DISCRIMINATOR {
+            return Ok(Some(DecodedRaydiumCpmmEvent::Withdraw(Self::decode_withdraw_event(
+                instruction,
+                account_keys,
+                transaction,
+            )?)));
+        }
+        Ok(None)
+    }
+
+    /// Both swap instructions share their accounts:
+    /// [payer, authority, amm_config, pool_state, input_token_account, output_token_account,
+    /// input_vault, output_vault, input_token_program, output_token_program, input_token_mint,
+    /// output_token_mint, observation_state]
+    ///
+    /// The transaction type is the name of the instruction, as the pool has no base and quote
+    /// side to tell a buy from a sell.
+    pub fn decode_swap_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+        transaction_type: &str,
+    ) -> Result<RaydiumCpmmSwap, DecodeErrorKind> {
+        let pool = get_account(instruction, account_keys, 3)?;
+        let input_vault = get_account(instruction, account_keys, 6)?;
+        let output_vault = get_account(instruction, account_keys, 7)?;
+        let mint_in = get_account(instruction, account_keys, 10)?;
+        let mint_out = get_account(instruction, account_keys, 11)?;
+        let (_, mint_in_reserve, in_decimals) =
+            get_instruction_token_balance(instruction, transaction, 6)?;
+        let (_, mint_out_reserve, out_decimals) =
+            get_instruction_token_balance(instruction, transaction, 7)?;
+
+        let transfers = get_token_transfers(instruction, account_keys)?;
+        let amount_in = find_token_transfer(&transfers, &input_vault, true)?.amount;
+        let amount_out = find_token_transfer(&transfers, &output_vault, false)?.amount;
+
+        Ok(RaydiumCpmmSwap {
+            pool,
+            mint_in,
+            mint_out,
+            platform: Self::PLATFORM.to_string(),
+            transaction_type: transaction_type.to_string(),
+            in_decimals,
+            out_decimals,
+            mint_in_reserve,
+            mint_out_reserve,
+            amount_in,
+            amount_out,
+        })
+    }
+
+    pub fn decode_pool_creation_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Result<DecodedRaydiumCpmmCreatePoolEvent, DecodeErrorKind> {
+        let creator = get_account(instruction, account_keys, 0)?;
+        let pool = get_account(instruction, account_keys, 3)?;
+        let token_0_mint = get_account(instruction, account_keys, 4)?;
+        let token_1_mint = get_account(instruction, account_keys, 5)?;
+        let lp_mint = get_account(instruction, account_keys, 6)?;
+        let token_0_vault = get_account(instruction, account_keys, 10)?;
+        let token_1_vault = get_account(instruction, account_keys, 11)?;
+
+        // args: init_amount_0, init_amount_1, open_time
+        let open_time = read_u64(&instruction.data, 24)?;
+
+        let transfers = get_token_transfers(instruction, account_keys)?;
+        let token_0_amount = find_token_transfer(&transfers, &token_0_vault, true)?.amount;
+        let token_1_amount = find_token_transfer(&transfers, &token_1_vault, true)?.amount;
+        let lp_amount = get_token_mint_or_burn_amount(instruction, account_keys, false)?;
+
+        Ok(DecodedRaydiumCpmmCreatePoolEvent {
+            pool,
+            creator,
+            token_0_mint,
+            token_1_mint,
+            lp_mint,
+            token_0_vault,
+            token_1_vault,
+            token_0_amount,
+            token_1_amount,
+            lp_amount,
+            open_time,
+        })
+    }
+
+    /// Deposit and withdraw share their first accounts:
+    /// [owner, authority, pool_state, owner_lp_token, token_0_account, token_1_account,
+    /// token_0_vault, token_1_vault, token_program, token_program_2022, vault_0_mint,
+    /// vault_1_mint, lp_mint]
+    pub fn decode_deposit_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> Result<DecodedRaydiumCpmmDepositEvent, DecodeErrorKind> {
+        let user = get_account(instruction, account_keys, 0)?;
+        let pool = get_account(instruction, account_keys, 2)?;
+        let token_0_vault = get_account(instruction, account_keys, 6)?;
+        let token_1_vault = get_account(instruction, account_keys, 7)?;
+        let token_0_mint = get_account(instruction, account_keys, 10)?;
+        let token_1_mint = get_account(instruction, account_keys, 11)?;
+        let lp_mint = get_account(instruction, account_keys, 12)?;
+        let (_, pool_token_0_reserves, _) = get_instruction_token_balance(instruction, transaction, 6)?;
+        let (_, pool_token_1_reserves, _) = get_instruction_token_balance(instruction, transaction, 7)?;
+
+        let transfers = get_token_transfers(instruction, account_keys)?;
+        let token_0_amount_in = find_token_transfer(&transfers, &token_0_vault, true)?.amount;
+        let token_1_amount_in = find_token_transfer(&transfers, &token_1_vault, true)?.amount;
+        let lp_amount_out = get_token_mint_or_burn_amount(instruction, account_keys, false)?;
+
+        Ok(DecodedRaydiumCpmmDepositEvent {
+            pool,
+            user,
+            token_0_mint,
+            token_1_mint,
+            lp_mint,
+            token_0_amount_in,
+            token_1_amount_in,
+            lp_amount_out,
+            pool_token_0_reserves,
+            pool_token_1_reserves,
+        })
+    }
+
+    pub fn decode_withdraw_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> Result<DecodedRaydiumCpmmWithdrawEvent, DecodeErrorKind> {
+        let user = get_account(instruction, account_keys, 0)?;
+        let pool = get_account(instruction, account_keys, 2)?;
+        let token_0_vault = get_account(instruction, account_keys, 6)?;
+        let token_1_vault = get_account(instruction, account_keys, 7)?;
+        let token_0_mint = get_account(instruction, account_keys, 10)?;
+        let token_1_mint = get_account(instruction, account_keys, 11)?;
+        let lp_mint = get_account(instruction, account_keys, 12)?;
+        let (_, pool_token_0_reserves, _) = get_instruction_token_balance(instruction, transaction, 6)?;
+        let (_, pool_token_1_reserves, _) = get_instruction_token_balance(instruction, transaction, 7)?;
+
+        let transfers = get_token_transfers(instruction, account_keys)?;
+        let token_0_amount_out = find_token_transfer(&transfers, &token_0_vault, false)?.amount;
+        let token_1_amount_out = find_token_transfer(&transfers, &token_1_vault, false)?.amount;
+        let lp_amount_in = get_token_mint_or_burn_amount(instruction, account_keys, true)?;
+
+        Ok(DecodedRaydiumCpmmWithdrawEvent {
+            pool,
+            user,
+            token_0_mint,
+            token_1_mint,
+            lp_mint,
+            token_0_amount_out,
+            token_1_amount_out,
+            lp_amount_in,
+            pool_token_0_reserves,
+            pool_token_1_reserves,
+        })
+    }
+}
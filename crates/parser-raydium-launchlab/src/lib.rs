@@ -0,0 +1,390 @@
+use instruction_parser::InstructionParser;
+use types::{
+    DecodeError, DecodeErrorKind, DecodeReport, DecodedEvent, DecodedInstruction,
+    DecodedLaunchLabCreatePoolEvent, DecodedLaunchLabEvent, DecodedLaunchLabMigrateEvent,
+    DecodedLaunchLabSwapEvent, DecodedLaunchLabTradeLog, LaunchLabMigrationTarget,
+    StructuredInstruction, SwapEventAccounts, TransactionType,
+};
+use utils::{
+    find_event, get_account, get_instruction_token_balance, read_pubkey, read_string,
+    read_u8, read_u64,
+};
+use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
+
+#[derive(Clone, Debug, Default)]
+pub struct LaunchLabInstructionParser {}
+
+impl InstructionParser for LaunchLabInstructionParser {
+    fn get_program_id(&self) -> &str {
+        Self::PROGRAM_ID
+    }
+
+    fn decode_instructions(
+        &self,
+        instructions: Vec<StructuredInstruction>,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> DecodeReport {
+        let mut report = DecodeReport::default();
+        for instruction in instructions.iter() {
+            match self.decode_instruction(instruction, account_keys, transaction) {
+                Ok(Some(decoded_instruction)) => report.events.push(DecodedInstruction::new(
+                    Self::PROGRAM_ID,
+                    instruction,
+                    DecodedEvent::LaunchLab(decoded_instruction),
+                )),
+                Ok(None) => {}
+                Err(kind) => report
+                    .errors
+                    .push(DecodeError::new(Self::PROGRAM_ID, instruction, 8, kind)),
+            }
+        }
+        report
+    }
+}
+
+impl LaunchLabInstructionParser {
+    const PROGRAM_ID: &'static str = "LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj";
+    const INITIALIZE_DISCRIMINATOR: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
+    const BUY_EXACT_IN_DISCRIMINATOR: [u8; 8] = [250, 234, 13, 123, 213, 156, 19, 236];
+    const BUY_EXACT_OUT_DISCRIMINATOR: [u8; 8] = [24, 211, 116, 40, 105, 3, 153, 56];
+    const SELL_EXACT_IN_DISCRIMINATOR: [u8; 8] = [149, 39, 222, 155, 211, 124, 152, 26];
+    const SELL_EXACT_OUT_DISCRIMINATOR: [u8; 8] = [95, 200, 71, 34, 8, 9, 11, 166];
+    const MIGRATE_TO_AMM_DISCRIMINATOR: [u8; 8] = [207, 82, 192, 145, 254, 207, 145, 223];
+    const MIGRATE_TO_CPSWAP_DISCRIMINATOR: [u8; 8] = [136, 92, 200, 103, 28, 218, 144, 140];
+    const TRADE_EVENT_DISCRIMINATOR: [u8; 8] = [189, 219, 127, 211, 78, 230, 97, 238];
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// TradeEvent payload size once `creator_fee` was added after `platform_fee`.
+    const TRADE_EVENT_WITH_CREATOR_FEE_LEN: usize = 139;
+
+    pub fn decode_instruction(
+        &self,
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+    ) -> Result<Option<DecodedLaunchLabEvent>, DecodeErrorKind> {
+        let discriminator = match instruction.data.get(..8) {
+            Some(discriminator) => discriminator,
+            None => return Ok(None),
+        };
+        if discriminator == Self::BUY_EXACT_IN_DISCRIMINATOR {
+            return Ok(Some(DecodedLaunchLabEvent::Swap(Self::decode_trade_event(
+                instruction,
+                account_keys,
+                true,
+                true,
+            )?)));
+        } else if discriminator == Self::BUY_EXACT_OUT_DISCRIMINATOR {
+            return Ok(Some(DecodedLaunchLabEvent::Swap(Self::decode_trade_event(
+                instruction,
+                account_keys,
+                true,
+                false,
+            )?)));
+        } else if discriminator == Self::SELL_EXACT_IN_DISCRIMINATOR {
+            return Ok(Some(DecodedLaunchLabEvent::Swap(Self::decode_trade_event(
+                instruction,
+                account_keys,
+                false,
+                true,
+            )?)));
+        } else if discriminator == Self::SELL_EXACT_OUT_DISCRIMINATOR {
+            return Ok(Some(DecodedLaunchLabEvent::Swap(Self::decode_trade_event(
+                instruction,
+                account_keys,
+                false,
+                false,
+            )?)));
+        } else if discriminator == Self::INITIALIZE_DISCRIMINATOR {
+            return Ok(Some(DecodedLaunchLabEvent::CreatePool(
+                Self::decode_pool_creation_event(instruction, account_keys)?,
+            )));
+        } else if discriminator == Self::MIGRATE_TO_AMM_DISCRIMINATOR {
+            return Ok(Some(DecodedLaunchLabEvent::Migrate(Self::decode_migrate_event(
+                instruction,
+                account_keys,
+                transaction,
+                LaunchLabMigrationTarget::RaydiumAmm,
+            )?)));
+        } else if discriminator == Self::MIGRATE_TO_CPSWAP_DISCRIMINATOR {
+            return Ok(Some(DecodedLaunchLabEvent::Migrate(Self::decode_migrate_event(
+                instruction,
+                account_keys,
+                transaction,
+                LaunchLabMigrationTarget::RaydiumCpmm,
+            )?)));
+        }
+        Ok(None)
+    }
+
+    /// All four trade instructions share their accounts:
+    /// [payer, authority, global_config, platform_config, pool_state, user_base_token,
+    /// user_quote_token, base_vault, quote_vault, base_token_mint, quote_token_mint, ...]
+    pub fn decode_trade_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        is_buy: bool,
+        exact_in: bool,
+    ) -> Result<DecodedLaunchLabSwapEvent, DecodeErrorKind> {
+        let user = get_account(instruction, account_keys, 0)?;
+        let pool = get_account(instruction, account_keys, 4)?;
+        let base_mint = get_account(instruction, account_keys, 9)?;
+        let quote_mint = get_account(instruction, account_keys, 10)?;
+        let trade_log = find_event(instruction, &Self::TRADE_EVENT_DISCRIMINATOR)
+            .ok_or(DecodeErrorKind::MissingEventLog)?;
+        let decoded_trade_log = Self::decode_trade_log(trade_log)?;
+
+        let base_reserve = decoded_trade_log
+            .virtual_base
+            .saturating_sub(decoded_trade_log.real_base_after);
+        let quote_reserve = decoded_trade_log
+            .virtual_quote
+            .saturating_add(decoded_trade_log.real_quote_after);
+        let (mint_in, mint_out, mint_in_reserve, mint_out_reserve, event_type) = if is_buy {
+            (quote_mint.clone(), base_mint.clone(), quote_reserve, base_reserve, TransactionType::Buy)
+        } else {
+            (base_mint.clone(), quote_mint.clone(), base_reserve, quote_reserve, TransactionType::Sell)
+        };
+
+        Ok(DecodedLaunchLabSwapEvent {
+            accounts: SwapEventAccounts {
+                pool,
+                user,
+                base_mint,
+                quote_mint,
+            },
+            mint_in,
+            mint_out,
+            amount_in: decoded_trade_log.amount_in,
+            amount_out: decoded_trade_log.amount_out,
+            mint_in_reserve,
+            mint_out_reserve,
+            virtual_base_reserves: decoded_trade_log.virtual_base,
+            virtual_quote_reserves: decoded_trade_log.virtual_quote,
+            real_base_reserves: decoded_trade_log.real_base_after,
+            real_quote_reserves: decoded_trade_log.real_quote_after,
+            protocol_fee: decoded_trade_log.protocol_fee,
+            platform_fee: decoded_trade_log.platform_fee,
+            creator_fee: decoded_trade_log.creator_fee,
+            share_fee: decoded_trade_log.share_fee,
+            exact_in,
+            event_type,
+        })
+    }
+
+    pub fn decode_trade_log(data: &[u8]) -> Result<DecodedLaunchLabTradeLog, DecodeErrorKind> {
+        let mut offset: usize = 0;
+        let pool = read_pubkey(data, offset)?;
+        offset += 32;
+        let total_base_sell = read_u64(data, offset)?;
+        offset += 8;
+        let virtual_base = read_u64(data, offset)?;
+        offset += 8;
+        let virtual_quote = read_u64(data, offset)?;
+        offset += 8;
+        let real_base_before = read_u64(data, offset)?;
+        offset += 8;
+        let real_quote_before = read_u64(data, offset)?;
+        offset += 8;
+        let real_base_after = read_u64(data, offset)?;
+        offset += 8;
+        let real_quote_after = read_u64(data, offset)?;
+        offset += 8;
+        let amount_in = read_u64(data, offset)?;
+        offset += 8;
+        let amount_out = read_u64(data, offset)?;
+        offset += 8;
+        let protocol_fee = read_u64(data, offset)?;
+        offset += 8;
+        let platform_fee = read_u64(data, offset)?;
+        offset += 8;
+        let creator_fee = if data.len() >= Self::TRADE_EVENT_WITH_CREATOR_FEE_LEN {
+            let creator_fee = read_u64(data, offset)?;
+            offset += 8;
+            creator_fee
+        } else {
+            0
+        };
+        let share_fee = read_u64(data, offset)?;
+        offset += 8;
+        // TradeDirection::Buy = 0, TradeDirection::Sell = 1
+        let is_buy = read_u8(data, offset)? == 0;
+
+        Ok(DecodedLaunchLabTradeLog {
+            pool,
+            total_base_sell,
+            virtual_base,
+            virtual_quote,
+            real_base_before,
+            real_quote_before,
+            real_base_after,
+            real_quote_after,
+            amount_in,
+            amount_out,
+            protocol_fee,
+            platform_fee,
+            creator_fee,
+            share_fee,
+            is_buy,
+        })
+    }
+
+    /// Accounts: [payer, creator, global_config, platform_config, authority, pool_state,
+    /// base_mint, quote_mint, base_vault, quote_vault, metadata_account, ...]
+    pub fn decode_pool_creation_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+    ) -> Result<DecodedLaunchLabCreatePoolEvent, DecodeErrorKind> {
+        let creator = get_account(instruction, account_keys, 1)?;
+        let platform_config = get_account(instruction, account_keys, 3)?;
+        let pool = get_account(instruction, account_keys, 5)?;
+        let base_mint = get_account(instruction, account_keys, 6)?;
+        let quote_mint = get_account(instruction, account_keys, 7)?;
+        let base_vault = get_account(instruction, account_keys, 8)?;
+        let quote_vault = get_account(instruction, account_keys, 9)?;
+
+        // base_mint_param: decimals, name, symbol, uri
+        let data = &instruction.data;
+        let decimals = read_u8(data, 8)?;
+        let (name, offset) = read_string(data, 9)?;
+        let (symbol, offset) = read_string(data, offset)?;
+        let (uri, _) = read_string(data, offset)?;
+
+        Ok(DecodedLaunchLabCreatePoolEvent {
+            name,
+            symbol,
+            uri,
+            decimals,
+            creator,
+            pool,
+            base_mint,
+            quote_mint,
+            base_vault,
+            quote_vault,
+            platform_config,
+            event_type: TransactionType::CreatePool,
+        })
+    }
+
+    /// Account positions of (pool_state, target pool, target base vault, target quote vault).
+    fn migrate_account_positions(target: LaunchLabMigrationTarget) -> (usize, usize, usize, usize) {
+        match target {
+            // [payer, base_mint, quote_mint, openbook_program, market, request_queue,
+            // event_queue, bids, asks, market_vault_signer, market_base_vault,
+            // market_quote_vault, amm_program, amm_pool, amm_authority, amm_open_orders,
+            // amm_lp_mint, amm_base_vault, amm_quote_vault, amm_target_orders, amm_config,
+            // amm_create_fee_destination, authority, pool_state, ...]
+            LaunchLabMigrationTarget::RaydiumAmm => (23, 13, 17, 18),
+            // [payer, base_mint, quote_mint, platform_config, cpswap_program, cpswap_pool,
+            // cpswap_authority, cpswap_lp_mint, cpswap_base_vault, cpswap_quote_vault,
+            // cpswap_config, cpswap_create_pool_fee, cpswap_observation, lock_program,
+            // lock_authority, lock_lp_vault, authority, pool_state, ...]
+            LaunchLabMigrationTarget::RaydiumCpmm => (17, 5, 8, 9),
+        }
+    }
+
+    pub fn decode_migrate_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+        target: LaunchLabMigrationTarget,
+    ) -> Result<DecodedLaunchLabMigrateEvent, DecodeErrorKind> {
+        let (pool_position, target_pool_position, base_vault_position, quote_vault_position) =
+            Self::migrate_account_positions(target);
+        let base_mint = get_account(instruction, account_keys, 1)?;
+        let quote_mint = get_account(instruction, account_keys, 2)?;
+        let pool = get_account(instruction, account_keys, pool_position)?;
+        let target_pool = get_account(instruction, account_keys, target_pool_position)?;
+        let (_, base_amount, _) =
+            get_instruction_token_balance(instruction, transaction, base_vault_position)?;
+        let (_, quote_amount, _) =
+            get_instruction_token_balance(instruction, transaction, quote_vault_position)?;
+
+        Ok(DecodedLaunchLabMigrateEvent {
+            pool,
+            base_mint,
+            quote_mint,
+            target,
+            target_pool,
+            base_amount,
+            quote_amount,
+            event_type: TransactionType::Migrate,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::testing::pubkey;
+
+    const POOL: &str = "5gB4NPgFB3MHFHSeKN4sbaY6t9MB8ikCe9HyiKYid4Td";
+
+    /// The `TradeEvent` of a 0.5 SOL buy; `creator_fee` and `exact_in` are only logged since
+    /// the creator fee was introduced.
+    fn trade_log(with_creator_fee: bool) -> Vec<u8> {
+        let mut data = pubkey(POOL).to_vec();
+        for amount in [
+            793_100_000_000_000u64, // total_base_sell
+            1_073_025_605_596_382,  // virtual_base
+            30_000_852_951,         // virtual_quote
+            12_345_678_901_234,     // real_base_before
+            1_234_567_890,          // real_quote_before
+            29_876_543_210_987,     // real_base_after
+            1_730_067_890,          // real_quote_after
+            500_000_000,            // amount_in
+            17_530_864_309_753,     // amount_out
+            1_250_000,              // protocol_fee
+            5_000_000,              // platform_fee
+        ] {
+            data.extend(amount.to_le_bytes());
+        }
+        if with_creator_fee {
+            data.extend(250_000u64.to_le_bytes()); // creator_fee
+        }
+        data.extend(0u64.to_le_bytes()); // share_fee
+        data.extend([0, 0]); // trade_direction, pool_status
+        if with_creator_fee {
+            data.push(1); // exact_in
+        }
+        data
+    }
+
+    #[test]
+    fn decodes_a_trade_log_with_creator_fee() {
+        let data = trade_log(true);
+        assert_eq!(data.len(), LaunchLabInstructionParser::TRADE_EVENT_WITH_CREATOR_FEE_LEN);
+
+        let log = LaunchLabInstructionParser::decode_trade_log(&data).unwrap();
+        assert_eq!(log.pool, POOL);
+        assert_eq!(log.total_base_sell, 793_100_000_000_000);
+        assert_eq!(log.virtual_base, 1_073_025_605_596_382);
+        assert_eq!(log.virtual_quote, 30_000_852_951);
+        assert_eq!(log.real_base_before, 12_345_678_901_234);
+        assert_eq!(log.real_quote_before, 1_234_567_890);
+        assert_eq!(log.real_base_after, 29_876_543_210_987);
+        assert_eq!(log.real_quote_after, 1_730_067_890);
+        assert_eq!(log.amount_in, 500_000_000);
+        assert_eq!(log.amount_out, 17_530_864_309_753);
+        assert_eq!(log.protocol_fee, 1_250_000);
+        assert_eq!(log.platform_fee, 5_000_000);
+        assert_eq!(log.creator_fee, 250_000);
+        assert_eq!(log.share_fee, 0);
+        assert!(log.is_buy);
+    }
+
+    #[test]
+    fn decodes_a_trade_log_from_before_the_creator_fee() {
+        let data = trade_log(false);
+        let log = LaunchLabInstructionParser::decode_trade_log(&data).unwrap();
+        assert_eq!(log.amount_in, 500_000_000);
+        assert_eq!(log.amount_out, 17_530_864_309_753);
+        assert_eq!(log.platform_fee, 5_000_000);
+        assert_eq!(log.creator_fee, 0);
+        assert_eq!(log.share_fee, 0);
+        assert!(log.is_buy);
+    }
+}
@@ -0,0 +1,118 @@
+//! Layouts of the Raydium AMM v4 instruction args, decoded by `#[derive(Layout)]`. The program
+//! is not Anchor, instructions are tagged by a single byte and the pool's coin and pc sides are
+//! its base and quote.
+
+use layout_derive::Layout;
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [1])]
+pub struct Initialize2Args {
+    pub nonce: u8,
+    pub open_time: u64,
+    pub init_pc_amount: u64,
+    pub init_coin_amount: u64,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [3])]
+pub struct DepositArgs {
+    pub max_coin_amount: u64,
+    pub max_pc_amount: u64,
+    pub base_side: u64,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [4])]
+pub struct WithdrawArgs {
+    pub amount: u64,
+    /// The minimums are only sent by clients asking for slippage protection.
+    #[layout(trailing)]
+    pub min_coin_amount: Option<u64>,
+    #[layout(trailing)]
+    pub min_pc_amount: Option<u64>,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [9])]
+pub struct SwapBaseInArgs {
+    pub amount_in: u64,
+    pub minimum_amount_out: u64,
+}
+
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [11])]
+pub struct SwapBaseOutArgs {
+    pub max_amount_in: u64,
+    pub amount_out: u64,
+}
+
+/// Same args as `SwapBaseInArgs`, without the OpenBook market accounts.
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [16])]
+pub struct SwapBaseInV2Args {
+    pub amount_in: u64,
+    pub minimum_amount_out: u64,
+}
+
+/// Same args as `SwapBaseOutArgs`, without the OpenBook market accounts.
+#[derive(Layout, Debug, Clone)]
+#[layout(discriminator = [17])]
+pub struct SwapBaseOutV2Args {
+    pub max_amount_in: u64,
+    pub amount_out: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_the_initialize2_args() {
+        let mut data = vec![1, 254]; // nonce
+        data.extend(1_750_012_345u64.to_le_bytes()); // open_time
+        data.extend(80_000_000_000u64.to_le_bytes()); // init_pc_amount
+        data.extend(200_000_000_000_000u64.to_le_bytes()); // init_coin_amount
+
+        let args = Initialize2Args::decode_with_discriminator(&data).unwrap().unwrap();
+        assert_eq!(args.nonce, 254);
+        assert_eq!(args.open_time, 1_750_012_345);
+        assert_eq!(args.init_pc_amount, 80_000_000_000);
+        assert_eq!(args.init_coin_amount, 200_000_000_000_000);
+    }
+
+    #[test]
+    fn decodes_a_withdraw_with_its_minimums() {
+        let mut data = vec![4];
+        data.extend(1_234_567_890u64.to_le_bytes()); // amount
+        data.extend(6_000_000_000u64.to_le_bytes()); // min_coin_amount
+        data.extend(2_400_000u64.to_le_bytes()); // min_pc_amount
+
+        let args = WithdrawArgs::decode_with_discriminator(&data).unwrap().unwrap();
+        assert_eq!(args.amount, 1_234_567_890);
+        assert_eq!(args.min_coin_amount, Some(6_000_000_000));
+        assert_eq!(args.min_pc_amount, Some(2_400_000));
+    }
+
+    #[test]
+    fn decodes_a_withdraw_without_its_minimums() {
+        let mut data = vec![4];
+        data.extend(1_234_567_890u64.to_le_bytes()); // amount
+
+        let args = WithdrawArgs::decode_with_discriminator(&data).unwrap().unwrap();
+        assert_eq!(args.amount, 1_234_567_890);
+        assert_eq!(args.min_coin_amount, None);
+        assert_eq!(args.min_pc_amount, None);
+    }
+
+    #[test]
+    fn tells_the_swap_variants_apart_by_their_tag() {
+        let mut data = vec![16];
+        data.extend(500_000_000u64.to_le_bytes()); // amount_in
+        data.extend(12_000_000_000_000u64.to_le_bytes()); // minimum_amount_out
+
+        assert!(SwapBaseInArgs::decode_with_discriminator(&data).unwrap().is_none());
+        let args = SwapBaseInV2Args::decode_with_discriminator(&data).unwrap().unwrap();
+        assert_eq!(args.amount_in, 500_000_000);
+        assert_eq!(args.minimum_amount_out, 12_000_000_000_000);
+    }
+}
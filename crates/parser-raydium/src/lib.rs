@@ -1,19 +1,27 @@
-use bs58;
+pub mod layouts;
+
 use instruction_parser::InstructionParser;
+use layouts::{
+    DepositArgs, Initialize2Args, SwapBaseInArgs, SwapBaseInV2Args, SwapBaseOutArgs,
+    SwapBaseOutV2Args, WithdrawArgs,
+};
 use types::{
-    DecodedEvent, StructuredInstruction, DecodedRaydiumSwapEvent, DecodedRaydiumCreatePoolEvent, DecodedRaydiumEvent
+    DecodeError, DecodeErrorKind, DecodeReport, DecodedEvent, DecodedInstruction,
+    StructuredInstruction, DecodedRaydiumSwapEvent, DecodedRaydiumCreatePoolEvent,
+    DecodedRaydiumEvent, DecodedRaydiumDepositEvent, DecodedRaydiumWithdrawEvent,
+    RaydiumSwapType,
 };
 use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
-use utils::parse_token_program_transfer;
+use utils::{
+    find_token_transfer, get_account, get_instruction_token_balance, get_token_amount, get_token_balance,
+    get_token_mint_or_burn_amount, get_token_program_instructions, get_token_transfers,
+    parse_token_program_transfer, TOKEN_BURN_DISCRIMINATOR,
+};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct RaydiumInstructionParser {}
 
 impl InstructionParser for RaydiumInstructionParser {
-    fn new() -> Self {
-        Self {}
-    }
-
     fn get_program_id(&self) -> &str {
         Self::PROGRAM_ID
     }
@@ -21,118 +29,226 @@ impl InstructionParser for RaydiumInstructionParser {
     fn decode_instructions(
         &self,
         instructions: Vec<StructuredInstruction>,
-        account_keys: &Vec<String>,
+        account_keys: &[String],
         transaction: &SubscribeUpdateTransaction,
-    ) -> Vec<DecodedEvent> {
-        instructions
-            .iter()
-            .filter_map(
-                |instruction| match self.decode_instruction(instruction, &account_keys, transaction) {
-                    Some(decoded_instruction) => Some(DecodedEvent::Raydium(decoded_instruction)),
-                    None => None,
-                },
-            )
-            .collect()
+    ) -> DecodeReport {
+        let mut report = DecodeReport::default();
+        for instruction in instructions.iter() {
+            match self.decode_instruction(instruction, account_keys, transaction) {
+                Ok(Some(decoded_instruction)) => report.events.push(DecodedInstruction::new(
+                    Self::PROGRAM_ID,
+                    instruction,
+                    DecodedEvent::Raydium(decoded_instruction),
+                )),
+                Ok(None) => {}
+                Err(kind) => report
+                    .errors
+                    .push(DecodeError::new(Self::PROGRAM_ID, instruction, 1, kind)),
+            }
+        }
+        report
     }
 }
 
 impl RaydiumInstructionParser {
     const PROGRAM_ID: &'static str = "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8";
-    const TOKEN_PROGRAM_ID: &'static str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
-    const WSOL_ADDRESS: &'static str = "So11111111111111111111111111111111111111112";
-    const POOL_CREATION_DISCRIMINATOR: [u8; 1] = [1];
-    const SWAP_BASE_IN_DISCRIMINATOR: [u8; 1] = [9];
+
+    pub fn new() -> Self {
+        Self {}
+    }
 
     pub fn decode_instruction(
         &self,
         instruction: &StructuredInstruction,
-        account_keys: &Vec<String>,
+        account_keys: &[String],
         transaction: &SubscribeUpdateTransaction,
-    ) -> Option<DecodedRaydiumEvent> {
-        let discriminator = &instruction.data[0..1];
-        if discriminator == Self::SWAP_BASE_IN_DISCRIMINATOR {
-            return Some(DecodedRaydiumEvent::Swap(Self::decode_swap_base_in(
-                instruction, account_keys, transaction
-            )));
-        } else if discriminator == Self::POOL_CREATION_DISCRIMINATOR {
-            return Some(DecodedRaydiumEvent::CreatePool(
-                Self::decode_pool_creation_event(instruction, account_keys),
-            ));
-        }
-        None
+    ) -> Result<Option<DecodedRaydiumEvent>, DecodeErrorKind> {
+        let data: &Vec<u8> = &instruction.data;
+        let (swap_type, max_amount_in, minimum_amount_out) =
+            if let Some(args) = SwapBaseInArgs::decode_with_discriminator(data)? {
+                (RaydiumSwapType::BaseIn, None, Some(args.minimum_amount_out))
+            } else if let Some(args) = SwapBaseOutArgs::decode_with_discriminator(data)? {
+                (RaydiumSwapType::BaseOut, Some(args.max_amount_in), None)
+            } else if let Some(args) = SwapBaseInV2Args::decode_with_discriminator(data)? {
+                (RaydiumSwapType::BaseInV2, None, Some(args.minimum_amount_out))
+            } else if let Some(args) = SwapBaseOutV2Args::decode_with_discriminator(data)? {
+                (RaydiumSwapType::BaseOutV2, Some(args.max_amount_in), None)
+            } else if let Some(args) = Initialize2Args::decode_with_discriminator(data)? {
+                return Ok(Some(DecodedRaydiumEvent::CreatePool(
+                    Self::decode_pool_creation_event(instruction, account_keys, args)?,
+                )));
+            } else if let Some(args) = DepositArgs::decode_with_discriminator(data)? {
+                return Ok(Some(DecodedRaydiumEvent::Deposit(Self::decode_deposit_event(
+                    instruction, account_keys, transaction, args
+                )?)));
+            } else if let Some(args) = WithdrawArgs::decode_with_discriminator(data)? {
+                return Ok(Some(DecodedRaydiumEvent::Withdraw(Self::decode_withdraw_event(
+                    instruction, account_keys, transaction, args
+                )?)));
+            } else {
+                return Ok(None);
+            };
+        Ok(Some(DecodedRaydiumEvent::Swap(Self::decode_swap(
+            instruction,
+            account_keys,
+            transaction,
+            swap_type,
+            max_amount_in,
+            minimum_amount_out,
+        )?)))
+    }
+
+    pub fn decode_deposit_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+        args: DepositArgs,
+    ) -> Result<DecodedRaydiumDepositEvent, DecodeErrorKind> {
+        let pool = get_account(instruction, account_keys, 1)?;
+        let lp_mint = get_account(instruction, account_keys, 5)?;
+        let base_vault = get_account(instruction, account_keys, 6)?;
+        let quote_vault = get_account(instruction, account_keys, 7)?;
+        let (base_mint, pool_base_token_reserves, _) = get_instruction_token_balance(instruction, transaction, 6)?;
+        let (quote_mint, pool_quote_token_reserves, _) = get_instruction_token_balance(instruction, transaction, 7)?;
+
+        let transfers = get_token_transfers(instruction, account_keys)?;
+        let base_transfer = find_token_transfer(&transfers, &base_vault, true)?;
+        let quote_transfer = find_token_transfer(&transfers, &quote_vault, true)?;
+        let lp_amount_out = get_token_mint_or_burn_amount(instruction, account_keys, false)?;
+
+        Ok(DecodedRaydiumDepositEvent {
+            pool,
+            user: base_transfer.authority.clone(),
+            base_mint,
+            quote_mint,
+            lp_mint,
+            base_amount_in: base_transfer.amount,
+            quote_amount_in: quote_transfer.amount,
+            max_base_amount_in: args.max_coin_amount,
+            max_quote_amount_in: args.max_pc_amount,
+            lp_amount_out,
+            pool_base_token_reserves,
+            pool_quote_token_reserves,
+        })
+    }
+
+    pub fn decode_withdraw_event(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+        args: WithdrawArgs,
+    ) -> Result<DecodedRaydiumWithdrawEvent, DecodeErrorKind> {
+        let pool = get_account(instruction, account_keys, 1)?;
+        let lp_mint = get_account(instruction, account_keys, 5)?;
+        let base_vault = get_account(instruction, account_keys, 6)?;
+        let quote_vault = get_account(instruction, account_keys, 7)?;
+        let (base_mint, pool_base_token_reserves, _) = get_instruction_token_balance(instruction, transaction, 6)?;
+        let (quote_mint, pool_quote_token_reserves, _) = get_instruction_token_balance(instruction, transaction, 7)?;
+
+        let transfers = get_token_transfers(instruction, account_keys)?;
+        let base_transfer = find_token_transfer(&transfers, &base_vault, false)?;
+        let quote_transfer = find_token_transfer(&transfers, &quote_vault, false)?;
+        let lp_amount_in = get_token_mint_or_burn_amount(instruction, account_keys, true)?;
+        // burn accounts: [account, mint, authority]
+        let lp_burn = get_token_program_instructions(instruction, account_keys)
+            .into_iter()
+            .find(|inner| inner.data.first() == Some(&TOKEN_BURN_DISCRIMINATOR))
+            .ok_or(DecodeErrorKind::MissingTokenTransfer { position: transfers.len() })?;
+        let user = get_account(lp_burn, account_keys, 2)?;
+
+        Ok(DecodedRaydiumWithdrawEvent {
+            pool,
+            user,
+            base_mint,
+            quote_mint,
+            lp_mint,
+            base_amount_out: base_transfer.amount,
+            quote_amount_out: quote_transfer.amount,
+            min_base_amount_out: args.min_coin_amount,
+            min_quote_amount_out: args.min_pc_amount,
+            lp_amount_in,
+            pool_base_token_reserves,
+            pool_quote_token_reserves,
+        })
     }
 
     pub fn decode_pool_creation_event(
         instruction: &StructuredInstruction,
         account_keys: &[String],
-    ) -> DecodedRaydiumCreatePoolEvent {
-        let account_key_indexes = &instruction.account_key_indexes;
-        let inner_instructions = &instruction.inner_instructions;
-        let token_program_index = account_keys.iter().position(|key| key == Self::TOKEN_PROGRAM_ID).unwrap();
-        let token_program_transactions: Vec<&StructuredInstruction> = inner_instructions.iter().filter(|instruction| instruction.program_id_index == token_program_index as u8).collect();
-        let base_mint_transfer = parse_token_program_transfer(token_program_transactions[0], &account_keys.to_vec());
-        let quote_mint_transfer =  parse_token_program_transfer(token_program_transactions[1], &account_keys.to_vec());
+        args: Initialize2Args,
+    ) -> Result<DecodedRaydiumCreatePoolEvent, DecodeErrorKind> {
+        let token_program_transactions = get_token_program_instructions(instruction, account_keys);
+        let base_mint_transfer = parse_token_program_transfer(
+            token_program_transactions
+                .first()
+                .ok_or(DecodeErrorKind::MissingTokenTransfer { position: 0 })?,
+            account_keys,
+        )?;
+        let quote_mint_transfer = parse_token_program_transfer(
+            token_program_transactions
+                .get(1)
+                .ok_or(DecodeErrorKind::MissingTokenTransfer { position: 1 })?,
+            account_keys,
+        )?;
 
-        let user = account_keys[account_key_indexes[0] as usize].clone();
-        let pool = account_keys[account_key_indexes[4] as usize].clone();
-        let base_mint = account_keys[account_key_indexes[8] as usize].clone();
-        let quote_mint = account_keys[account_key_indexes[9] as usize].clone();
+        let user = get_account(instruction, account_keys, 0)?;
+        let pool = get_account(instruction, account_keys, 4)?;
+        let base_mint = get_account(instruction, account_keys, 8)?;
+        let quote_mint = get_account(instruction, account_keys, 9)?;
 
-        DecodedRaydiumCreatePoolEvent {
+        Ok(DecodedRaydiumCreatePoolEvent {
             pool,
             user,
             base_mint,
             quote_mint,
             base_amount: base_mint_transfer.amount,
             quote_amount: quote_mint_transfer.amount,
-        }
+            open_time: args.open_time,
+        })
 
     }
 
-    pub fn decode_swap_base_in(instruction: &StructuredInstruction, account_keys: &Vec<String>, transaction: &SubscribeUpdateTransaction) -> DecodedRaydiumSwapEvent {
-        let account_key_indexes = &instruction.account_key_indexes;
+    /// Decodes every swap variant; they all move funds with a transfer into the pool
+    /// followed by a transfer out of it, and only differ in which amount is fixed.
+    pub fn decode_swap(
+        instruction: &StructuredInstruction,
+        account_keys: &[String],
+        transaction: &SubscribeUpdateTransaction,
+        swap_type: RaydiumSwapType,
+        max_amount_in: Option<u64>,
+        minimum_amount_out: Option<u64>,
+    ) -> Result<DecodedRaydiumSwapEvent, DecodeErrorKind> {
         let inner_instructions = &instruction.inner_instructions;
-        let in_transfer = parse_token_program_transfer(&inner_instructions[0], account_keys);
-        let out_transfer = parse_token_program_transfer(&inner_instructions[1], account_keys);
-
-        let pre_token_balances = transaction.clone().transaction.unwrap().meta.unwrap().pre_token_balances;
-        let post_token_balances = transaction.clone().transaction.unwrap().meta.unwrap().post_token_balances;
-
-        let mut out_token_balance;
-        let mut in_token_balance;
-
-        match post_token_balances.iter().find(|balance| balance.account_index == inner_instructions[0].account_key_indexes[1] as u32) {
-            Some(balance) =>
-                in_token_balance = balance.clone(),
-            None => {
-                match pre_token_balances.iter().find(|balance| balance.account_index == inner_instructions[0].account_key_indexes[1] as u32) {
-                    Some(balance) => in_token_balance = balance.clone(),
-                    None => panic!("No in_token_balance could be found")
-                }
-            }
-        }
-        match post_token_balances.iter().find(|balance| balance.account_index == inner_instructions[1].account_key_indexes[0] as u32) {
-            Some(balance) => out_token_balance = balance.clone(),
-            None => {
-                match pre_token_balances.iter().find(|balance| balance.account_index == inner_instructions[1].account_key_indexes[0] as u32) {
-                    Some(balance) => out_token_balance = balance.clone(),
-                    None => panic!("No out_token_balance could be found")
-                }
-            }
-        }
+        let in_transfer_ix = inner_instructions
+            .first()
+            .ok_or(DecodeErrorKind::MissingTokenTransfer { position: 0 })?;
+        let out_transfer_ix = inner_instructions
+            .get(1)
+            .ok_or(DecodeErrorKind::MissingTokenTransfer { position: 1 })?;
+        let in_transfer = parse_token_program_transfer(in_transfer_ix, account_keys)?;
+        let out_transfer = parse_token_program_transfer(out_transfer_ix, account_keys)?;
 
-        let pool = account_keys[account_key_indexes[1] as usize].clone();
+        let in_vault_index = *in_transfer_ix
+            .account_key_indexes
+            .get(1)
+            .ok_or(DecodeErrorKind::MissingAccount { position: 1 })?;
+        let out_vault_index = *out_transfer_ix
+            .account_key_indexes
+            .first()
+            .ok_or(DecodeErrorKind::MissingAccount { position: 0 })?;
+        let in_token_balance = get_token_balance(transaction, in_vault_index as u32)?;
+        let out_token_balance = get_token_balance(transaction, out_vault_index as u32)?;
+
+        let pool = get_account(instruction, account_keys, 1)?;
         let user = in_transfer.authority;
-        let mint_in = in_token_balance.clone().mint;
-        let mint_out = out_token_balance.clone().mint;
+        let (mint_in_reserve, in_decimals) = get_token_amount(&in_token_balance)?;
+        let (mint_out_reserve, out_decimals) = get_token_amount(&out_token_balance)?;
+        let mint_in = in_token_balance.mint;
+        let mint_out = out_token_balance.mint;
         let amount_in = in_transfer.amount;
         let amount_out = out_transfer.amount;
-        let mint_in_reserve = in_token_balance.clone().ui_token_amount.unwrap().amount.parse::<u64>().unwrap();
-        let mint_out_reserve = out_token_balance.clone().ui_token_amount.unwrap().amount.parse::<u64>().unwrap();
-        let in_decimals = in_token_balance.ui_token_amount.unwrap().decimals as u8;
-        let out_decimals = out_token_balance.ui_token_amount.unwrap().decimals as u8;
 
-        DecodedRaydiumSwapEvent {
+        Ok(DecodedRaydiumSwapEvent {
             pool,
             user,
             mint_in,
@@ -142,7 +258,10 @@ impl RaydiumInstructionParser {
             mint_in_reserve,
             mint_out_reserve,
             in_decimals,
-            out_decimals
-        }
+            out_decimals,
+            max_amount_in,
+            minimum_amount_out,
+            swap_type,
+        })
     }
 }
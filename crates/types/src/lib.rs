@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 #[derive(Serialize, Deserialize)]
 pub struct MyTransactionInner {
@@ -162,7 +163,7 @@ pub struct RaydiumSwap {
     pub amount_out: u64,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct RaydiumCpmmSwap {
     pub pool: String,
     pub mint_in: String,
@@ -280,6 +281,12 @@ pub struct StructuredInstruction {
     pub data: Vec<u8>,
     pub inner_instructions: Vec<StructuredInstruction>,
     pub stack_height: u8,
+    /// Position of the instruction in the transaction: the outer instruction index
+    /// followed by the index at each level of inner instructions.
+    pub path: Vec<usize>,
+    /// Base64-decoded `Program data:` lines logged by this invocation itself, in order. Only
+    /// filled in once the transaction's logs are attached with `attach_program_data_logs`.
+    pub program_data_logs: Vec<Vec<u8>>,
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -301,38 +308,96 @@ pub enum PumpAmmTransaction {
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DecodedPumpAmmBuyLog {
-    pub quote_amount_in: u64,
+    pub timestamp: i64,
     pub base_amount_out: u64,
+    pub max_quote_amount_in: u64,
+    pub user_base_token_reserves: u64,
+    pub user_quote_token_reserves: u64,
     pub pool_base_token_reserves: u64,
     pub pool_quote_token_reserves: u64,
+    pub quote_amount_in: u64,
+    pub lp_fee_basis_points: u64,
+    pub lp_fee: u64,
+    pub protocol_fee_basis_points: u64,
+    pub protocol_fee: u64,
+    pub quote_amount_in_with_lp_fee: u64,
+    pub user_quote_amount_in: u64,
+    pub pool: String,
+    pub user: String,
+    pub user_base_token_account: String,
+    pub user_quote_token_account: String,
+    pub protocol_fee_recipient: String,
+    pub protocol_fee_recipient_token_account: String,
     pub coin_creator: String,
+    pub coin_creator_fee_basis_points: u64,
+    pub coin_creator_fee: u64,
     pub transaction_type: TransactionType,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DecodedPumpAmmSellLog {
-    pub quote_amount_out: u64,
+    pub timestamp: i64,
     pub base_amount_in: u64,
+    pub min_quote_amount_out: u64,
+    pub user_base_token_reserves: u64,
+    pub user_quote_token_reserves: u64,
     pub pool_base_token_reserves: u64,
     pub pool_quote_token_reserves: u64,
+    pub quote_amount_out: u64,
+    pub lp_fee_basis_points: u64,
+    pub lp_fee: u64,
+    pub protocol_fee_basis_points: u64,
+    pub protocol_fee: u64,
+    pub quote_amount_out_without_lp_fee: u64,
+    pub user_quote_amount_out: u64,
+    pub pool: String,
+    pub user: String,
+    pub user_base_token_account: String,
+    pub user_quote_token_account: String,
+    pub protocol_fee_recipient: String,
+    pub protocol_fee_recipient_token_account: String,
     pub coin_creator: String,
+    pub coin_creator_fee_basis_points: u64,
+    pub coin_creator_fee: u64,
     pub transaction_type: TransactionType,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DecodedPumpAmmWithdrawEvent {
+    pub pool: String,
+    pub user: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub lp_mint: String,
+    pub user_pool_token_account: String,
+    pub lp_token_amount_in: u64,
+    pub min_base_amount_out: u64,
+    pub min_quote_amount_out: u64,
     pub pool_base_token_reserves: u64,
     pub pool_quote_token_reserves: u64,
     pub base_amount_out: u64,
     pub quote_amount_out: u64,
+    /// LP mint supply after the withdrawal.
+    pub lp_mint_supply: u64,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DecodedPumpAmmDepositEvent {
+    pub pool: String,
+    pub user: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub lp_mint: String,
+    pub user_pool_token_account: String,
+    pub lp_token_amount_out: u64,
+    pub max_base_amount_in: u64,
+    pub max_quote_amount_in: u64,
     pub pool_base_token_reserves: u64,
     pub pool_quote_token_reserves: u64,
     pub base_amount_in: u64,
     pub quote_amount_in: u64,
+    /// LP mint supply after the deposit.
+    pub lp_mint_supply: u64,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -342,35 +407,169 @@ pub enum TransactionType {
     Deposit,
     Withdraw,
     CreatePool,
+    Migrate,
 }
 
 
 #[derive(Debug)]
 pub struct DecodedPumpAmmCreatePoolEvent {
     pub pool: String,
+    /// Signer that created the pool, the pump.fun migration authority for migrated coins.
     pub creator: String,
+    /// Only logged since coin creator fees were introduced.
+    pub coin_creator: Option<String>,
     pub base_mint: String,
     pub quote_mint: String,
+    pub base_decimals: u8,
+    pub quote_decimals: u8,
     pub pool_base_token_reserve: u64,
     pub pool_quote_token_reserve: u64,
     pub pool_base_token_account: String,
     pub pool_quote_token_account: String,
+    pub lp_mint: String,
+    /// LP supply minted at creation, `minimum_liquidity` of it locked in the pool.
+    pub initial_liquidity: u64,
+    pub minimum_liquidity: u64,
+    pub lp_token_amount_out: u64,
     pub index: u16,
+    /// The index 0 pool created by the pump.fun migration, as opposed to a user-created pool.
+    pub is_canonical: bool,
+    pub timestamp: i64,
     pub event_type: TransactionType,
 }
 
+/// Swaps are the common case, so they are kept inline rather than boxed.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug)]
 pub enum DecodedPumpAmmEvent {
     Swap(DecodedPumpAmmSwapEvent),
     CreatePool(DecodedPumpAmmCreatePoolEvent),
     Withdraw(DecodedPumpAmmWithdrawEvent),
     Deposit(DecodedPumpAmmDepositEvent),
+    CollectCoinCreatorFee(DecodedPumpAmmCollectCoinCreatorFeeEvent),
+    SetCoinCreator(DecodedPumpAmmSetCoinCreatorEvent),
+    CreateConfig(DecodedPumpAmmConfigEvent),
+    UpdateFeeConfig(DecodedPumpAmmConfigEvent),
+    UpdateAdmin(DecodedPumpAmmUpdateAdminEvent),
+    Disable(DecodedPumpAmmDisableEvent),
+    InitUserVolumeAccumulator(DecodedPumpAmmInitUserVolumeAccumulatorEvent),
+    SyncUserVolumeAccumulator(DecodedPumpAmmSyncUserVolumeAccumulatorEvent),
+    CloseUserVolumeAccumulator(DecodedPumpAmmCloseUserVolumeAccumulatorEvent),
+    ClaimTokenIncentives(DecodedPumpAmmClaimTokenIncentivesEvent),
+    AdminUpdateTokenIncentives(DecodedPumpAmmAdminUpdateTokenIncentivesEvent),
+}
+
+#[derive(Debug)]
+pub struct DecodedPumpAmmCollectCoinCreatorFeeEvent {
+    pub coin_creator: String,
+    pub coin_creator_fee: u64,
+    pub coin_creator_vault_ata: String,
+    pub coin_creator_token_account: String,
+    pub timestamp: i64,
+}
+
+#[derive(Debug)]
+pub struct DecodedPumpAmmSetCoinCreatorEvent {
+    pub base_mint: String,
+    pub pool: String,
+    /// The pump.fun bonding curve, or the Metaplex metadata account when the curve is gone.
+    pub coin_creator_source: String,
+    pub from_metadata: bool,
+    pub coin_creator: String,
+    pub timestamp: i64,
+}
+
+/// Emitted by `create_config` and `update_fee_config`.
+#[derive(Debug)]
+pub struct DecodedPumpAmmConfigEvent {
+    pub admin: String,
+    pub lp_fee_basis_points: u64,
+    pub protocol_fee_basis_points: u64,
+    pub protocol_fee_recipients: Vec<String>,
+    /// Only logged since coin creator fees were introduced.
+    pub coin_creator_fee_basis_points: Option<u64>,
+    pub admin_set_coin_creator_authority: Option<String>,
+    pub timestamp: i64,
+}
+
+#[derive(Debug)]
+pub struct DecodedPumpAmmUpdateAdminEvent {
+    pub admin: String,
+    pub new_admin: String,
+    pub timestamp: i64,
+}
+
+#[derive(Debug)]
+pub struct DecodedPumpAmmDisableEvent {
+    pub admin: String,
+    pub disable_create_pool: bool,
+    pub disable_deposit: bool,
+    pub disable_withdraw: bool,
+    pub disable_buy: bool,
+    pub disable_sell: bool,
+    pub timestamp: i64,
+}
+
+#[derive(Debug)]
+pub struct DecodedPumpAmmInitUserVolumeAccumulatorEvent {
+    pub payer: String,
+    pub user: String,
+    pub timestamp: i64,
+}
+
+#[derive(Debug)]
+pub struct DecodedPumpAmmSyncUserVolumeAccumulatorEvent {
+    pub user: String,
+    pub total_claimed_tokens_before: u64,
+    pub total_claimed_tokens_after: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Debug)]
+pub struct DecodedPumpAmmCloseUserVolumeAccumulatorEvent {
+    pub user: String,
+    pub total_unclaimed_tokens: u64,
+    pub total_claimed_tokens: u64,
+    pub current_sol_volume: u64,
+    pub last_update_timestamp: i64,
+    pub timestamp: i64,
+}
+
+#[derive(Debug)]
+pub struct DecodedPumpAmmClaimTokenIncentivesEvent {
+    pub user: String,
+    pub mint: String,
+    pub amount: u64,
+    pub total_claimed_tokens: u64,
+    pub current_sol_volume: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Debug)]
+pub struct DecodedPumpAmmAdminUpdateTokenIncentivesEvent {
+    pub mint: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub day_number: u64,
+    pub token_supply_per_day: u64,
+    pub seconds_in_a_day: i64,
+    pub timestamp: i64,
 }
 
 #[derive(Debug)]
 pub enum DecodedRaydiumEvent {
     Swap(DecodedRaydiumSwapEvent),
-    CreatePool(DecodedRaydiumCreatePoolEvent)
+    CreatePool(DecodedRaydiumCreatePoolEvent),
+    Deposit(DecodedRaydiumDepositEvent),
+    Withdraw(DecodedRaydiumWithdrawEvent),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RaydiumSwapType {
+    BaseIn,
+    BaseOut,
+    BaseInV2,
+    BaseOutV2,
 }
 
 #[derive(Debug)]
@@ -390,6 +589,32 @@ pub struct DecodedPumpAmmSwapEvent {
     pub amount_out: u64,
     pub mint_in_reserve: u64,
     pub mint_out_reserve: u64,
+    pub timestamp: i64,
+    /// Slippage bound of a buy.
+    pub max_quote_amount_in: Option<u64>,
+    /// Slippage bound of a sell.
+    pub min_quote_amount_out: Option<u64>,
+    /// Quote amount before fees.
+    pub quote_amount: u64,
+    /// Quote amount the user paid or received, fees included.
+    pub user_quote_amount: u64,
+    /// Only logged by buys.
+    pub quote_amount_in_with_lp_fee: Option<u64>,
+    /// Only logged by sells.
+    pub quote_amount_out_without_lp_fee: Option<u64>,
+    pub user_base_token_reserves: u64,
+    pub user_quote_token_reserves: u64,
+    pub lp_fee_basis_points: u64,
+    pub lp_fee: u64,
+    pub protocol_fee_basis_points: u64,
+    pub protocol_fee: u64,
+    pub coin_creator: String,
+    pub coin_creator_fee_basis_points: u64,
+    pub coin_creator_fee: u64,
+    pub user_base_token_account: String,
+    pub user_quote_token_account: String,
+    pub protocol_fee_recipient: String,
+    pub protocol_fee_recipient_token_account: String,
     pub event_type: TransactionType,
 }
 
@@ -406,12 +631,111 @@ pub struct DecodedPumpFunCreatePoolEvent {
     pub event_type: TransactionType
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug)]
 pub enum DecodedPumpFunEvent {
     Swap(DecodedPumpFunSwapEvent),
     CreatePool(DecodedPumpFunCreatePoolEvent),
+    Complete(DecodedPumpFunCompleteEvent),
+    Migrate(DecodedPumpFunMigrateEvent),
+    Withdraw(DecodedPumpFunWithdrawEvent),
+    /// `create_v2`, for Token-2022 mints.
+    CreatePoolV2(DecodedPumpFunCreatePoolEvent),
+    ExtendAccount(DecodedPumpFunExtendAccountEvent),
+    SetCreator(DecodedPumpFunSetCreatorEvent),
+    ClaimTokenIncentives(DecodedPumpFunClaimTokenIncentivesEvent),
+    CollectCreatorFee(DecodedPumpFunCollectCreatorFeeEvent),
+    SetParams(DecodedPumpFunSetParamsEvent),
+}
+
+#[derive(Debug)]
+pub struct DecodedPumpFunExtendAccountEvent {
+    pub account: String,
+    pub user: String,
+    pub current_size: u64,
+    pub new_size: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Debug)]
+pub struct DecodedPumpFunSetCreatorEvent {
+    pub mint: String,
+    pub bonding_curve: String,
+    pub creator: String,
+    pub timestamp: i64,
+}
+
+#[derive(Debug)]
+pub struct DecodedPumpFunClaimTokenIncentivesEvent {
+    pub user: String,
+    pub mint: String,
+    pub amount: u64,
+    pub total_claimed_tokens: u64,
+    pub current_sol_volume: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Debug)]
+pub struct DecodedPumpFunCollectCreatorFeeEvent {
+    pub creator: String,
+    pub creator_fee: u64,
+    pub timestamp: i64,
+}
+
+/// The global curve parameters after `set_params`.
+#[derive(Debug)]
+pub struct DecodedPumpFunSetParamsEvent {
+    pub initial_virtual_token_reserves: u64,
+    pub initial_virtual_sol_reserves: u64,
+    pub initial_real_token_reserves: u64,
+    pub final_real_sol_reserves: u64,
+    pub token_total_supply: u64,
+    pub fee_basis_points: u64,
+    pub withdraw_authority: String,
+    pub enable_migrate: bool,
+    pub pool_migration_fee: u64,
+    pub creator_fee_basis_points: u64,
+}
+
+/// Emitted by the buy that fills the bonding curve.
+#[derive(Debug)]
+pub struct DecodedPumpFunCompleteEvent {
+    pub user: String,
+    pub mint: String,
+    pub bonding_curve: String,
+    pub timestamp: i64,
+}
+
+/// Migration of a completed curve into a Pump AMM pool.
+#[derive(Debug)]
+pub struct DecodedPumpFunMigrateEvent {
+    pub user: String,
+    pub mint: String,
+    pub bonding_curve: String,
+    pub pool: String,
+    /// Reserves moved into the pool.
+    pub mint_amount: u64,
+    pub sol_amount: u64,
+    pub pool_migration_fee: u64,
+    pub timestamp: i64,
+    /// Index in `DecodeReport::events` of the Pump AMM `CreatePool` event of the pool.
+    pub pool_creation: Option<usize>,
+    pub event_type: TransactionType,
+}
+
+/// Legacy migration path: the withdraw authority takes the curve reserves out to seed a pool
+/// elsewhere.
+#[derive(Debug)]
+pub struct DecodedPumpFunWithdrawEvent {
+    pub user: String,
+    pub mint: String,
+    pub bonding_curve: String,
+    pub token_amount: u64,
+    pub sol_amount: u64,
+    pub event_type: TransactionType,
 }
 
+/// `mint_in_reserve`/`mint_out_reserve` are the virtual reserves.
 #[derive(Debug)]
 pub struct DecodedPumpFunSwapEvent {
     pub accounts: SwapEventAccounts,
@@ -421,6 +745,26 @@ pub struct DecodedPumpFunSwapEvent {
     pub amount_out: u64,
     pub mint_in_reserve: u64,
     pub mint_out_reserve: u64,
+    pub is_buy: bool,
+    pub timestamp: i64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    /// The fee and creator fields are `None` in events from program versions that did not log
+    /// them yet.
+    pub fee_recipient: Option<String>,
+    pub fee_basis_points: Option<u64>,
+    pub fee: Option<u64>,
+    pub creator: Option<String>,
+    pub creator_fee_basis_points: Option<u64>,
+    pub creator_fee: Option<u64>,
+    /// The volume fields are `None` in events logged before volume was tracked.
+    pub track_volume: Option<bool>,
+    pub total_unclaimed_tokens: Option<u64>,
+    pub total_claimed_tokens: Option<u64>,
+    pub current_sol_volume: Option<u64>,
+    pub last_update_timestamp: Option<i64>,
+    /// Name of the instruction that traded, only logged by recent program versions.
+    pub ix_name: Option<String>,
     pub event_type: TransactionType,
 }
 #[derive(Debug)]
@@ -428,6 +772,22 @@ pub enum DecodedEvent {
     PumpAmm(DecodedPumpAmmEvent),
     PumpFun(DecodedPumpFunEvent),
     Raydium(DecodedRaydiumEvent),
+    RaydiumCpmm(DecodedRaydiumCpmmEvent),
+    RaydiumClmm(DecodedRaydiumClmmEvent),
+    LaunchLab(DecodedLaunchLabEvent),
+    OrcaWhirlpool(DecodedOrcaWhirlpoolEvent),
+    MeteoraDlmm(DecodedMeteoraDlmmEvent),
+    MeteoraDamm(DecodedMeteoraDammEvent),
+    MeteoraDammV2(DecodedMeteoraDammV2Event),
+    MeteoraDbc(DecodedMeteoraDbcEvent),
+    Jupiter(DecodedJupiterEvent),
+    JupiterLimitOrder(DecodedJupiterLimitOrderEvent),
+    JupiterDca(DecodedJupiterDcaEvent),
+    Phoenix(DecodedPhoenixEvent),
+    OpenBookV2(DecodedOpenBookV2Event),
+    Moonshot(DecodedMoonshotEvent),
+    Boop(DecodedBoopEvent),
+    AnchorIdl(DecodedAnchorIdlEvent),
 }
 
 #[derive(Debug)]
@@ -435,9 +795,25 @@ pub struct DecodedPumpFunSwapLog {
     pub mint: String,
     pub sol_amount: u64,
     pub token_amount: u64,
+    pub is_buy: bool,
     pub user: String,
+    pub timestamp: i64,
     pub virtual_sol_reserves: u64,
     pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub fee_recipient: Option<String>,
+    pub fee_basis_points: Option<u64>,
+    pub fee: Option<u64>,
+    pub creator: Option<String>,
+    pub creator_fee_basis_points: Option<u64>,
+    pub creator_fee: Option<u64>,
+    pub track_volume: Option<bool>,
+    pub total_unclaimed_tokens: Option<u64>,
+    pub total_claimed_tokens: Option<u64>,
+    pub current_sol_volume: Option<u64>,
+    pub last_update_timestamp: Option<i64>,
+    pub ix_name: Option<String>,
 }
 
 #[derive(Debug)]
@@ -446,6 +822,17 @@ pub struct TokenProgramTransfer {
     pub destination: String,
     pub authority: String,
     pub amount: u64,
+    /// Only known for `TransferChecked`, which names the mint in its accounts.
+    pub mint: Option<String>,
+}
+
+/// A base64-decoded `Program data:` log line and the invocation that logged it.
+#[derive(Clone, Debug)]
+pub struct ProgramDataLog {
+    pub program_id: String,
+    /// Numbered the same way as `StructuredInstruction::path`.
+    pub instruction_path: Vec<usize>,
+    pub data: Vec<u8>,
 }
 
 #[derive(Debug)]
@@ -460,6 +847,11 @@ pub struct DecodedRaydiumSwapEvent {
     pub mint_out_reserve: u64,
     pub amount_in: u64,
     pub amount_out: u64,
+    /// Set on base-out swaps, which fix `amount_out`.
+    pub max_amount_in: Option<u64>,
+    /// Set on base-in swaps, which fix `amount_in`.
+    pub minimum_amount_out: Option<u64>,
+    pub swap_type: RaydiumSwapType,
 }
 
 #[derive(Debug)]
@@ -469,5 +861,1387 @@ pub struct DecodedRaydiumCreatePoolEvent {
     pub base_mint: String,
     pub quote_mint: String,
     pub base_amount: u64,
-    pub quote_amount: u64
-}
\ No newline at end of file
+    pub quote_amount: u64,
+    pub open_time: u64,
+}
+
+#[derive(Debug)]
+pub struct DecodedRaydiumDepositEvent {
+    pub pool: String,
+    pub user: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub lp_mint: String,
+    pub base_amount_in: u64,
+    pub quote_amount_in: u64,
+    pub max_base_amount_in: u64,
+    pub max_quote_amount_in: u64,
+    pub lp_amount_out: u64,
+    pub pool_base_token_reserves: u64,
+    pub pool_quote_token_reserves: u64,
+}
+
+#[derive(Debug)]
+pub struct DecodedRaydiumWithdrawEvent {
+    pub pool: String,
+    pub user: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub lp_mint: String,
+    pub base_amount_out: u64,
+    pub quote_amount_out: u64,
+    pub min_base_amount_out: Option<u64>,
+    pub min_quote_amount_out: Option<u64>,
+    pub lp_amount_in: u64,
+    pub pool_base_token_reserves: u64,
+    pub pool_quote_token_reserves: u64,
+}
+
+#[derive(Debug)]
+pub enum DecodedRaydiumCpmmEvent {
+    Swap(RaydiumCpmmSwap),
+    CreatePool(DecodedRaydiumCpmmCreatePoolEvent),
+    Deposit(DecodedRaydiumCpmmDepositEvent),
+    Withdraw(DecodedRaydiumCpmmWithdrawEvent),
+}
+
+#[derive(Debug)]
+pub struct DecodedRaydiumCpmmCreatePoolEvent {
+    pub pool: String,
+    pub creator: String,
+    pub token_0_mint: String,
+    pub token_1_mint: String,
+    pub lp_mint: String,
+    pub token_0_vault: String,
+    pub token_1_vault: String,
+    pub token_0_amount: u64,
+    pub token_1_amount: u64,
+    pub lp_amount: u64,
+    pub open_time: u64,
+}
+
+#[derive(Debug)]
+pub struct DecodedRaydiumCpmmDepositEvent {
+    pub pool: String,
+    pub user: String,
+    pub token_0_mint: String,
+    pub token_1_mint: String,
+    pub lp_mint: String,
+    pub token_0_amount_in: u64,
+    pub token_1_amount_in: u64,
+    pub lp_amount_out: u64,
+    pub pool_token_0_reserves: u64,
+    pub pool_token_1_reserves: u64,
+}
+
+#[derive(Debug)]
+pub struct DecodedRaydiumCpmmWithdrawEvent {
+    pub pool: String,
+    pub user: String,
+    pub token_0_mint: String,
+    pub token_1_mint: String,
+    pub lp_mint: String,
+    pub token_0_amount_out: u64,
+    pub token_1_amount_out: u64,
+    pub lp_amount_in: u64,
+    pub pool_token_0_reserves: u64,
+    pub pool_token_1_reserves: u64,
+}
+
+#[derive(Debug)]
+pub enum DecodedRaydiumClmmEvent {
+    Swap(DecodedRaydiumClmmSwapEvent),
+    CreatePool(DecodedRaydiumClmmCreatePoolEvent),
+    OpenPosition(DecodedRaydiumClmmLiquidityEvent),
+    IncreaseLiquidity(DecodedRaydiumClmmLiquidityEvent),
+    DecreaseLiquidity(DecodedRaydiumClmmLiquidityEvent),
+}
+
+#[derive(Debug)]
+pub struct DecodedRaydiumClmmSwapEvent {
+    pub pool: String,
+    pub user: String,
+    pub mint_in: String,
+    pub mint_out: String,
+    pub in_decimals: u8,
+    pub out_decimals: u8,
+    pub mint_in_reserve: u64,
+    pub mint_out_reserve: u64,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub zero_for_one: bool,
+    pub sqrt_price_x64: u128,
+    pub liquidity: u128,
+    pub tick: i32,
+    pub swap_type: RaydiumSwapType,
+}
+
+#[derive(Debug)]
+pub struct DecodedRaydiumClmmSwapLog {
+    pub pool: String,
+    pub sender: String,
+    pub amount_0: u64,
+    pub transfer_fee_0: u64,
+    pub amount_1: u64,
+    pub transfer_fee_1: u64,
+    pub zero_for_one: bool,
+    pub sqrt_price_x64: u128,
+    pub liquidity: u128,
+    pub tick: i32,
+}
+
+#[derive(Debug)]
+pub struct DecodedRaydiumClmmCreatePoolEvent {
+    pub pool: String,
+    pub creator: String,
+    pub token_0_mint: String,
+    pub token_1_mint: String,
+    pub token_0_vault: String,
+    pub token_1_vault: String,
+    pub sqrt_price_x64: u128,
+    pub open_time: u64,
+}
+
+#[derive(Debug)]
+pub struct DecodedRaydiumClmmLiquidityEvent {
+    pub pool: String,
+    pub user: String,
+    pub position: String,
+    pub token_0_mint: String,
+    pub token_1_mint: String,
+    pub liquidity: u128,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    /// Only known when the position is opened.
+    pub tick_lower_index: Option<i32>,
+    pub tick_upper_index: Option<i32>,
+}
+
+#[derive(Debug)]
+pub enum DecodedLaunchLabEvent {
+    Swap(DecodedLaunchLabSwapEvent),
+    CreatePool(DecodedLaunchLabCreatePoolEvent),
+    Migrate(DecodedLaunchLabMigrateEvent),
+}
+
+#[derive(Debug)]
+pub struct DecodedLaunchLabSwapEvent {
+    pub accounts: SwapEventAccounts,
+    pub mint_in: String,
+    pub mint_out: String,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Effective curve reserves after the trade: virtual plus real quote, virtual minus sold base.
+    pub mint_in_reserve: u64,
+    pub mint_out_reserve: u64,
+    pub virtual_base_reserves: u64,
+    pub virtual_quote_reserves: u64,
+    pub real_base_reserves: u64,
+    pub real_quote_reserves: u64,
+    pub protocol_fee: u64,
+    pub platform_fee: u64,
+    pub creator_fee: u64,
+    pub share_fee: u64,
+    pub exact_in: bool,
+    pub event_type: TransactionType,
+}
+
+#[derive(Debug)]
+pub struct DecodedLaunchLabTradeLog {
+    pub pool: String,
+    pub total_base_sell: u64,
+    pub virtual_base: u64,
+    pub virtual_quote: u64,
+    pub real_base_before: u64,
+    pub real_quote_before: u64,
+    pub real_base_after: u64,
+    pub real_quote_after: u64,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub protocol_fee: u64,
+    pub platform_fee: u64,
+    pub creator_fee: u64,
+    pub share_fee: u64,
+    pub is_buy: bool,
+}
+
+#[derive(Debug)]
+pub struct DecodedLaunchLabCreatePoolEvent {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub decimals: u8,
+    pub creator: String,
+    pub pool: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub base_vault: String,
+    pub quote_vault: String,
+    pub platform_config: String,
+    pub event_type: TransactionType,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaunchLabMigrationTarget {
+    RaydiumAmm,
+    RaydiumCpmm,
+}
+
+#[derive(Debug)]
+pub struct DecodedLaunchLabMigrateEvent {
+    pub pool: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub target: LaunchLabMigrationTarget,
+    pub target_pool: String,
+    /// Balances of the target pool vaults once the migration has seeded them.
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub event_type: TransactionType,
+}
+
+#[derive(Debug)]
+pub enum DecodedOrcaWhirlpoolEvent {
+    Swap(DecodedOrcaWhirlpoolSwapEvent),
+    TwoHopSwap(DecodedOrcaWhirlpoolTwoHopSwapEvent),
+    CreatePool(DecodedOrcaWhirlpoolCreatePoolEvent),
+    IncreaseLiquidity(DecodedOrcaWhirlpoolLiquidityEvent),
+    DecreaseLiquidity(DecodedOrcaWhirlpoolLiquidityEvent),
+}
+
+#[derive(Debug)]
+pub struct DecodedOrcaWhirlpoolSwapEvent {
+    pub pool: String,
+    pub user: String,
+    pub mint_in: String,
+    pub mint_out: String,
+    pub in_decimals: u8,
+    pub out_decimals: u8,
+    pub mint_in_reserve: u64,
+    pub mint_out_reserve: u64,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub a_to_b: bool,
+    pub amount_specified_is_input: bool,
+}
+
+#[derive(Debug)]
+pub struct DecodedOrcaWhirlpoolTwoHopSwapEvent {
+    pub first: DecodedOrcaWhirlpoolSwapEvent,
+    pub second: DecodedOrcaWhirlpoolSwapEvent,
+}
+
+#[derive(Debug)]
+pub struct DecodedOrcaWhirlpoolCreatePoolEvent {
+    pub pool: String,
+    pub creator: String,
+    pub config: String,
+    pub token_a_mint: String,
+    pub token_b_mint: String,
+    pub token_a_vault: String,
+    pub token_b_vault: String,
+    pub tick_spacing: u16,
+    pub initial_sqrt_price: u128,
+}
+
+#[derive(Debug)]
+pub struct DecodedOrcaWhirlpoolLiquidityEvent {
+    pub pool: String,
+    pub user: String,
+    pub position: String,
+    pub token_a_mint: String,
+    pub token_b_mint: String,
+    pub liquidity: u128,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+#[derive(Debug)]
+pub enum DecodedMeteoraDlmmEvent {
+    Swap(DecodedMeteoraDlmmSwapEvent),
+    CreatePool(DecodedMeteoraDlmmCreatePoolEvent),
+    AddLiquidity(DecodedMeteoraDlmmLiquidityEvent),
+    RemoveLiquidity(DecodedMeteoraDlmmLiquidityEvent),
+}
+
+#[derive(Debug)]
+pub enum MeteoraDlmmSwapType {
+    ExactIn,
+    ExactOut,
+    WithPriceImpact,
+    ExactInV2,
+    ExactOutV2,
+    WithPriceImpactV2,
+}
+
+#[derive(Debug)]
+pub struct DecodedMeteoraDlmmSwapEvent {
+    pub pool: String,
+    pub user: String,
+    pub mint_in: String,
+    pub mint_out: String,
+    pub in_decimals: u8,
+    pub out_decimals: u8,
+    pub mint_in_reserve: u64,
+    pub mint_out_reserve: u64,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub swap_for_y: bool,
+    pub start_bin_id: i32,
+    /// Active bin of the pair once the swap is done.
+    pub active_bin_id: i32,
+    pub fee: u64,
+    pub protocol_fee: u64,
+    pub host_fee: u64,
+    pub fee_bps: u128,
+    pub swap_type: MeteoraDlmmSwapType,
+}
+
+#[derive(Debug)]
+pub struct DecodedMeteoraDlmmSwapLog {
+    pub lb_pair: String,
+    pub from: String,
+    pub start_bin_id: i32,
+    pub end_bin_id: i32,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub swap_for_y: bool,
+    pub fee: u64,
+    pub protocol_fee: u64,
+    pub fee_bps: u128,
+    pub host_fee: u64,
+}
+
+#[derive(Debug)]
+pub struct DecodedMeteoraDlmmCreatePoolEvent {
+    pub pool: String,
+    pub creator: String,
+    pub token_x_mint: String,
+    pub token_y_mint: String,
+    pub reserve_x: String,
+    pub reserve_y: String,
+    pub bin_step: u16,
+    pub active_bin_id: i32,
+}
+
+#[derive(Debug)]
+pub struct DecodedMeteoraDlmmLiquidityEvent {
+    pub pool: String,
+    pub user: String,
+    pub position: String,
+    /// One-sided deposits only name the mint of the side that moved.
+    pub token_x_mint: Option<String>,
+    pub token_y_mint: Option<String>,
+    pub amount_x: u64,
+    pub amount_y: u64,
+    pub active_bin_id: i32,
+}
+
+#[derive(Debug)]
+pub enum DecodedMeteoraDammEvent {
+    Swap(DecodedMeteoraDammSwapEvent),
+    CreatePool(DecodedMeteoraDammCreatePoolEvent),
+    AddLiquidity(DecodedMeteoraDammLiquidityEvent),
+    RemoveLiquidity(DecodedMeteoraDammLiquidityEvent),
+}
+
+/// Dynamic AMM pools keep their liquidity in Meteora vaults, so the reserves are the
+/// balances of the vaults' token accounts, which every pool on the same vaults shares.
+#[derive(Debug)]
+pub struct DecodedMeteoraDammSwapEvent {
+    pub pool: String,
+    pub user: String,
+    pub mint_in: String,
+    pub mint_out: String,
+    pub in_decimals: u8,
+    pub out_decimals: u8,
+    pub mint_in_reserve: u64,
+    pub mint_out_reserve: u64,
+    /// Includes the protocol fee.
+    pub amount_in: u64,
+    pub amount_out: u64,
+}
+
+#[derive(Debug)]
+pub struct DecodedMeteoraDammCreatePoolEvent {
+    pub pool: String,
+    pub creator: String,
+    pub lp_mint: String,
+    pub token_a_mint: String,
+    pub token_b_mint: String,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub lp_amount: u64,
+}
+
+#[derive(Debug)]
+pub struct DecodedMeteoraDammLiquidityEvent {
+    pub pool: String,
+    pub user: String,
+    pub lp_mint: String,
+    pub token_a_mint: String,
+    pub token_b_mint: String,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub lp_amount: u64,
+}
+
+#[derive(Debug)]
+pub enum DecodedMeteoraDammV2Event {
+    Swap(DecodedMeteoraDammV2SwapEvent),
+    CreatePool(DecodedMeteoraDammV2CreatePoolEvent),
+    AddLiquidity(DecodedMeteoraDammV2LiquidityEvent),
+    RemoveLiquidity(DecodedMeteoraDammV2LiquidityEvent),
+}
+
+#[derive(Debug)]
+pub struct DecodedMeteoraDammV2SwapEvent {
+    pub pool: String,
+    pub user: String,
+    pub mint_in: String,
+    pub mint_out: String,
+    pub in_decimals: u8,
+    pub out_decimals: u8,
+    pub mint_in_reserve: u64,
+    pub mint_out_reserve: u64,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub a_to_b: bool,
+    pub lp_fee: u64,
+    pub protocol_fee: u64,
+    pub partner_fee: u64,
+    pub referral_fee: u64,
+    pub next_sqrt_price: u128,
+}
+
+#[derive(Debug)]
+pub struct DecodedMeteoraDammV2SwapLog {
+    pub pool: String,
+    pub trade_direction: u8,
+    pub has_referral: bool,
+    pub amount_in: u64,
+    pub minimum_amount_out: u64,
+    pub output_amount: u64,
+    pub next_sqrt_price: u128,
+    pub lp_fee: u64,
+    pub protocol_fee: u64,
+    pub partner_fee: u64,
+    pub referral_fee: u64,
+    pub actual_amount_in: u64,
+    pub current_timestamp: u64,
+}
+
+#[derive(Debug)]
+pub struct DecodedMeteoraDammV2CreatePoolEvent {
+    pub pool: String,
+    pub creator: String,
+    pub token_a_mint: String,
+    pub token_b_mint: String,
+    pub token_a_vault: String,
+    pub token_b_vault: String,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+}
+
+#[derive(Debug)]
+pub struct DecodedMeteoraDammV2LiquidityEvent {
+    pub pool: String,
+    pub user: String,
+    pub position: String,
+    pub token_a_mint: String,
+    pub token_b_mint: String,
+    pub liquidity_delta: u128,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub pool_token_a_reserves: u64,
+    pub pool_token_b_reserves: u64,
+}
+
+#[derive(Debug)]
+pub enum DecodedMeteoraDbcEvent {
+    Swap(DecodedMeteoraDbcSwapEvent),
+    CreatePool(DecodedMeteoraDbcCreatePoolEvent),
+    Migrate(DecodedMeteoraDbcMigrateEvent),
+}
+
+#[derive(Debug)]
+pub struct DecodedMeteoraDbcSwapEvent {
+    pub accounts: SwapEventAccounts,
+    pub mint_in: String,
+    pub mint_out: String,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub mint_in_reserve: u64,
+    pub mint_out_reserve: u64,
+    /// Balances of the curve vaults after the trade, fees not yet claimed included.
+    pub base_reserve: u64,
+    pub quote_reserve: u64,
+    pub trading_fee: u64,
+    pub protocol_fee: u64,
+    pub referral_fee: u64,
+    pub next_sqrt_price: u128,
+    /// Quote reserve the curve migrates at; only reported by the `EvtSwap2` event.
+    pub migration_quote_threshold: Option<u64>,
+    /// Share of the migration threshold reached by the curve's quote reserve, from 0 to 1.
+    pub curve_progress: Option<f64>,
+    pub event_type: TransactionType,
+}
+
+/// Common fields of the `EvtSwap` and `EvtSwap2` events.
+#[derive(Debug)]
+pub struct DecodedMeteoraDbcSwapLog {
+    pub pool: String,
+    pub config: String,
+    pub trade_direction: u8,
+    pub has_referral: bool,
+    pub input_amount: u64,
+    pub output_amount: u64,
+    pub next_sqrt_price: u128,
+    pub trading_fee: u64,
+    pub protocol_fee: u64,
+    pub referral_fee: u64,
+    pub quote_reserve_amount: Option<u64>,
+    pub migration_threshold: Option<u64>,
+    pub current_timestamp: u64,
+}
+
+#[derive(Debug)]
+pub struct DecodedMeteoraDbcCreatePoolEvent {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub creator: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub pool: String,
+    pub base_vault: String,
+    pub quote_vault: String,
+    pub config: String,
+    pub event_type: TransactionType,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MeteoraDbcMigrationTarget {
+    DammV1,
+    DammV2,
+}
+
+#[derive(Debug)]
+pub struct DecodedMeteoraDbcMigrateEvent {
+    pub pool: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub target: MeteoraDbcMigrationTarget,
+    pub target_pool: String,
+    /// Amounts moved out of the curve vaults into the target pool.
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub event_type: TransactionType,
+}
+
+#[derive(Debug)]
+pub enum DecodedJupiterEvent {
+    Route(DecodedJupiterRouteEvent),
+}
+
+#[derive(Debug)]
+pub enum JupiterRouteType {
+    Route,
+    SharedAccountsRoute,
+    ExactOutRoute,
+    SharedAccountsExactOutRoute,
+    RouteV2,
+    SharedAccountsRouteV2,
+    ExactOutRouteV2,
+    SharedAccountsExactOutRouteV2,
+}
+
+/// One hop as reported by Jupiter's `SwapEvent` / `SwapsEvent`.
+#[derive(Debug)]
+pub struct DecodedJupiterSwapLog {
+    /// Only `SwapEvent` names the AMM program of the hop.
+    pub amm: Option<String>,
+    pub input_mint: String,
+    pub input_amount: u64,
+    pub output_mint: String,
+    pub output_amount: u64,
+}
+
+#[derive(Debug)]
+pub struct DecodedJupiterRouteEvent {
+    pub user: String,
+    pub input_mint: String,
+    pub output_mint: String,
+    pub input_amount: u64,
+    pub output_amount: u64,
+    pub route_type: JupiterRouteType,
+    pub swaps: Vec<DecodedJupiterSwapLog>,
+    /// Indexes in `DecodeReport::events` of the events decoded from the AMM instructions
+    /// the route invoked, filled in once every parser has run.
+    pub hops: Vec<usize>,
+}
+
+#[derive(Debug)]
+pub enum DecodedJupiterLimitOrderEvent {
+    CreateOrder(DecodedJupiterLimitOrderCreateEvent),
+    CancelOrder(DecodedJupiterLimitOrderCancelEvent),
+    Fill(DecodedJupiterLimitOrderFillEvent),
+    /// `pre_flash_fill_order`, which a keeper runs ahead of the swaps of a flash fill.
+    PreFlashFill(DecodedJupiterLimitOrderPreFlashFillEvent),
+}
+
+#[derive(Debug)]
+pub struct DecodedJupiterLimitOrderCreateEvent {
+    pub order: String,
+    pub maker: String,
+    pub input_mint: String,
+    pub output_mint: String,
+    pub making_amount: u64,
+    pub taking_amount: u64,
+    pub expired_at: Option<i64>,
+}
+
+#[derive(Debug)]
+pub struct DecodedJupiterLimitOrderCancelEvent {
+    pub order: String,
+    pub maker: String,
+}
+
+#[derive(Debug)]
+pub struct DecodedJupiterLimitOrderPreFlashFillEvent {
+    pub order: String,
+    pub taker: String,
+    pub making_amount: u64,
+}
+
+#[derive(Debug)]
+pub struct DecodedJupiterLimitOrderFillEvent {
+    pub order: String,
+    pub taker: String,
+    pub making_amount: u64,
+    pub taking_amount: u64,
+    pub remaining_making_amount: u64,
+    pub remaining_taking_amount: u64,
+    /// Indexes in `DecodeReport::events` of the swaps the keeper ran to fill the order,
+    /// filled in once every parser has run.
+    pub swaps: Vec<usize>,
+}
+
+#[derive(Debug)]
+pub enum DecodedJupiterDcaEvent {
+    Open(DecodedJupiterDcaOpenEvent),
+    Close(DecodedJupiterDcaCloseEvent),
+    Fill(DecodedJupiterDcaFillEvent),
+    /// `initiate_flash_fill` and `initiate_dlmm_fill`, which a keeper runs ahead of the swaps
+    /// of a cycle.
+    InitiateFill(DecodedJupiterDcaInitiateFillEvent),
+}
+
+#[derive(Debug)]
+pub struct DecodedJupiterDcaOpenEvent {
+    pub dca: String,
+    pub user: String,
+    pub input_mint: String,
+    pub output_mint: String,
+    pub in_deposited: u64,
+    pub in_amount_per_cycle: u64,
+    /// Seconds between two fills.
+    pub cycle_frequency: i64,
+    pub created_at: i64,
+}
+
+#[derive(Debug)]
+pub struct DecodedJupiterDcaCloseEvent {
+    pub dca: String,
+    pub user: String,
+    pub input_mint: String,
+    pub output_mint: String,
+    pub total_in_withdrawn: u64,
+    pub total_out_withdrawn: u64,
+    pub unfilled_amount: u64,
+    pub user_closed: bool,
+}
+
+#[derive(Debug)]
+pub struct DecodedJupiterDcaInitiateFillEvent {
+    pub dca: String,
+    pub keeper: String,
+}
+
+#[derive(Debug)]
+pub struct DecodedJupiterDcaFillEvent {
+    pub dca: String,
+    pub user: String,
+    pub input_mint: String,
+    pub output_mint: String,
+    pub in_amount: u64,
+    pub out_amount: u64,
+    pub fee_mint: String,
+    pub fee: u64,
+    /// Indexes in `DecodeReport::events` of the swaps the keeper ran for this cycle,
+    /// filled in once every parser has run.
+    pub swaps: Vec<usize>,
+}
+
+#[derive(Debug)]
+pub enum DecodedPhoenixEvent {
+    Fill(DecodedPhoenixFillEvent),
+    Place(DecodedPhoenixOrderEvent),
+    Reduce(DecodedPhoenixOrderEvent),
+    Evict(DecodedPhoenixOrderEvent),
+    FillSummary(DecodedPhoenixFillSummaryEvent),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhoenixSide {
+    Bid,
+    Ask,
+}
+
+/// Amounts in token units are only known for `Swap`, where the vault transfers and the fill
+/// summary let us recover the market's lot sizes.
+#[derive(Debug)]
+pub struct DecodedPhoenixFillEvent {
+    pub market: String,
+    pub maker: String,
+    pub taker: String,
+    pub maker_side: PhoenixSide,
+    pub order_sequence_number: u64,
+    pub price_in_ticks: u64,
+    pub base_lots_filled: u64,
+    pub base_lots_remaining: u64,
+    pub base_amount: Option<u64>,
+    pub quote_amount: Option<u64>,
+    /// Quote units per base unit, decimals applied.
+    pub price: Option<f64>,
+}
+
+#[derive(Debug)]
+pub struct DecodedPhoenixOrderEvent {
+    pub market: String,
+    /// Owner of the order: the signer for places and reductions, the evicted maker for evictions.
+    pub trader: String,
+    pub side: PhoenixSide,
+    pub order_sequence_number: u64,
+    pub price_in_ticks: u64,
+    /// Lots placed, removed or evicted.
+    pub base_lots: u64,
+    /// Only places carry the client order id.
+    pub client_order_id: Option<u128>,
+}
+
+#[derive(Debug)]
+pub struct DecodedPhoenixFillSummaryEvent {
+    pub market: String,
+    pub taker: String,
+    /// Unknown when nothing was filled.
+    pub taker_side: Option<PhoenixSide>,
+    pub client_order_id: u128,
+    pub total_base_lots_filled: u64,
+    pub total_quote_lots_filled: u64,
+    pub total_fee_in_quote_lots: u64,
+    pub base_mint: Option<String>,
+    pub quote_mint: Option<String>,
+    pub base_amount: Option<u64>,
+    pub quote_amount: Option<u64>,
+}
+
+#[derive(Debug)]
+pub enum DecodedOpenBookV2Event {
+    PlaceOrder(DecodedOpenBookV2PlaceOrderEvent),
+    ConsumeEvents(DecodedOpenBookV2ConsumeEventsEvent),
+    CancelOrder(DecodedOpenBookV2CancelOrderEvent),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenBookV2Side {
+    Bid,
+    Ask,
+}
+
+#[derive(Debug)]
+pub enum OpenBookV2PlaceOrderType {
+    PlaceOrder,
+    PlaceTakeOrder,
+}
+
+/// Prices are in quote lots per base lot and quantities in base lots; the lot sizes live in
+/// the market account.
+#[derive(Debug)]
+pub struct DecodedOpenBookV2PlaceOrderEvent {
+    pub market: String,
+    pub owner: String,
+    /// Take orders settle directly with the owner's token accounts.
+    pub open_orders_account: Option<String>,
+    pub side: OpenBookV2Side,
+    pub price_lots: i64,
+    pub max_base_lots: i64,
+    pub max_quote_lots_including_fees: i64,
+    pub client_order_id: Option<u64>,
+    pub order_type: u8,
+    pub place_order_type: OpenBookV2PlaceOrderType,
+    /// Fills against makers whose open orders account was passed along, settled right away.
+    pub fills: Vec<DecodedOpenBookV2FillEvent>,
+    /// Sequence numbers of fills left on the event heap for `consume_events`.
+    pub pending_fills: Vec<u64>,
+    /// Amounts moved through the market vaults, only known for take orders.
+    pub base_amount: Option<u64>,
+    pub quote_amount: Option<u64>,
+}
+
+/// `FillLog`, emitted when a fill is settled for the maker.
+#[derive(Debug)]
+pub struct DecodedOpenBookV2FillEvent {
+    pub market: String,
+    pub taker_side: OpenBookV2Side,
+    pub maker_slot: u8,
+    pub maker_out: bool,
+    pub timestamp: u64,
+    pub seq_num: u64,
+    pub maker: String,
+    pub maker_client_order_id: u64,
+    pub maker_fee: u64,
+    pub maker_timestamp: u64,
+    pub taker: String,
+    pub taker_client_order_id: u64,
+    pub taker_fee_ceil: u64,
+    pub price_lots: i64,
+    pub base_lots: i64,
+}
+
+#[derive(Debug)]
+pub struct DecodedOpenBookV2ConsumeEventsEvent {
+    pub market: String,
+    pub fills: Vec<DecodedOpenBookV2FillEvent>,
+}
+
+#[derive(Debug)]
+pub struct DecodedOpenBookV2CancelOrderEvent {
+    pub market: String,
+    pub owner: String,
+    pub open_orders_account: String,
+    pub order_id: Option<u128>,
+    pub client_order_id: Option<u64>,
+}
+
+#[derive(Debug)]
+pub enum DecodedMoonshotEvent {
+    Swap(DecodedMoonshotSwapEvent),
+    CreatePool(DecodedMoonshotCreatePoolEvent),
+    Migrate(DecodedMoonshotMigrateEvent),
+}
+
+/// Moonshot curves keep no virtual reserves: the reserves are the tokens left in the curve's
+/// token account and the lamports held by the curve account, rent included.
+#[derive(Debug)]
+pub struct DecodedMoonshotSwapEvent {
+    pub accounts: SwapEventAccounts,
+    pub mint_in: String,
+    pub mint_out: String,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub mint_in_reserve: u64,
+    pub mint_out_reserve: u64,
+    pub dex_fee: u64,
+    pub helio_fee: u64,
+    pub event_type: TransactionType,
+}
+
+#[derive(Debug)]
+pub struct DecodedMoonshotCreatePoolEvent {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub creator: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub bonding_curve: String,
+    pub bonding_curve_token_account: String,
+    pub decimals: u8,
+    pub event_type: TransactionType,
+}
+
+#[derive(Debug)]
+pub struct DecodedMoonshotMigrateEvent {
+    pub base_mint: String,
+    pub bonding_curve: String,
+    pub tokens_migrated: u64,
+    pub tokens_burned: u64,
+    pub collateral_migrated: u64,
+    pub fee: u64,
+    pub event_type: TransactionType,
+}
+
+#[derive(Debug)]
+pub enum DecodedBoopEvent {
+    Swap(DecodedBoopSwapEvent),
+    CreatePool(DecodedBoopCreatePoolEvent),
+    DeployBondingCurve(DecodedBoopDeployBondingCurveEvent),
+    Migrate(DecodedBoopMigrateEvent),
+}
+
+/// Reserves are the balances of the curve's token vault and SOL vault after the trade.
+#[derive(Debug)]
+pub struct DecodedBoopSwapEvent {
+    pub accounts: SwapEventAccounts,
+    pub mint_in: String,
+    pub mint_out: String,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub mint_in_reserve: u64,
+    pub mint_out_reserve: u64,
+    pub swap_fee: u64,
+    pub event_type: TransactionType,
+}
+
+/// `create_token` only mints the token; the curve is set up by a `deploy_bonding_curve`
+/// usually found in the same transaction.
+#[derive(Debug)]
+pub struct DecodedBoopCreatePoolEvent {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub creator: String,
+    pub base_mint: String,
+    pub quote_mint: String,
+    pub event_type: TransactionType,
+}
+
+#[derive(Debug)]
+pub struct DecodedBoopDeployBondingCurveEvent {
+    pub creator: String,
+    pub base_mint: String,
+    pub bonding_curve: String,
+    pub bonding_curve_vault: String,
+    pub bonding_curve_sol_vault: String,
+}
+
+#[derive(Debug)]
+pub struct DecodedBoopMigrateEvent {
+    pub base_mint: String,
+    pub bonding_curve: String,
+    pub event_type: TransactionType,
+}
+
+/// A value decoded from a type described in an Anchor IDL.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdlValue {
+    Unit,
+    Bool(bool),
+    Unsigned(u128),
+    Signed(i128),
+    Float(f64),
+    String(String),
+    Bytes(Vec<u8>),
+    Pubkey(String),
+    Option(Option<Box<IdlValue>>),
+    Array(Vec<IdlValue>),
+    /// Named fields, in declaration order.
+    Struct(Vec<(String, IdlValue)>),
+    Tuple(Vec<IdlValue>),
+    Enum { variant: String, value: Box<IdlValue> },
+}
+
+impl IdlValue {
+    /// The field called `name` of a struct value.
+    pub fn get(&self, name: &str) -> Option<&IdlValue> {
+        match self {
+            IdlValue::Struct(fields) => fields
+                .iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DecodedAnchorIdlAccount {
+    pub name: String,
+    pub pubkey: String,
+}
+
+#[derive(Debug)]
+pub struct DecodedAnchorIdlEmittedEvent {
+    pub name: String,
+    pub data: IdlValue,
+}
+
+/// An instruction decoded from an Anchor IDL loaded at runtime.
+#[derive(Debug)]
+pub struct DecodedAnchorIdlEvent {
+    pub program_name: String,
+    pub instruction: String,
+    pub args: IdlValue,
+    /// Accounts named by the IDL, composite accounts flattened as `group.name`.
+    pub accounts: Vec<DecodedAnchorIdlAccount>,
+    pub remaining_accounts: Vec<String>,
+    /// Events the instruction emitted through a self-CPI.
+    pub events: Vec<DecodedAnchorIdlEmittedEvent>,
+}
+
+/// An event together with the instruction it was decoded from.
+#[derive(Debug)]
+pub struct DecodedInstruction {
+    pub program_id: String,
+    pub instruction_path: Vec<usize>,
+    pub event: DecodedEvent,
+}
+
+impl DecodedInstruction {
+    pub fn new(program_id: &str, instruction: &StructuredInstruction, event: DecodedEvent) -> Self {
+        Self {
+            program_id: program_id.to_string(),
+            instruction_path: instruction.path.clone(),
+            event,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct DecodeReport {
+    pub events: Vec<DecodedInstruction>,
+    pub errors: Vec<DecodeError>,
+}
+
+impl DecodeReport {
+    pub fn append(&mut self, other: &mut DecodeReport) {
+        self.events.append(&mut other.events);
+        self.errors.append(&mut other.errors);
+    }
+
+    /// Indexes in `events` of the events decoded from instructions invoked, directly or not,
+    /// by the instruction at `instruction_path`.
+    pub fn nested_event_indexes(&self, instruction_path: &[usize]) -> Vec<usize> {
+        self.events
+            .iter()
+            .enumerate()
+            .filter(|(_, decoded)| {
+                decoded.instruction_path.len() > instruction_path.len()
+                    && decoded.instruction_path.starts_with(instruction_path)
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Indexes in `events` of what a fill at `index` executed: the events since the keeper
+    /// began the fill (keepers swap in the instructions ahead of a flash fill) and the events
+    /// nested under the fill itself. The walk back stops at the first fill or begin instruction
+    /// and only keeps what it passed when that is the fill's own begin instruction, so a fill
+    /// without one takes none of the events ahead of it. Events nested under another one of
+    /// them, like the hops of a Jupiter route, are left out so nothing is counted twice.
+    pub fn fill_event_indexes(&self, index: usize) -> Vec<usize> {
+        let fill = &self.events[index];
+        let bound = (0..index)
+            .rev()
+            .find(|&previous| Self::bounds_fill(&self.events[previous].event));
+        let mut indexes: Vec<usize> = match bound {
+            Some(bound) if Self::begins_fill(&self.events[bound].event, &fill.event) => {
+                (bound + 1..index).collect()
+            }
+            _ => Vec::new(),
+        };
+        indexes.extend(self.nested_event_indexes(&fill.instruction_path));
+        indexes
+            .iter()
+            .copied()
+            .filter(|&candidate| {
+                let path = &self.events[candidate].instruction_path;
+                !indexes.iter().any(|&other| {
+                    let other_path = &self.events[other].instruction_path;
+                    other_path.len() < path.len() && path.starts_with(other_path)
+                })
+            })
+            .collect()
+    }
+
+    /// Whether `event` ends the walk back from a fill: any Jupiter Limit Order or DCA fill, or
+    /// the instruction a keeper begins a flash fill with.
+    fn bounds_fill(event: &DecodedEvent) -> bool {
+        matches!(
+            event,
+            DecodedEvent::JupiterLimitOrder(
+                DecodedJupiterLimitOrderEvent::Fill(_)
+                    | DecodedJupiterLimitOrderEvent::PreFlashFill(_)
+            ) | DecodedEvent::JupiterDca(
+                DecodedJupiterDcaEvent::Fill(_) | DecodedJupiterDcaEvent::InitiateFill(_)
+            )
+        )
+    }
+
+    /// Whether `begin` is the instruction the keeper began the fill `fill` with: the
+    /// `pre_flash_fill_order` of the same order or the `initiate_flash_fill` of the same DCA.
+    fn begins_fill(begin: &DecodedEvent, fill: &DecodedEvent) -> bool {
+        match (begin, fill) {
+            (
+                DecodedEvent::JupiterLimitOrder(DecodedJupiterLimitOrderEvent::PreFlashFill(begin)),
+                DecodedEvent::JupiterLimitOrder(DecodedJupiterLimitOrderEvent::Fill(fill)),
+            ) => begin.order == fill.order,
+            (
+                DecodedEvent::JupiterDca(DecodedJupiterDcaEvent::InitiateFill(begin)),
+                DecodedEvent::JupiterDca(DecodedJupiterDcaEvent::Fill(fill)),
+            ) => begin.dca == fill.dca,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DecodeError {
+    pub program_id: String,
+    pub instruction_path: Vec<usize>,
+    pub discriminator: Vec<u8>,
+    pub kind: DecodeErrorKind,
+}
+
+impl DecodeError {
+    pub fn new(
+        program_id: &str,
+        instruction: &StructuredInstruction,
+        discriminator_len: usize,
+        kind: DecodeErrorKind,
+    ) -> Self {
+        let discriminator_len = discriminator_len.min(instruction.data.len());
+        Self {
+            program_id: program_id.to_string(),
+            instruction_path: instruction.path.clone(),
+            discriminator: instruction.data[..discriminator_len].to_vec(),
+            kind,
+        }
+    }
+
+    /// An error about the transaction as a whole rather than one of its instructions.
+    pub fn for_transaction(kind: DecodeErrorKind) -> Self {
+        Self {
+            program_id: String::new(),
+            instruction_path: Vec::new(),
+            discriminator: Vec::new(),
+            kind,
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.instruction_path.is_empty() {
+            return write!(f, "failed to decode transaction: {}", self.kind);
+        }
+        let path = self
+            .instruction_path
+            .iter()
+            .map(|index| index.to_string())
+            .collect::<Vec<String>>()
+            .join(".");
+        write!(
+            f,
+            "failed to decode instruction {} of program {} (discriminator {:?}): {}",
+            path, self.program_id, self.discriminator, self.kind
+        )
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DecodeErrorKind {
+    DataTooShort { offset: usize, needed: usize, len: usize },
+    MissingAccount { position: usize },
+    MissingEventLog,
+    MissingTokenTransfer { position: usize },
+    MissingTokenBalance { account_index: u32 },
+    MissingTransactionMeta,
+    InvalidString { offset: usize },
+    InvalidAmount(String),
+    AmountOverflow,
+    UnknownEventTag { offset: usize, tag: u8 },
+    InvalidEnumVariant { offset: usize, variant: u32 },
+    UnsupportedIdlType(String),
+}
+
+impl fmt::Display for DecodeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeErrorKind::DataTooShort { offset, needed, len } => write!(
+                f,
+                "data too short: needed {} bytes at offset {} but data is {} bytes",
+                needed, offset, len
+            ),
+            DecodeErrorKind::MissingAccount { position } => {
+                write!(f, "missing account at position {}", position)
+            }
+            DecodeErrorKind::MissingEventLog => write!(f, "missing event log inner instruction"),
+            DecodeErrorKind::MissingTokenTransfer { position } => {
+                write!(f, "missing token transfer at position {}", position)
+            }
+            DecodeErrorKind::MissingTokenBalance { account_index } => {
+                write!(f, "no token balance for account index {}", account_index)
+            }
+            DecodeErrorKind::MissingTransactionMeta => write!(f, "transaction meta is missing"),
+            DecodeErrorKind::InvalidString { offset } => {
+                write!(f, "invalid utf-8 string at offset {}", offset)
+            }
+            DecodeErrorKind::InvalidAmount(amount) => {
+                write!(f, "invalid token amount {:?}", amount)
+            }
+            DecodeErrorKind::AmountOverflow => write!(f, "token amount overflows"),
+            DecodeErrorKind::UnknownEventTag { offset, tag } => {
+                write!(f, "unknown event tag {} at offset {}", tag, offset)
+            }
+            DecodeErrorKind::InvalidEnumVariant { offset, variant } => {
+                write!(f, "invalid enum variant {} at offset {}", variant, offset)
+            }
+            DecodeErrorKind::UnsupportedIdlType(ty) => write!(f, "unsupported IDL type {}", ty),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JUPITER: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+    const LIMIT_ORDER: &str = "j1o2qRpjcyUwEvwtcfhEQefh773ZgjxcVRry7LDqg5X";
+    const DCA: &str = "DCA265Vj8a9CEuX1eb1LWRnDT7uK6q1xMipnNyatn23M";
+    const SOL: &str = "So11111111111111111111111111111111111111112";
+    const USDC: &str = "EPjFWdd5AufqSSqeM2qJxX1dhFyd5uzdQzmQkgtSBBCT";
+
+    fn event(program_id: &str, path: &[usize], event: DecodedEvent) -> DecodedInstruction {
+        DecodedInstruction {
+            program_id: program_id.to_string(),
+            instruction_path: path.to_vec(),
+            event,
+        }
+    }
+
+    /// A Jupiter route, standing in for any swap the keeper runs.
+    fn route(path: &[usize]) -> DecodedInstruction {
+        event(
+            JUPITER,
+            path,
+            DecodedEvent::Jupiter(DecodedJupiterEvent::Route(DecodedJupiterRouteEvent {
+                user: "keeper".to_string(),
+                input_mint: SOL.to_string(),
+                output_mint: USDC.to_string(),
+                input_amount: 1_000_000_000,
+                output_amount: 150_000_000,
+                route_type: JupiterRouteType::Route,
+                swaps: Vec::new(),
+                hops: Vec::new(),
+            })),
+        )
+    }
+
+    fn pre_flash_fill(path: &[usize]) -> DecodedInstruction {
+        event(
+            LIMIT_ORDER,
+            path,
+            DecodedEvent::JupiterLimitOrder(DecodedJupiterLimitOrderEvent::PreFlashFill(
+                DecodedJupiterLimitOrderPreFlashFillEvent {
+                    order: "order".to_string(),
+                    taker: "keeper".to_string(),
+                    making_amount: 1_000_000_000,
+                },
+            )),
+        )
+    }
+
+    fn limit_order_fill(path: &[usize]) -> DecodedInstruction {
+        event(
+            LIMIT_ORDER,
+            path,
+            DecodedEvent::JupiterLimitOrder(DecodedJupiterLimitOrderEvent::Fill(
+                DecodedJupiterLimitOrderFillEvent {
+                    order: "order".to_string(),
+                    taker: "keeper".to_string(),
+                    making_amount: 1_000_000_000,
+                    taking_amount: 150_000_000,
+                    remaining_making_amount: 0,
+                    remaining_taking_amount: 0,
+                    swaps: Vec::new(),
+                },
+            )),
+        )
+    }
+
+    fn initiate_fill(path: &[usize]) -> DecodedInstruction {
+        event(
+            DCA,
+            path,
+            DecodedEvent::JupiterDca(DecodedJupiterDcaEvent::InitiateFill(
+                DecodedJupiterDcaInitiateFillEvent {
+                    dca: "dca".to_string(),
+                    keeper: "keeper".to_string(),
+                },
+            )),
+        )
+    }
+
+    fn dca_fill(path: &[usize]) -> DecodedInstruction {
+        event(
+            DCA,
+            path,
+            DecodedEvent::JupiterDca(DecodedJupiterDcaEvent::Fill(DecodedJupiterDcaFillEvent {
+                dca: "dca".to_string(),
+                user: "user".to_string(),
+                input_mint: USDC.to_string(),
+                output_mint: SOL.to_string(),
+                in_amount: 10_000_000,
+                out_amount: 66_000_000,
+                fee_mint: SOL.to_string(),
+                fee: 66_000,
+                swaps: Vec::new(),
+            })),
+        )
+    }
+
+    fn report(events: Vec<DecodedInstruction>) -> DecodeReport {
+        DecodeReport {
+            events,
+            errors: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn flash_fill_takes_the_swaps_since_the_pre_flash_fill_without_their_hops() {
+        let report = report(vec![
+            route(&[0]),
+            pre_flash_fill(&[1]),
+            route(&[2]),
+            route(&[2, 1]),
+            route(&[2, 2]),
+            limit_order_fill(&[3]),
+        ]);
+        assert_eq!(report.fill_event_indexes(5), [2]);
+    }
+
+    #[test]
+    fn fill_does_not_take_the_swaps_of_an_earlier_fill() {
+        let report = report(vec![
+            initiate_fill(&[0]),
+            route(&[1]),
+            dca_fill(&[2]),
+            route(&[3]),
+            limit_order_fill(&[4]),
+        ]);
+        assert_eq!(report.fill_event_indexes(2), [1]);
+        assert!(report.fill_event_indexes(4).is_empty());
+    }
+
+    #[test]
+    fn fill_without_a_begin_instruction_takes_no_earlier_swaps() {
+        let report = report(vec![route(&[0]), route(&[1]), limit_order_fill(&[2])]);
+        assert!(report.fill_event_indexes(2).is_empty());
+    }
+
+    #[test]
+    fn fill_takes_the_outermost_swaps_nested_under_it() {
+        let report = report(vec![
+            pre_flash_fill(&[0]),
+            limit_order_fill(&[1]),
+            route(&[1, 0]),
+            route(&[1, 0, 2]),
+            route(&[1, 1]),
+        ]);
+        assert_eq!(report.fill_event_indexes(1), [2, 4]);
+    }
+}
@@ -1,126 +1,94 @@
-use bs58;
+use base64::{Engine, prelude::BASE64_STANDARD};
 use std::collections::{HashMap, HashSet};
-use types::{StructuredInstruction, TokenProgramTransfer};
-use yellowstone_grpc_proto::prelude::SubscribeUpdateTransaction;
-
-pub fn get_account_keys(transaction: &SubscribeUpdateTransaction) -> Vec<String> {
-    let txn = transaction.transaction.clone().unwrap();
-    let mut account_keys = txn.transaction.unwrap().message.unwrap().account_keys;
-    let meta = txn.meta.unwrap();
-    let loaded_addresses_readonly = meta.loaded_readonly_addresses;
-    let loaded_addresses_writable = meta.loaded_writable_addresses;
-    account_keys.extend(loaded_addresses_writable);
-    account_keys.extend(loaded_addresses_readonly);
-    account_keys
-        .into_iter()
+use types::{DecodeErrorKind, ProgramDataLog, StructuredInstruction, TokenProgramTransfer};
+use yellowstone_grpc_proto::prelude::{
+    Message, SubscribeUpdateTransaction, TokenBalance, TransactionStatusMeta,
+};
+
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+/// The message and meta of `transaction`, which updates do not always carry.
+fn get_message_and_meta(
+    transaction: &SubscribeUpdateTransaction,
+) -> Result<(&Message, &TransactionStatusMeta), DecodeErrorKind> {
+    let txn = transaction
+        .transaction
+        .as_ref()
+        .ok_or(DecodeErrorKind::MissingTransactionMeta)?;
+    let message = txn
+        .transaction
+        .as_ref()
+        .and_then(|inner| inner.message.as_ref())
+        .ok_or(DecodeErrorKind::MissingTransactionMeta)?;
+    let meta = txn.meta.as_ref().ok_or(DecodeErrorKind::MissingTransactionMeta)?;
+    Ok((message, meta))
+}
+
+pub fn get_account_keys(
+    transaction: &SubscribeUpdateTransaction,
+) -> Result<Vec<String>, DecodeErrorKind> {
+    let (message, meta) = get_message_and_meta(transaction)?;
+    Ok(message
+        .account_keys
+        .iter()
+        .chain(meta.loaded_writable_addresses.iter())
+        .chain(meta.loaded_readonly_addresses.iter())
         .map(|key| bs58::encode(key).into_string())
-        .collect()
+        .collect())
 }
 
 pub fn structure_all_instructions(
     transaction: &SubscribeUpdateTransaction,
-) -> Vec<StructuredInstruction> {
-    let txn = &transaction.transaction.clone().unwrap();
-    let compiled_instructions = txn
-        .transaction
-        .clone()
-        .unwrap()
-        .message
-        .unwrap()
-        .instructions;
-    let inner_instructions = txn.meta.clone().unwrap().inner_instructions;
-
-    if inner_instructions.is_empty() {
-        return compiled_instructions
-            .iter()
-            .map(|instruction| StructuredInstruction {
-                account_key_indexes: instruction.accounts.clone(),
-                program_id_index: instruction.program_id_index as u8,
-                data: instruction.data.clone(),
-                inner_instructions: Vec::new(),
-                stack_height: 0,
-            })
-            .collect();
-    }
+) -> Result<Vec<StructuredInstruction>, DecodeErrorKind> {
+    let (message, meta) = get_message_and_meta(transaction)?;
+    let compiled_instructions = &message.instructions;
+    let inner_instructions = &meta.inner_instructions;
 
     let mut formatted: Vec<StructuredInstruction> = Vec::new();
 
-    for inner_instruction_group in inner_instructions.iter() {
-        let parent_ix = &compiled_instructions[inner_instruction_group.index as usize];
-
+    for (index, compiled_instruction) in compiled_instructions.iter().enumerate() {
         let mut parent = StructuredInstruction {
-            account_key_indexes: parent_ix.accounts.clone(),
-            program_id_index: parent_ix.program_id_index as u8,
-            data: parent_ix.data.clone(),
+            account_key_indexes: compiled_instruction.accounts.clone(),
+            program_id_index: compiled_instruction.program_id_index as u8,
+            data: compiled_instruction.data.clone(),
             inner_instructions: Vec::new(),
             stack_height: 1,
+            path: vec![index],
+            program_data_logs: Vec::new(),
         };
 
-        for inner_instruction in inner_instruction_group.instructions.iter() {
-            match inner_instruction.stack_height {
-                Some(stack_height) => {
-                    if stack_height == 2 {
-                        parent.inner_instructions.push(StructuredInstruction {
-                            account_key_indexes: inner_instruction.accounts.clone(),
-                            program_id_index: inner_instruction.program_id_index.clone() as u8,
-                            data: inner_instruction.data.clone(),
-                            inner_instructions: Vec::new(),
-                            stack_height: 2,
-                        });
-                    } else if stack_height == 3 {
-                        if let Some(last) = parent.inner_instructions.last_mut() {
-                            last.inner_instructions.push(StructuredInstruction {
-                                account_key_indexes: inner_instruction.accounts.clone(),
-                                program_id_index: inner_instruction.program_id_index.clone() as u8,
-                                data: inner_instruction.data.clone(),
-                                inner_instructions: Vec::new(),
-                                stack_height: 3,
-                            });
-                        }
-                    } else if stack_height == 4 {
-                        if let Some(last) = parent.inner_instructions.last_mut() {
-                            if let Some(last_last) = last.inner_instructions.last_mut() {
-                                last_last.inner_instructions.push(StructuredInstruction {
-                                    account_key_indexes: inner_instruction.accounts.clone(),
-                                    program_id_index: inner_instruction.program_id_index.clone()
-                                        as u8,
-                                    data: inner_instruction.data.clone(),
-                                    inner_instructions: Vec::new(),
-                                    stack_height: 4,
-                                });
-                            }
-                        }
-                    } else if stack_height == 5 {
-                        if let Some(last) = parent.inner_instructions.last_mut() {
-                            if let Some(last_last) = last.inner_instructions.last_mut() {
-                                if let Some(last_last_last) =
-                                    last_last.inner_instructions.last_mut()
-                                {
-                                    last_last_last
-                                        .inner_instructions
-                                        .push(StructuredInstruction {
-                                            account_key_indexes: inner_instruction.accounts.clone(),
-                                            program_id_index: inner_instruction
-                                                .program_id_index
-                                                .clone()
-                                                as u8,
-                                            data: inner_instruction.data.clone(),
-                                            inner_instructions: Vec::new(),
-                                            stack_height: 5,
-                                        });
-                                }
-                            }
-                        }
+        if let Some(inner_instruction_group) = inner_instructions
+            .iter()
+            .find(|group| group.index as usize == index)
+        {
+            for inner_instruction in inner_instruction_group.instructions.iter() {
+                // transactions from before stack heights were recorded only carry direct children
+                let stack_height = inner_instruction.stack_height.unwrap_or(2);
+                let mut level = &mut parent;
+                for _ in 2..stack_height {
+                    if level.inner_instructions.is_empty() {
+                        break;
                     }
+                    level = level.inner_instructions.last_mut().unwrap();
                 }
-                None => {
-                    panic!("Stack height is None");
-                }
+                let mut path = level.path.clone();
+                path.push(level.inner_instructions.len());
+                let stack_height = level.stack_height + 1;
+                level.inner_instructions.push(StructuredInstruction {
+                    account_key_indexes: inner_instruction.accounts.clone(),
+                    program_id_index: inner_instruction.program_id_index as u8,
+                    data: inner_instruction.data.clone(),
+                    inner_instructions: Vec::new(),
+                    stack_height,
+                    path,
+                    program_data_logs: Vec::new(),
+                });
             }
         }
         formatted.push(parent);
     }
-    formatted
+    Ok(formatted)
 }
 
 pub fn filter_instructions(
@@ -138,7 +106,10 @@ pub fn filter_instructions(
         out: &mut HashMap<String, Vec<StructuredInstruction>>,
     ) {
         // 1. resolve program-ID
-        let pid = &account_keys[ix.program_id_index as usize];
+        let pid = match account_keys.get(ix.program_id_index as usize) {
+            Some(pid) => pid,
+            None => return,
+        };
 
         // 2. keep if caller asked for it
         if program_ids.contains(pid) {
@@ -159,16 +130,635 @@ pub fn filter_instructions(
     out
 }
 
-pub fn parse_token_program_transfer(instruction: &StructuredInstruction, account_keys: &Vec<String>) -> TokenProgramTransfer {
-    let accounts = &instruction.account_key_indexes;
-    let source = account_keys[accounts[0] as usize].clone();
-    let destination = account_keys[accounts[1] as usize].clone();
-    let authority = account_keys[accounts[2] as usize].clone();
-    let amount = u64::from_le_bytes(instruction.data[1..10].try_into().unwrap());
-    TokenProgramTransfer {
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+pub const TOKEN_TRANSFER_DISCRIMINATOR: u8 = 3;
+pub const TOKEN_MINT_TO_DISCRIMINATOR: u8 = 7;
+pub const TOKEN_BURN_DISCRIMINATOR: u8 = 8;
+pub const TOKEN_TRANSFER_CHECKED_DISCRIMINATOR: u8 = 12;
+pub const TOKEN_MINT_TO_CHECKED_DISCRIMINATOR: u8 = 14;
+pub const TOKEN_BURN_CHECKED_DISCRIMINATOR: u8 = 15;
+
+/// Parses a `Transfer` or `TransferChecked` instruction of the token or token-2022 program.
+pub fn parse_token_program_transfer(
+    instruction: &StructuredInstruction,
+    account_keys: &[String],
+) -> Result<TokenProgramTransfer, DecodeErrorKind> {
+    if instruction.data.first() == Some(&TOKEN_TRANSFER_CHECKED_DISCRIMINATOR) {
+        // accounts: [source, mint, destination, authority]
+        return Ok(TokenProgramTransfer {
+            source: get_account(instruction, account_keys, 0)?,
+            destination: get_account(instruction, account_keys, 2)?,
+            authority: get_account(instruction, account_keys, 3)?,
+            amount: read_u64(&instruction.data, 1)?,
+            mint: Some(get_account(instruction, account_keys, 1)?),
+        });
+    }
+    let source = get_account(instruction, account_keys, 0)?;
+    let destination = get_account(instruction, account_keys, 1)?;
+    let authority = get_account(instruction, account_keys, 2)?;
+    let amount = read_u64(&instruction.data, 1)?;
+    Ok(TokenProgramTransfer {
         source,
         destination,
         authority,
-        amount
+        amount,
+        mint: None,
+    })
+}
+
+/// Direct inner instructions of `instruction` that belong to the token or token-2022 program.
+pub fn get_token_program_instructions<'a>(
+    instruction: &'a StructuredInstruction,
+    account_keys: &[String],
+) -> Vec<&'a StructuredInstruction> {
+    instruction
+        .inner_instructions
+        .iter()
+        .filter(|inner| {
+            matches!(
+                account_keys.get(inner.program_id_index as usize).map(String::as_str),
+                Some(TOKEN_PROGRAM_ID) | Some(TOKEN_2022_PROGRAM_ID)
+            )
+        })
+        .collect()
+}
+
+/// All `Transfer`/`TransferChecked` instructions issued directly by `instruction`, in order.
+pub fn get_token_transfers(
+    instruction: &StructuredInstruction,
+    account_keys: &[String],
+) -> Result<Vec<TokenProgramTransfer>, DecodeErrorKind> {
+    get_token_program_instructions(instruction, account_keys)
+        .into_iter()
+        .filter(|inner| {
+            matches!(
+                inner.data.first(),
+                Some(&TOKEN_TRANSFER_DISCRIMINATOR) | Some(&TOKEN_TRANSFER_CHECKED_DISCRIMINATOR)
+            )
+        })
+        .map(|inner| parse_token_program_transfer(inner, account_keys))
+        .collect()
+}
+
+/// All `Transfer`/`TransferChecked` instructions below `instruction` at any depth, in order.
+/// Needed for programs that move tokens through another program, e.g. a vault.
+pub fn get_nested_token_transfers(
+    instruction: &StructuredInstruction,
+    account_keys: &[String],
+) -> Result<Vec<TokenProgramTransfer>, DecodeErrorKind> {
+    let mut transfers = Vec::new();
+    for inner in instruction.inner_instructions.iter() {
+        let is_token_program = matches!(
+            account_keys.get(inner.program_id_index as usize).map(String::as_str),
+            Some(TOKEN_PROGRAM_ID) | Some(TOKEN_2022_PROGRAM_ID)
+        );
+        if is_token_program
+            && matches!(
+                inner.data.first(),
+                Some(&TOKEN_TRANSFER_DISCRIMINATOR) | Some(&TOKEN_TRANSFER_CHECKED_DISCRIMINATOR)
+            )
+        {
+            transfers.push(parse_token_program_transfer(inner, account_keys)?);
+        }
+        transfers.extend(get_nested_token_transfers(inner, account_keys)?);
+    }
+    Ok(transfers)
+}
+
+/// Finds the transfer into (`to_account`) or out of `account` among `transfers`.
+pub fn find_token_transfer<'a>(
+    transfers: &'a [TokenProgramTransfer],
+    account: &str,
+    to_account: bool,
+) -> Result<&'a TokenProgramTransfer, DecodeErrorKind> {
+    transfers
+        .iter()
+        .find(|transfer| {
+            if to_account {
+                transfer.destination == account
+            } else {
+                transfer.source == account
+            }
+        })
+        .ok_or(DecodeErrorKind::MissingTokenTransfer {
+            position: transfers.len(),
+        })
+}
+
+/// Amount of the first `MintTo`/`MintToChecked` (or `Burn`/`BurnChecked` when `burn` is set)
+/// issued directly by `instruction`.
+pub fn get_token_mint_or_burn_amount(
+    instruction: &StructuredInstruction,
+    account_keys: &[String],
+    burn: bool,
+) -> Result<u64, DecodeErrorKind> {
+    let discriminators = if burn {
+        [TOKEN_BURN_DISCRIMINATOR, TOKEN_BURN_CHECKED_DISCRIMINATOR]
+    } else {
+        [TOKEN_MINT_TO_DISCRIMINATOR, TOKEN_MINT_TO_CHECKED_DISCRIMINATOR]
+    };
+    let token_instructions = get_token_program_instructions(instruction, account_keys);
+    let inner = token_instructions
+        .iter()
+        .find(|inner| {
+            inner
+                .data
+                .first()
+                .is_some_and(|discriminator| discriminators.contains(discriminator))
+        })
+        .ok_or(DecodeErrorKind::MissingTokenTransfer {
+            position: token_instructions.len(),
+        })?;
+    read_u64(&inner.data, 1)
+}
+
+/// Looks up the token balance of an account, preferring the post-transaction balance
+/// and falling back to the pre-transaction one for accounts closed by the transaction.
+pub fn get_token_balance(
+    transaction: &SubscribeUpdateTransaction,
+    account_index: u32,
+) -> Result<TokenBalance, DecodeErrorKind> {
+    let meta = transaction
+        .transaction
+        .as_ref()
+        .and_then(|txn| txn.meta.as_ref())
+        .ok_or(DecodeErrorKind::MissingTransactionMeta)?;
+    meta.post_token_balances
+        .iter()
+        .chain(meta.pre_token_balances.iter())
+        .find(|balance| balance.account_index == account_index)
+        .cloned()
+        .ok_or(DecodeErrorKind::MissingTokenBalance { account_index })
+}
+
+/// Lamports held by an account after the transaction.
+pub fn get_account_lamports(
+    transaction: &SubscribeUpdateTransaction,
+    account_index: u32,
+) -> Result<u64, DecodeErrorKind> {
+    let meta = transaction
+        .transaction
+        .as_ref()
+        .and_then(|txn| txn.meta.as_ref())
+        .ok_or(DecodeErrorKind::MissingTransactionMeta)?;
+    meta.post_balances
+        .get(account_index as usize)
+        .copied()
+        .ok_or(DecodeErrorKind::MissingTokenBalance { account_index })
+}
+
+/// Lamports held by an account before the transaction.
+pub fn get_account_pre_lamports(
+    transaction: &SubscribeUpdateTransaction,
+    account_index: u32,
+) -> Result<u64, DecodeErrorKind> {
+    let meta = transaction
+        .transaction
+        .as_ref()
+        .and_then(|txn| txn.meta.as_ref())
+        .ok_or(DecodeErrorKind::MissingTransactionMeta)?;
+    meta.pre_balances
+        .get(account_index as usize)
+        .copied()
+        .ok_or(DecodeErrorKind::MissingTokenBalance { account_index })
+}
+
+/// Returns the raw amount and decimals of a token balance.
+pub fn get_token_amount(balance: &TokenBalance) -> Result<(u64, u8), DecodeErrorKind> {
+    let ui_token_amount = balance
+        .ui_token_amount
+        .as_ref()
+        .ok_or(DecodeErrorKind::MissingTokenBalance {
+            account_index: balance.account_index,
+        })?;
+    let amount = ui_token_amount
+        .amount
+        .parse::<u64>()
+        .map_err(|_| DecodeErrorKind::InvalidAmount(ui_token_amount.amount.clone()))?;
+    Ok((amount, ui_token_amount.decimals as u8))
+}
+
+/// Mint, raw amount and decimals of the token account at `position` in the instruction's accounts.
+pub fn get_instruction_token_balance(
+    instruction: &StructuredInstruction,
+    transaction: &SubscribeUpdateTransaction,
+    position: usize,
+) -> Result<(String, u64, u8), DecodeErrorKind> {
+    let account_index = *instruction
+        .account_key_indexes
+        .get(position)
+        .ok_or(DecodeErrorKind::MissingAccount { position })?;
+    let balance = get_token_balance(transaction, account_index as u32)?;
+    let (amount, decimals) = get_token_amount(&balance)?;
+    Ok((balance.mint, amount, decimals))
+}
+
+pub fn get_account(
+    instruction: &StructuredInstruction,
+    account_keys: &[String],
+    position: usize,
+) -> Result<String, DecodeErrorKind> {
+    instruction
+        .account_key_indexes
+        .get(position)
+        .and_then(|index| account_keys.get(*index as usize))
+        .cloned()
+        .ok_or(DecodeErrorKind::MissingAccount { position })
+}
+
+pub fn read_bytes(data: &[u8], offset: usize, len: usize) -> Result<&[u8], DecodeErrorKind> {
+    data.get(offset..offset + len)
+        .ok_or(DecodeErrorKind::DataTooShort {
+            offset,
+            needed: len,
+            len: data.len(),
+        })
+}
+
+pub fn read_u8(data: &[u8], offset: usize) -> Result<u8, DecodeErrorKind> {
+    Ok(read_bytes(data, offset, 1)?[0])
+}
+
+pub fn read_bool(data: &[u8], offset: usize) -> Result<bool, DecodeErrorKind> {
+    Ok(read_u8(data, offset)? != 0)
+}
+
+pub fn read_u16(data: &[u8], offset: usize) -> Result<u16, DecodeErrorKind> {
+    Ok(u16::from_le_bytes(read_bytes(data, offset, 2)?.try_into().unwrap()))
+}
+
+pub fn read_u32(data: &[u8], offset: usize) -> Result<u32, DecodeErrorKind> {
+    Ok(u32::from_le_bytes(read_bytes(data, offset, 4)?.try_into().unwrap()))
+}
+
+pub fn read_u64(data: &[u8], offset: usize) -> Result<u64, DecodeErrorKind> {
+    Ok(u64::from_le_bytes(read_bytes(data, offset, 8)?.try_into().unwrap()))
+}
+
+pub fn read_i32(data: &[u8], offset: usize) -> Result<i32, DecodeErrorKind> {
+    Ok(i32::from_le_bytes(read_bytes(data, offset, 4)?.try_into().unwrap()))
+}
+
+pub fn read_i64(data: &[u8], offset: usize) -> Result<i64, DecodeErrorKind> {
+    Ok(i64::from_le_bytes(read_bytes(data, offset, 8)?.try_into().unwrap()))
+}
+
+pub fn read_u128(data: &[u8], offset: usize) -> Result<u128, DecodeErrorKind> {
+    Ok(u128::from_le_bytes(read_bytes(data, offset, 16)?.try_into().unwrap()))
+}
+
+pub fn read_pubkey(data: &[u8], offset: usize) -> Result<String, DecodeErrorKind> {
+    Ok(bs58::encode(read_bytes(data, offset, 32)?).into_string())
+}
+
+/// Reads a borsh string (u32 length prefix followed by utf-8 bytes) and returns it
+/// together with the offset right after it.
+pub fn read_string(data: &[u8], offset: usize) -> Result<(String, usize), DecodeErrorKind> {
+    let len = read_u32(data, offset)? as usize;
+    let bytes = read_bytes(data, offset + 4, len)?;
+    let string = String::from_utf8(bytes.to_vec())
+        .map_err(|_| DecodeErrorKind::InvalidString { offset })?;
+    Ok((string, offset + 4 + len))
+}
+
+/// Prefix of the self-CPI instruction Anchor's `emit_cpi!` uses to log events.
+pub const EVENT_IX_TAG: [u8; 8] = [228, 69, 165, 46, 81, 203, 154, 29];
+
+/// Finds the event emitted through a self-CPI directly below `instruction` and returns its
+/// payload, i.e. the data after the event tag and the event discriminator.
+pub fn find_event_cpi<'a>(
+    instruction: &'a StructuredInstruction,
+    discriminator: &[u8; 8],
+) -> Option<&'a [u8]> {
+    instruction
+        .inner_instructions
+        .iter()
+        .find(|inner| {
+            inner.data.get(..8) == Some(&EVENT_IX_TAG[..])
+                && inner.data.get(8..16) == Some(&discriminator[..])
+        })
+        .map(|inner| &inner.data[16..])
+}
+
+/// Payloads of every event with `discriminator` emitted through a self-CPI directly below
+/// `instruction`, in emission order.
+pub fn find_event_cpis<'a>(
+    instruction: &'a StructuredInstruction,
+    discriminator: &[u8; 8],
+) -> Vec<&'a [u8]> {
+    instruction
+        .inner_instructions
+        .iter()
+        .filter(|inner| {
+            inner.data.get(..8) == Some(&EVENT_IX_TAG[..])
+                && inner.data.get(8..16) == Some(&discriminator[..])
+        })
+        .map(|inner| &inner.data[16..])
+        .collect()
+}
+
+/// Every `Program data:` line of the transaction, base64-decoded and attributed to the program
+/// and instruction that logged it by following the `invoke [n]` / `success` nesting of the logs.
+/// Inner invocations are numbered from the `invoke [n]` lines the same way instruction paths
+/// are. Top-level ones are matched to the next compiled instruction of the invoked program, as
+/// precompiles (ed25519, secp256k1) run without logging an invoke. Nothing is found past the
+/// point where the runtime truncated the logs.
+pub fn get_program_data_logs(
+    transaction: &SubscribeUpdateTransaction,
+    account_keys: &[String],
+) -> Result<Vec<ProgramDataLog>, DecodeErrorKind> {
+    let (message, meta) = get_message_and_meta(transaction)?;
+    let mut top_level_program_ids = message
+        .instructions
+        .iter()
+        .enumerate()
+        .map(|(index, ix)| (index, account_keys.get(ix.program_id_index as usize)));
+    let mut data_logs = Vec::new();
+    // program and path of every running invocation, with the number of invocations it made so
+    // far
+    let mut stack: Vec<(&str, Vec<usize>, usize)> = Vec::new();
+    for log in meta.log_messages.iter() {
+        if let Some(data) = log.strip_prefix("Program data: ") {
+            if let Some((program_id, path, _)) = stack.last() {
+                for payload in data.split_whitespace() {
+                    if let Ok(decoded) = BASE64_STANDARD.decode(payload) {
+                        data_logs.push(ProgramDataLog {
+                            program_id: program_id.to_string(),
+                            instruction_path: path.clone(),
+                            data: decoded,
+                        });
+                    }
+                }
+            }
+            continue;
+        }
+        let words: Vec<&str> = log.split_whitespace().collect();
+        if words.len() < 3 || words[0] != "Program" || words[1].ends_with(':') {
+            continue;
+        }
+        if words[2] == "invoke" {
+            let path = match stack.last_mut() {
+                Some((_, parent, invocations)) => {
+                    let mut path = parent.clone();
+                    path.push(*invocations);
+                    *invocations += 1;
+                    path
+                }
+                None => match top_level_program_ids
+                    .find(|(_, program_id)| program_id.is_some_and(|id| id == words[1]))
+                {
+                    Some((index, _)) => vec![index],
+                    // the logs do not follow the message, attributing anything further would
+                    // be a guess
+                    None => break,
+                },
+            };
+            stack.push((words[1], path, 0));
+        } else if (words[2] == "success" && words.len() == 3) || words[2] == "failed:" {
+            stack.pop();
+        }
+    }
+    Ok(data_logs)
+}
+
+/// Hands every log in `data_logs` to the instruction at its path, so decoders can read the
+/// events of an instruction without going back to the transaction's logs.
+pub fn attach_program_data_logs(
+    instructions: &mut [StructuredInstruction],
+    data_logs: Vec<ProgramDataLog>,
+) {
+    for log in data_logs {
+        let Some((&outer, inner_path)) = log.instruction_path.split_first() else {
+            continue;
+        };
+        let mut level = instructions.iter_mut().find(|ix| ix.path == [outer]);
+        for &index in inner_path {
+            level = level.and_then(|ix| ix.inner_instructions.get_mut(index));
+        }
+        if let Some(instruction) = level {
+            instruction.program_data_logs.push(log.data);
+        }
+    }
+}
+
+/// Base64-decoded `Program data:` lines logged by `instruction` itself, in order, for programs
+/// that emit events with `sol_log_data` instead of a self-CPI.
+pub fn find_program_data_logs(instruction: &StructuredInstruction) -> &[Vec<u8>] {
+    &instruction.program_data_logs
+}
+
+/// Payload of the event with `discriminator` emitted by `instruction`, taken from its self-CPI
+/// or, when there is none, from its `Program data:` logs, which is where programs (and older
+/// versions of them) that use `emit!` rather than `emit_cpi!` put their events.
+pub fn find_event<'a>(
+    instruction: &'a StructuredInstruction,
+    discriminator: &[u8; 8],
+) -> Option<&'a [u8]> {
+    find_event_cpi(instruction, discriminator).or_else(|| {
+        find_program_data_logs(instruction)
+            .iter()
+            .find(|data| data.starts_with(discriminator))
+            .map(|data| &data[8..])
+    })
+}
+
+/// Payloads of every event with `discriminator` emitted by `instruction`, in emission order,
+/// taken from its self-CPIs or, when there are none, from its `Program data:` logs.
+pub fn find_events<'a>(
+    instruction: &'a StructuredInstruction,
+    discriminator: &[u8; 8],
+) -> Vec<&'a [u8]> {
+    let events = find_event_cpis(instruction, discriminator);
+    if !events.is_empty() {
+        return events;
+    }
+    find_program_data_logs(instruction)
+        .iter()
+        .filter(|data| data.starts_with(discriminator))
+        .map(|data| &data[8..])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yellowstone_grpc_proto::prelude::{
+        CompiledInstruction, InnerInstruction, InnerInstructions, SubscribeUpdateTransactionInfo,
+        Transaction,
+    };
+
+    const PAYER: &str = "5Q544fKrFoe6tsEbD7S8EmxGTJYAKtTVhAW5Q5pge4j1";
+    const ED25519: &str = "Ed25519SigVerify111111111111111111111111111";
+    const PUMP_AMM: &str = "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA";
+    const PUMP_FUN: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
+
+    /// A transaction signed by `PAYER` calling the precompile, then pump-amm (which calls
+    /// pump.fun) and pump.fun, with `logs` as its log messages.
+    fn transaction(logs: &[&str]) -> SubscribeUpdateTransaction {
+        let account_keys = [PAYER, ED25519, PUMP_AMM, PUMP_FUN]
+            .iter()
+            .map(|key| bs58::decode(key).into_vec().unwrap())
+            .collect();
+        let compiled = |program_id_index| CompiledInstruction {
+            program_id_index,
+            accounts: vec![0],
+            data: vec![program_id_index as u8],
+        };
+        SubscribeUpdateTransaction {
+            transaction: Some(SubscribeUpdateTransactionInfo {
+                transaction: Some(Transaction {
+                    message: Some(Message {
+                        account_keys,
+                        instructions: vec![compiled(1), compiled(2), compiled(3)],
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                meta: Some(TransactionStatusMeta {
+                    inner_instructions: vec![InnerInstructions {
+                        index: 1,
+                        instructions: vec![InnerInstruction {
+                            program_id_index: 3,
+                            accounts: vec![0],
+                            data: vec![3],
+                            stack_height: Some(2),
+                        }],
+                    }],
+                    log_messages: logs.iter().map(|log| log.to_string()).collect(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn data_log(data: &[u8]) -> String {
+        format!("Program data: {}", BASE64_STANDARD.encode(data))
+    }
+
+    fn nested_logs() -> Vec<String> {
+        vec![
+            format!("Program {PUMP_AMM} invoke [1]"),
+            "Program log: Instruction: Buy".to_string(),
+            data_log(&[1]),
+            format!("Program {PUMP_FUN} invoke [2]"),
+            data_log(&[2]),
+            format!("Program {PUMP_FUN} consumed 2000 of 190000 compute units"),
+            format!("Program {PUMP_FUN} success"),
+            data_log(&[3]),
+            format!("Program {PUMP_AMM} success"),
+            format!("Program {PUMP_FUN} invoke [1]"),
+            data_log(&[4]),
+            format!("Program {PUMP_FUN} success"),
+        ]
+    }
+
+    #[test]
+    fn program_data_logs_follow_the_invoke_nesting_past_precompiles() {
+        let logs = nested_logs();
+        let tx = transaction(&logs.iter().map(String::as_str).collect::<Vec<_>>());
+        let account_keys = get_account_keys(&tx).unwrap();
+        let data_logs = get_program_data_logs(&tx, &account_keys).unwrap();
+
+        let found: Vec<(&str, &[usize], &[u8])> = data_logs
+            .iter()
+            .map(|log| {
+                (log.program_id.as_str(), log.instruction_path.as_slice(), log.data.as_slice())
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (PUMP_AMM, &[1][..], &[1][..]),
+                (PUMP_FUN, &[1, 0][..], &[2][..]),
+                (PUMP_AMM, &[1][..], &[3][..]),
+                (PUMP_FUN, &[2][..], &[4][..]),
+            ]
+        );
+    }
+
+    #[test]
+    fn program_data_logs_are_attached_to_their_instruction() {
+        let logs = nested_logs();
+        let tx = transaction(&logs.iter().map(String::as_str).collect::<Vec<_>>());
+        let account_keys = get_account_keys(&tx).unwrap();
+        let mut instructions = structure_all_instructions(&tx).unwrap();
+        attach_program_data_logs(
+            &mut instructions,
+            get_program_data_logs(&tx, &account_keys).unwrap(),
+        );
+
+        assert!(find_program_data_logs(&instructions[0]).is_empty());
+        assert_eq!(find_program_data_logs(&instructions[1]), [vec![1], vec![3]]);
+        assert_eq!(find_program_data_logs(&instructions[1].inner_instructions[0]), [vec![2]]);
+        assert_eq!(find_program_data_logs(&instructions[2]), [vec![4]]);
+    }
+
+    #[test]
+    fn program_data_logs_stop_where_the_logs_leave_the_message() {
+        let logs = [
+            format!("Program {PUMP_FUN} invoke [1]"),
+            data_log(&[4]),
+            format!("Program {PUMP_FUN} success"),
+            // pump.fun has no compiled instruction left to match
+            format!("Program {PUMP_FUN} invoke [1]"),
+            data_log(&[5]),
+            format!("Program {PUMP_FUN} success"),
+        ];
+        let tx = transaction(&logs.iter().map(String::as_str).collect::<Vec<_>>());
+        let account_keys = get_account_keys(&tx).unwrap();
+        let data_logs = get_program_data_logs(&tx, &account_keys).unwrap();
+
+        assert_eq!(data_logs.len(), 1);
+        assert_eq!(data_logs[0].instruction_path, [2]);
+        assert_eq!(data_logs[0].data, [4]);
+    }
+
+    #[test]
+    fn find_event_prefers_the_self_cpi_over_the_logs() {
+        let discriminator = [1, 2, 3, 4, 5, 6, 7, 8];
+        let mut instruction = StructuredInstruction {
+            account_key_indexes: vec![],
+            program_id_index: 2,
+            data: vec![],
+            inner_instructions: vec![],
+            stack_height: 1,
+            path: vec![0],
+            program_data_logs: vec![[&discriminator[..], &[9]].concat()],
+        };
+        assert_eq!(find_event(&instruction, &discriminator), Some(&[9][..]));
+        assert_eq!(find_event(&instruction, &[0; 8]), None);
+
+        instruction.inner_instructions.push(StructuredInstruction {
+            account_key_indexes: vec![],
+            program_id_index: 2,
+            data: [&EVENT_IX_TAG[..], &discriminator[..], &[10]].concat(),
+            inner_instructions: vec![],
+            stack_height: 2,
+            path: vec![0, 0],
+            program_data_logs: vec![],
+        });
+        assert_eq!(find_event(&instruction, &discriminator), Some(&[10][..]));
+    }
+
+    #[test]
+    fn find_events_takes_every_logged_event_when_there_is_no_self_cpi() {
+        let discriminator = [1, 2, 3, 4, 5, 6, 7, 8];
+        let instruction = StructuredInstruction {
+            account_key_indexes: vec![],
+            program_id_index: 2,
+            data: vec![],
+            inner_instructions: vec![],
+            stack_height: 1,
+            path: vec![0],
+            program_data_logs: vec![
+                [&discriminator[..], &[9]].concat(),
+                [&[0; 8][..], &[10]].concat(),
+                [&discriminator[..], &[11]].concat(),
+            ],
+        };
+        assert_eq!(find_events(&instruction, &discriminator), [&[9][..], &[11][..]]);
     }
 }
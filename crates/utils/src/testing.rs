@@ -0,0 +1,67 @@
+//! Fixtures shared by the parser tests, built from the addresses and transactions they decode.
+//! Parser crates enable them with the `testing` feature in their dev-dependencies.
+
+use crate::EVENT_IX_TAG;
+use types::StructuredInstruction;
+use yellowstone_grpc_proto::prelude::{
+    SubscribeUpdateTransaction, SubscribeUpdateTransactionInfo, TokenBalance,
+    TransactionStatusMeta, UiTokenAmount,
+};
+
+pub const USER: &str = "7u7cD7NxcZEuzRCBaYo8uVpotRdqZwez47vvuwzCov43";
+pub const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+pub const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qJxX1dhFyd5uzdQzmQkgtSBBCT";
+
+/// Raw bytes of a base58 address, as event and instruction data carry it.
+pub fn pubkey(address: &str) -> [u8; 32] {
+    bs58::decode(address).into_vec().unwrap().try_into().unwrap()
+}
+
+/// Placeholder keys for the accounts a test does not look at.
+pub fn account_keys(count: usize) -> Vec<String> {
+    (0..count).map(|index| format!("account{index}")).collect()
+}
+
+/// The self-CPI at `path` through which `emit_cpi!` logs `event`, given as its discriminator
+/// followed by its payload, from the program at `program_id_index`.
+pub fn event_cpi(program_id_index: u8, path: Vec<usize>, event: &[u8]) -> StructuredInstruction {
+    StructuredInstruction {
+        account_key_indexes: vec![program_id_index],
+        program_id_index,
+        data: [&EVENT_IX_TAG[..], event].concat(),
+        inner_instructions: Vec::new(),
+        stack_height: path.len() as u8,
+        path,
+        program_data_logs: Vec::new(),
+    }
+}
+
+/// A transaction whose only meta is the post balances of its token accounts, given as
+/// (account index, mint, amount, decimals).
+pub fn token_balances_transaction(
+    balances: &[(u32, &str, u64, u32)],
+) -> SubscribeUpdateTransaction {
+    let post_token_balances = balances
+        .iter()
+        .map(|&(account_index, mint, amount, decimals)| TokenBalance {
+            account_index,
+            mint: mint.to_string(),
+            ui_token_amount: Some(UiTokenAmount {
+                amount: amount.to_string(),
+                decimals,
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+        .collect();
+    SubscribeUpdateTransaction {
+        transaction: Some(SubscribeUpdateTransactionInfo {
+            meta: Some(TransactionStatusMeta {
+                post_token_balances,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
@@ -3,9 +3,7 @@ use {
     clap::Parser as ClapParser,
     futures::{future::TryFutureExt, sink::SinkExt, stream::StreamExt},
     log::{error, info},
-    parser_core::TransactionParser,
-    serde::{Deserialize, Serialize},
-    serde_json,
+    parser_core::{AnchorIdlInstructionParser, ParserEnum, TransactionParser},
     std::{collections::HashMap, env, sync::Arc, time::Duration},
     tokio::sync::Mutex,
     tonic::transport::channel::ClientTlsConfig,
@@ -21,10 +19,6 @@ use {
 
 type TxnFilterMap = HashMap<String, SubscribeRequestFilterTransactions>;
 
-const PUMP_AMM_PROGRAM_ID: &str = "pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA";
-const PUMP_FUN_PROGRAM_ID: &str = "6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P";
-const RAYDIUM_PROGRAM_ID: &str = "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8";
-
 #[derive(Debug, Clone, ClapParser)]
 #[clap(author, version, about)]
 struct Args {
@@ -33,6 +27,9 @@ struct Args {
 
     #[clap(long, help = "X-Token")]
     x_token: String,
+
+    #[clap(long, help = "Anchor IDL JSON file of an extra program to decode, repeatable")]
+    idl: Vec<String>,
 }
 
 impl Args {
@@ -49,7 +46,7 @@ impl Args {
             .map_err(Into::into)
     }
 
-    pub fn get_txn_updates(&self) -> SubscribeRequest {
+    pub fn get_txn_updates(&self, program_ids: Vec<String>) -> SubscribeRequest {
         let mut transactions: TxnFilterMap = TxnFilterMap::new();
 
         transactions.insert(
@@ -57,11 +54,7 @@ impl Args {
             SubscribeRequestFilterTransactions {
                 vote: Some(false),
                 failed: Some(false),
-                account_include: vec![
-                    PUMP_AMM_PROGRAM_ID.to_string(),
-                    PUMP_FUN_PROGRAM_ID.to_string(),
-                    //RAYDIUM_PROGRAM_ID.to_string(),
-                ],
+                account_include: program_ids,
                 account_exclude: vec![],
                 account_required: vec![],
                 signature: None,
@@ -107,7 +100,12 @@ async fn main() -> anyhow::Result<()> {
         attempts_since_success: 0,
     }));
 
-    let parser = TransactionParser::new();
+    let mut parser = TransactionParser::new();
+    for path in &args.idl {
+        let idl_parser = AnchorIdlInstructionParser::from_idl_file(path)?;
+        info!("Decoding {} from IDL {}", idl_parser.idl().name, path);
+        parser.register(ParserEnum::AnchorIdl(idl_parser));
+    }
 
     // The default exponential backoff strategy intervals:
     // [500ms, 750ms, 1.125s, 1.6875s, 2.53125s, 3.796875s, 5.6953125s,
@@ -146,11 +144,11 @@ async fn main() -> anyhow::Result<()> {
             };
             info!("Connected");
 
-            let mut request = args.get_txn_updates();
-            if let Some(slot) = maybe_slot {
-                if use_from_slot {
-                    request.from_slot = Some(slot);
-                }
+            let mut request = args.get_txn_updates(parser.get_program_ids());
+            if let Some(slot) = maybe_slot
+                && use_from_slot
+            {
+                request.from_slot = Some(slot);
             }
 
             geyser_subscribe(client, request, state.clone(), parser)
@@ -226,13 +224,18 @@ async fn geyser_subscribe(
                             timestamp,
                         );
                         let decoded_txn = parser.decode_transaction(&upd_clone);
-                        if decoded_txn.is_empty() {
-                            if has_balance_change(&upd_clone) {
-                                println!(
-                                    "https://solscan.io/tx/{}",
-                                    bs58::encode(&raw_signature).into_string()
-                                );
-                            }
+                        for decode_error in decoded_txn.errors.iter() {
+                            error!(
+                                "{} in {}",
+                                decode_error,
+                                bs58::encode(&raw_signature).into_string()
+                            );
+                        }
+                        if decoded_txn.events.is_empty() && has_balance_change(&upd_clone) {
+                            println!(
+                                "https://solscan.io/tx/{}",
+                                bs58::encode(&raw_signature).into_string()
+                            );
                         }
                     }
                     Some(UpdateOneof::Ping(_)) => {
@@ -272,42 +275,44 @@ async fn geyser_subscribe(
 }
 
 pub fn has_balance_change(transaction: &SubscribeUpdateTransaction) -> bool {
-    let meta = transaction.clone().transaction.unwrap().meta.unwrap();
-    let pre_token_balances = meta.pre_token_balances;
-    let post_token_balances = meta.post_token_balances;
+    let Some(meta) = transaction.transaction.as_ref().and_then(|txn| txn.meta.as_ref()) else {
+        return false;
+    };
+    let pre_token_balances = &meta.pre_token_balances;
+    let post_token_balances = &meta.post_token_balances;
     let mut has_balance_c = false;
-    for balance in pre_token_balances.clone() {
-        let mint = balance.mint;
+    for balance in pre_token_balances.iter() {
+        let mint = &balance.mint;
         let owner = balance.account_index;
         let post_balance = post_token_balances
             .iter()
-            .find(|post_balance| post_balance.mint == mint && post_balance.account_index == owner);
-        match post_balance {
-            Some(post_balance) => {
-                let pre_amount = balance.ui_token_amount.unwrap().ui_amount;
-                let post_amount = post_balance.clone().ui_token_amount.unwrap().ui_amount;
-                if pre_amount != post_amount {
-                    has_balance_c = true;
-                }
+            .find(|post_balance| post_balance.mint == *mint && post_balance.account_index == owner);
+        if let Some(post_balance) = post_balance {
+            let (Some(pre_amount), Some(post_amount)) =
+                (&balance.ui_token_amount, &post_balance.ui_token_amount)
+            else {
+                return false;
+            };
+            if pre_amount.ui_amount != post_amount.ui_amount {
+                has_balance_c = true;
             }
-            None => {}
         }
     }
-    for balance in post_token_balances {
-        let mint = balance.mint;
+    for balance in post_token_balances.iter() {
+        let mint = &balance.mint;
         let owner = balance.account_index;
         let pre_balance = pre_token_balances
             .iter()
-            .find(|pre_balance| pre_balance.mint == mint && pre_balance.account_index == owner);
-        match pre_balance {
-            Some(pre_balance) => {
-                let post_amount = balance.ui_token_amount.unwrap().ui_amount;
-                let pre_amount = pre_balance.clone().ui_token_amount.unwrap().ui_amount;
-                if pre_amount != post_amount {
-                    has_balance_c = true;
-                }
+            .find(|pre_balance| pre_balance.mint == *mint && pre_balance.account_index == owner);
+        if let Some(pre_balance) = pre_balance {
+            let (Some(post_amount), Some(pre_amount)) =
+                (&balance.ui_token_amount, &pre_balance.ui_token_amount)
+            else {
+                return false;
+            };
+            if pre_amount.ui_amount != post_amount.ui_amount {
+                has_balance_c = true;
             }
-            None => {}
         }
     }
     has_balance_c